@@ -0,0 +1,62 @@
+use atat::blocking::AtatClient;
+use embedded_nal::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::{
+    client::Device, command::psn::GetPDPContextAddress, config::CellularConfig, error::Error,
+    network::ContextId,
+};
+
+/// Addresses assigned by the network to a PDP context, see
+/// [`ip_address`](Device::ip_address)
+///
+/// Dual stack contexts have both an IPv4 and an IPv6 address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PdpAddress {
+    pub ipv4: Option<Ipv4Addr>,
+    pub ipv6: Option<Ipv6Addr>,
+}
+
+impl PdpAddress {
+    /// The IPv4 address, or the IPv6 address of IPv6 only contexts
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.ipv4
+            .map(IpAddr::V4)
+            .or_else(|| self.ipv6.map(IpAddr::V6))
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Get the addresses the network assigned to the PDP context `cid`
+    /// (+CGPADDR)
+    ///
+    /// Fails with [`Error::NotConnected`] if the context is not active.
+    pub fn ip_address(&mut self, cid: ContextId) -> Result<PdpAddress, Error> {
+        if !self.network.active_contexts.contains(&cid) {
+            return Err(Error::NotConnected);
+        }
+
+        let response = self.send_at(&GetPDPContextAddress { cid })?;
+
+        let mut address = PdpAddress {
+            ipv4: None,
+            ipv6: None,
+        };
+        for ip in response.addresses() {
+            match ip {
+                IpAddr::V4(ip) => address.ipv4 = Some(ip),
+                IpAddr::V6(ip) => address.ipv6 = Some(ip),
+            }
+        }
+
+        if address.ip().is_none() {
+            return Err(Error::NotConnected);
+        }
+        Ok(address)
+    }
+}
@@ -0,0 +1,137 @@
+use atat::blocking::AtatClient;
+use core::ops::{BitOr, BitOrAssign};
+
+use crate::{
+    client::Device,
+    command::network_service::{
+        responses::BandMasks, types::BandMaskRat, GetBandMask, SetBandMask,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+};
+
+/// Set of LTE bands 1 to 64, as programmed with +UBANDMASK
+///
+/// # Examples
+///
+/// ```ignore
+/// let bands = BandMask::band(3) | BandMask::band(20);
+/// modem.set_bands(BandMaskRat::LteCatM1, bands)?;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BandMask(u64);
+
+impl BandMask {
+    /// No bands, which prevents registration
+    pub const EMPTY: Self = Self(0);
+    /// All bands
+    pub const ALL: Self = Self(u64::MAX);
+
+    /// Bitmask with bit 0 being band 1
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Only the LTE band `band`. Bands outside of 1 to 64 are ignored.
+    pub const fn band(band: u8) -> Self {
+        Self::EMPTY.with(band)
+    }
+
+    /// Add the LTE band `band`. Bands outside of 1 to 64 are ignored.
+    #[must_use]
+    pub const fn with(self, band: u8) -> Self {
+        match band {
+            1..=64 => Self(self.0 | 1 << (band - 1)),
+            _ => self,
+        }
+    }
+
+    pub const fn contains(&self, band: u8) -> bool {
+        matches!(band, 1..=64) && self.0 & 1 << (band - 1) != 0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the bands in the set
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (1..=64).filter(|band| self.contains(*band))
+    }
+}
+
+impl BitOr for BandMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for BandMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Restrict the bands the module may use on `rat` (+UBANDMASK)
+    ///
+    /// Fails with [`Error::EmptyBandMask`] if `bands` is empty, as the module
+    /// could not register at all. Depending on the module, the new bands only
+    /// take effect after a reboot.
+    pub fn set_bands(&mut self, rat: BandMaskRat, bands: BandMask) -> Result<(), Error> {
+        if bands.is_empty() {
+            return Err(Error::EmptyBandMask);
+        }
+
+        self.send_at(&SetBandMask {
+            rat,
+            bitmask1: bands.bits(),
+            bitmask2: None,
+        })?;
+        Ok(())
+    }
+
+    /// Get the bands the module may use on `rat` (+UBANDMASK?)
+    pub fn bands(&mut self, rat: BandMaskRat) -> Result<BandMask, Error> {
+        let BandMasks { masks } = self.send_at(&GetBandMask)?;
+
+        masks
+            .iter()
+            .find(|mask| mask.rat == rat as u8)
+            .map(|mask| BandMask::from_bits(mask.bitmask1))
+            .ok_or(Error::Generic(GenericError::Unsupported))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn band_mask() {
+        let bands = BandMask::band(3) | BandMask::band(20);
+        assert_eq!(bands.bits(), 0b100 | 1 << 19);
+        assert!(bands.contains(20));
+        assert!(!bands.contains(1));
+        assert_eq!(BandMask::band(65), BandMask::EMPTY);
+        assert!(!BandMask::ALL.contains(0));
+
+        let mut iter = bands.iter();
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(20));
+        assert_eq!(iter.next(), None);
+    }
+}
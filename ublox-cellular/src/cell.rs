@@ -0,0 +1,106 @@
+use atat::blocking::AtatClient;
+use heapless::Vec;
+
+use crate::{
+    client::Device,
+    command::network_service::{
+        responses::{CellInfoEntry, CellInfoList, MAX_CELLS},
+        GetCellInfo,
+    },
+    config::CellularConfig,
+    error::Error,
+    operator::{Operator, Plmn},
+};
+
+/// Identity and signal level of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cell {
+    pub plmn: Option<Plmn>,
+    /// Location Area Code, or Tracking Area Code for LTE
+    pub lac: Option<u32>,
+    pub cell_id: Option<u32>,
+    /// Received signal level in dBm, if reported
+    pub rssi: Option<i16>,
+}
+
+/// Snapshot of the cell environment, as returned by
+/// [`cell_info`](Device::cell_info)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellInfo {
+    pub serving: Cell,
+    /// Neighbour cells, empty if the module does not report them
+    pub neighbors: Vec<Cell, MAX_CELLS>,
+}
+
+impl From<&CellInfoEntry> for Cell {
+    fn from(entry: &CellInfoEntry) -> Self {
+        let rssi = entry.level.map(|level| match entry.cell_type {
+            // RxLev 0 is below -110 dBm, in steps of 1 dB
+            0 | 1 => level as i16 - 111,
+            // RSCP level 0 is below -120 dBm, in steps of 1 dB
+            _ => level as i16 - 121,
+        });
+
+        Self {
+            plmn: Some(Plmn::new(entry.mcc, entry.mnc)),
+            lac: Some(entry.lac),
+            cell_id: Some(entry.ci),
+            rssi,
+        }
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Get the serving cell and neighbour cells (+UCELLINFO)
+    ///
+    /// Modules that don't report the cell environment, e.g. LTE only modules,
+    /// only return the serving cell, from the last registration status.
+    pub fn cell_info(&mut self) -> Result<CellInfo, Error> {
+        match self.send_at(&GetCellInfo) {
+            Ok(CellInfoList { cells }) => {
+                // Serving cells are reported as type 0 (GSM) or 2 (UMTS)
+                if let Some(serving) = cells.iter().find(|c| matches!(c.cell_type, 0 | 2)) {
+                    return Ok(CellInfo {
+                        serving: serving.into(),
+                        neighbors: cells
+                            .iter()
+                            .filter(|c| !matches!(c.cell_type, 0 | 2))
+                            .map(Cell::from)
+                            .collect(),
+                    });
+                }
+            }
+            Err(Error::Uninitialized) => return Err(Error::Uninitialized),
+            Err(e) => debug!("Cell environment unavailable: {:?}", e),
+        }
+
+        let plmn = match self.operator()? {
+            Operator::Registered { plmn, .. } => plmn,
+            Operator::None => None,
+        };
+        let cgi = &self.network.status.cgi;
+
+        Ok(CellInfo {
+            serving: Cell {
+                plmn,
+                lac: cgi
+                    .lac
+                    .as_ref()
+                    .and_then(|lac| u32::from_str_radix(lac, 16).ok()),
+                cell_id: cgi
+                    .cell_id
+                    .as_ref()
+                    .and_then(|ci| u32::from_str_radix(ci, 16).ok()),
+                rssi: None,
+            },
+            neighbors: Vec::new(),
+        })
+    }
+}
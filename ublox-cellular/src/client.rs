@@ -1,44 +1,58 @@
 use atat::{blocking::AtatClient, AtatUrcChannel, UrcSubscription};
-use embassy_time::Duration;
+use embassy_time::{Duration, Instant};
 use ublox_sockets::SocketSet;
 
 use crate::{
     blocking_timer::BlockingTimer,
-    command::device_lock::{responses::PinStatus, types::PinStatusCode, GetPinStatus},
+    command::device_lock::{
+        responses::PinStatus,
+        types::{PinStatusCode, SimStatusReporting},
+        GetPinStatus, SetPin, SetSimStatusReporting,
+    },
     command::{
         control::{
-            types::{Circuit108Behaviour, Circuit109Behaviour, FlowControl},
-            SetCircuit108Behaviour, SetCircuit109Behaviour, SetFlowControl,
+            types::{BaudRate, Circuit108Behaviour, Circuit109Behaviour, FlowControl},
+            SetCircuit108Behaviour, SetCircuit109Behaviour, SetDataRate, SetFlowControl,
         },
         ip_transport_layer,
         mobile_control::{
-            types::{AutomaticTimezone, Functionality, ResetMode, TerminationErrorMode},
-            SetAutomaticTimezoneUpdate, SetModuleFunctionality, SetReportMobileTerminationError,
+            types::{
+                AutomaticTimezone, Functionality, GreetingTextMode, PowerMode, ResetMode,
+                TerminationErrorMode, TimeZoneReporting,
+            },
+            SetAutomaticTimezoneUpdate, SetGreetingText, SetModuleFunctionality,
+            SetReportMobileTerminationError, SetTimeZoneReporting,
         },
         network_service, psn,
-        system_features::{types::PowerSavingMode, SetPowerSavingControl},
-        Urc,
+        system_features::{
+            types::{EdrxMode, PowerSavingMode, PsmMode, PsmReporting, Seconds},
+            GetEdrxDynamicParameters, GetTemperature, SetEdrxSettings, SetPowerSavingControl,
+            SetPowerSavingMode, SetPsmReporting,
+        },
+        RawCommand, RawResponse, Urc, GREETING, MAX_RAW_COMMAND,
     },
     command::{
-        general::{GetCCID, GetFirmwareVersion, GetModelId},
+        general::{GetCCID, GetFirmwareVersion, GetIMEI, GetModelId},
         gpio::{
             types::{GpioInPull, GpioMode, GpioOutValue},
             SetGpioConfiguration,
         },
         network_service::{
             responses::{OperatorSelection, SignalQuality},
-            types::OperatorSelectionMode,
-            GetOperatorSelection, GetSignalQuality, SetOperatorSelection,
+            types::{OperatorSelectionMode, RadioAccessTechnologySelected, RatAct},
+            GetOperatorSelection, GetSignalQuality, GetSignalStrength, SetOperatorSelection,
+            SetRadioAccessTechnology,
         },
-        psn::{types::PSEventReportingMode, SetPacketSwitchedEventReporting},
+        psn::SetPacketSwitchedEventReporting,
     },
-    config::CellularConfig,
+    config::{self, CellularConfig, ConfigCheck, EdrxStatus, RecoveryLevel},
     error::{Error, GenericError},
-    network::{AtTx, Network},
+    module::Capability,
+    network::{AtTx, Event, Network, TimeoutMs},
     power::PowerState,
-    registration::ConnectionState,
+    registration::{ConnectionState, Status as RegistrationStatus},
     services::data::ContextState,
-    UbloxCellularBuffers, UbloxCellularIngress, UbloxCellularUrcChannel,
+    signal, UbloxCellularBuffers, UbloxCellularIngress, UbloxCellularUrcChannel,
 };
 use ip_transport_layer::{types::HexMode, SetHexMode};
 use network_service::{types::NetworkRegistrationUrcConfig, SetNetworkRegistrationStatus};
@@ -50,6 +64,24 @@ use psn::{
 pub(crate) const URC_CAPACITY: usize = 3;
 pub(crate) const URC_SUBSCRIBERS: usize = 2;
 
+/// Interval between [`Device::spin`] calls in [`Device::connect_blocking`]
+const CONNECT_SPIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval in which the SIM is polled for while it is removed
+const SIM_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// +CME ERROR code of a missing SIM
+const CME_SIM_NOT_INSERTED: u16 = 10;
+
+/// +CME ERROR code of a SIM that is still initializing
+const CME_SIM_BUSY: u16 = 14;
+
+/// Interval in which the SIM status is queried while the SIM is busy
+const SIM_BUSY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// +CME ERROR code of a command the module does not support
+const CME_OPERATION_NOT_SUPPORTED: u16 = 4;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum State {
@@ -59,6 +91,9 @@ pub enum State {
     AtInitialized,
     /// Device is fully initialized
     FullyInitialized,
+    /// The SIM has been removed, and is polled for until it is inserted
+    /// again, after which the device is initialized again
+    WaitingForSim,
 }
 
 pub struct Device<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize> {
@@ -116,10 +151,20 @@ where
     Config: CellularConfig,
 {
     pub fn new(client: AtCl, urc_channel: &'buf AtUrcCh, config: Config) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = ConfigCheck::<Config>::ALL;
+
         let network_urc_subscription = urc_channel.subscribe().unwrap();
         Self {
             config,
-            network: Network::new(AtTx::new(client, network_urc_subscription)),
+            network: Network::new(
+                AtTx::new(client, network_urc_subscription, Config::AT_RETRIES),
+                Config::REGISTRATION_TIMEOUT,
+                Config::MAX_ATTACH_RETRIES,
+                Config::RECONNECT_BACKOFF_BASE,
+                Config::RECONNECT_BACKOFF_MAX,
+                Config::ROAMING_ALLOWED,
+            ),
             state: State::Off,
             power_state: PowerState::Off,
             sockets: None,
@@ -141,18 +186,13 @@ where
     /// # Examples
     ///
     /// ```ignore
-    /// use ublox_cellular::sockets::SocketSet;
-    ///
     /// const MAX_SOCKET_COUNT: usize = 1;
     /// const SOCKET_RING_BUFFER_LEN: usize = 1024;
     ///
-    /// static mut SOCKET_SET: Option<SocketSet<MAX_SOCKET_COUNT, SOCKET_RING_BUFFER_LEN>> = None;
-    ///
-    /// unsafe {
-    ///     SOCKET_SET = Some(SocketSet::new());
-    /// }
-    ///
-    /// modem.set_socket_storage(unsafe { SOCKET_SET.as_mut().unwrap() });
+    /// modem.set_socket_storage(ublox_cellular::socket_set!(
+    ///     MAX_SOCKET_COUNT,
+    ///     SOCKET_RING_BUFFER_LEN
+    /// ));
     /// ```
     pub fn set_socket_storage(&mut self, socket_set: &'static mut SocketSet<N, L>) {
         socket_set.prune();
@@ -166,6 +206,128 @@ where
     pub fn signal_strength(&mut self) -> Result<SignalQuality, Error> {
         self.send_at(&GetSignalQuality)
     }
+
+    /// Read the signal quality metrics of the serving cell
+    ///
+    /// Unknown values reported by the module are mapped to `None`.
+    pub fn signal_quality(&mut self) -> Result<signal::SignalQuality, Error> {
+        let csq = self.send_at(&GetSignalStrength)?;
+        // +CESQ is not supported by all modules, and only carries the LTE
+        // metrics when the serving cell is an E-UTRA cell.
+        let cesq = self.send_at(&GetSignalQuality).ok();
+
+        Ok(signal::SignalQuality::new(&csq, cesq.as_ref()))
+    }
+    /// Read the eDRX parameters currently granted by the network
+    ///
+    /// These often differ from the values requested through
+    /// [`CellularConfig::EDRX`].
+    pub fn edrx_status(&mut self) -> Result<EdrxStatus, Error> {
+        self.require(Capability::Edrx)?;
+        Ok(self.send_at(&GetEdrxDynamicParameters)?.into())
+    }
+
+    /// Read the internal temperature of the module (+UTEMP), in degrees
+    /// Celsius
+    ///
+    /// Fails with [`Error::UnsupportedByModule`] if the module has no
+    /// temperature sensor.
+    pub fn module_temperature(&mut self) -> Result<i16, Error> {
+        self.require(Capability::Temperature)?;
+        match self.send_at(&GetTemperature) {
+            Ok(resp) => Ok(resp.temperature),
+            Err(Error::Cme(CME_OPERATION_NOT_SUPPORTED)) => Err(Error::UnsupportedByModule),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether the module accepted the packet domain event reporting mode
+    /// [`CellularConfig::PACKET_EVENT_REPORTING`] during initialization
+    ///
+    /// If not, detach and deactivation events (+CGEV) are not reported.
+    pub fn packet_event_reporting(&self) -> bool {
+        self.network.packet_event_reporting
+    }
+
+    /// Reject cause of the latest denied registration (+CEER, +CEREG), until
+    /// the module is registered again
+    ///
+    /// See [`Error::RegistrationDenied`] for the meaning of the cause.
+    pub fn reject_cause(&self) -> Option<u8> {
+        self.network.status.reject_cause
+    }
+
+    /// Backoff waited before the current registration attempt, zero until an
+    /// attempt failed, see
+    /// [`CellularConfig::RECONNECT_BACKOFF_BASE`]
+    pub fn reconnect_backoff(&self) -> Duration {
+        self.network.backoff
+    }
+
+    /// Current state of the device
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Change to `state`, reporting the transition to
+    /// [`CellularConfig::ON_STATE_CHANGE`]
+    pub(crate) fn set_state(&mut self, state: State) {
+        if state == self.state {
+            return;
+        }
+
+        debug!("State: {:?} -> {:?}", self.state, state);
+        let old = core::mem::replace(&mut self.state, state);
+        if let Some(on_state_change) = Config::ON_STATE_CHANGE {
+            on_state_change(old, state);
+        }
+    }
+
+    /// Latest network registration status, e.g. whether registered on the
+    /// home network or roaming
+    pub fn registration_status(&self) -> RegistrationStatus {
+        self.network.status.status()
+    }
+
+    /// Radio access technology of the current registration, e.g. LTE or
+    /// E-UTRAN (NB-S1 mode) for NB-IoT, `None` while not registered
+    pub fn active_rat(&self) -> Option<RatAct> {
+        self.network.status.act
+    }
+
+    /// Handle all pending URCs
+    ///
+    /// [`spin`](Device::spin) only handles a single URC per call, so this
+    /// catches up on URCs received e.g. while the host was sleeping. The
+    /// raised [`Event`]s are retrieved with [`poll_event`](Device::poll_event).
+    pub fn process_urcs(&mut self) -> Result<(), Error> {
+        loop {
+            let socket_urc = self.handle_urc_internal()?;
+            let network_urc = self.network.handle_urc()?;
+            if !socket_urc && !network_urc {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Get the oldest pending [`Event`], handling any received URCs first
+    ///
+    /// If events have been dropped as they were not retrieved in time,
+    /// [`Event::EventQueueOverflow`] is returned first.
+    pub fn poll_event(&mut self) -> Option<Event> {
+        self.network.handle_urc().ok();
+        if core::mem::take(&mut self.network.events_overflowed) {
+            return Some(Event::EventQueueOverflow);
+        }
+        self.network.events.pop_front()
+    }
+
+    /// Number of [`Event`]s that can be retrieved with
+    /// [`poll_event`](Device::poll_event), without handling further URCs
+    pub fn pending_events(&self) -> usize {
+        self.network.events.len() + usize::from(self.network.events_overflowed)
+    }
+
     /// Run modem state machine
     ///
     /// Turns on modem if needed and processes URCs.
@@ -174,6 +336,19 @@ where
     ///
     /// This must be called periodically in a loop.
     pub fn spin(&mut self) -> nb::Result<(), Error> {
+        if self.network.backing_off() {
+            // Keep handling socket and network URCs until the next attempt
+            self.handle_urc_internal().ok();
+            self.network.handle_urc().ok();
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if self.state == State::WaitingForSim
+            || (!self.network.sim_present && self.state != State::Off)
+        {
+            return self.wait_for_sim();
+        }
+
         let res = self.initialize();
 
         self.process_events().map_err(Error::from)?;
@@ -183,16 +358,82 @@ where
         if self.network.is_connected().map_err(Error::from)?
             && self.state == State::FullyInitialized
         {
+            self.network.recovery_failures = 0;
+            self.network.recovery_level = RecoveryLevel::Retry;
+            self.record_registration();
             Ok(())
         } else {
             // Reset context state if data connection is lost (This will act as a safeguard if a URC is missed)
-            if self.network.context_state == ContextState::Active {
-                self.network.context_state = ContextState::Activating;
-            }
+            self.network.data_connection_lost();
             Err(nb::Error::WouldBlock)
         }
     }
 
+    /// Suspend registration while the SIM is removed, polling for it with
+    /// +CPIN until it is inserted again
+    fn wait_for_sim(&mut self) -> nb::Result<(), Error> {
+        if self.state != State::WaitingForSim {
+            warn!("Waiting for the SIM to be inserted");
+            self.set_state(State::WaitingForSim);
+            self.network.data_connection_lost();
+            self.network.status.reset();
+            self.network
+                .status
+                .set_connection_state(ConnectionState::Connecting);
+        }
+
+        self.network.handle_urc().ok();
+
+        let poll = self
+            .network
+            .sim_check_time
+            .map_or(true, |time| time.elapsed() >= SIM_POLL_INTERVAL);
+        if !self.network.sim_present && poll {
+            self.network.sim_check_time = Some(Instant::now());
+            match self.network.send_internal(&GetPinStatus, false) {
+                // Any other answer requires a SIM, while a timeout tells
+                // nothing about it
+                Ok(_) => self.network.sim_status_changed(true),
+                Err(crate::network::Error::Cme(code)) if code != CME_SIM_NOT_INSERTED => {
+                    self.network.sim_status_changed(true)
+                }
+                Err(_) => {}
+            }
+        }
+
+        if self.network.sim_present {
+            // Select the SIM and register again
+            self.set_state(State::AtInitialized);
+            self.network.reset_reg_time().ok();
+        }
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Run the modem state machine until connected to the network
+    ///
+    /// Calls [`spin`](Device::spin) repeatedly, until connected or `timeout`
+    /// expires, in which case [`Error::StateTimeout`] is returned. Errors that
+    /// retrying can not resolve, like [`Error::PukRequired`], are returned
+    /// immediately.
+    pub fn connect_blocking(&mut self, timeout: Duration) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match self.spin() {
+                Ok(()) => return Ok(()),
+                Err(nb::Error::Other(e)) if e.is_fatal() => return Err(e),
+                Err(nb::Error::Other(e)) => debug!("Connecting: {:?}", e),
+                Err(nb::Error::WouldBlock) => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err(Error::StateTimeout);
+            }
+
+            BlockingTimer::after(CONNECT_SPIN_INTERVAL).wait();
+        }
+    }
+
     /// Setup only essential settings to use AT commands
     ///
     /// Nornally this is not used and AT interface is setup in [`initialize`](Device::initialize).
@@ -215,6 +456,22 @@ where
             return Err(e);
         }
 
+        // Discard the greeting of a reset done while setting up, and have the
+        // module announce the next reboot
+        while let Ok(true) = self.network.handle_urc() {}
+        self.network.rebooted = false;
+        self.network.send_internal(
+            &SetGreetingText {
+                mode: GreetingTextMode::Enabled,
+                text: GREETING,
+            },
+            false,
+        )?;
+
+        if let Some(rate) = self.config.baud_rate() {
+            self.set_baud_rate(rate)?;
+        }
+
         // Extended errors on
         self.network.send_internal(
             &SetReportMobileTerminationError {
@@ -264,22 +521,75 @@ where
             false,
         )?;
 
-        // Ignore changes to DTR
+        // Ignore changes to DTR, unless it is used to control power saving
         self.network.send_internal(
             &SetCircuit108Behaviour {
-                value: Circuit108Behaviour::Ignore,
+                value: if Config::DTR_POWER_SAVING {
+                    Circuit108Behaviour::OnlineCommandState
+                } else {
+                    Circuit108Behaviour::Ignore
+                },
             },
             false,
         )?;
 
-        // Switch off UART power saving until it is integrated into this API
-        self.network.send_internal(
-            &SetPowerSavingControl {
-                mode: PowerSavingMode::Disabled,
-                timeout: None,
-            },
-            false,
-        )?;
+        if let Some(psm) = Config::PSM {
+            self.network.send_internal(
+                &SetPowerSavingMode {
+                    mode: PsmMode::Enabled,
+                    requested_periodic_rau: None,
+                    requested_gprs_ready_timer: None,
+                    requested_periodic_tau: Some(psm.periodic_tau_bits().as_str()),
+                    requested_active_time: Some(psm.active_time_bits().as_str()),
+                },
+                false,
+            )?;
+            self.network.send_internal(
+                &SetPsmReporting {
+                    mode: PsmReporting::Enabled,
+                },
+                false,
+            )?;
+        } else if Config::DTR_POWER_SAVING {
+            // Keep the module awake until the application releases DTR
+            self.set_dtr(true).ok();
+            self.network.send_internal(
+                &SetPowerSavingControl {
+                    mode: PowerSavingMode::CtrlByDtr,
+                    timeout: None,
+                },
+                false,
+            )?;
+        } else if let Some(timeout) = Config::UART_POWER_SAVING {
+            self.network.send_internal(
+                &SetPowerSavingControl {
+                    mode: PowerSavingMode::Enabled,
+                    timeout: Some(Seconds(config::gsm_frames(timeout) as u32)),
+                },
+                false,
+            )?;
+            self.network.at_tx.wake_after = Some(timeout);
+        } else {
+            self.network.send_internal(
+                &SetPowerSavingControl {
+                    mode: PowerSavingMode::Disabled,
+                    timeout: None,
+                },
+                false,
+            )?;
+        }
+
+        if let Some(edrx) = Config::EDRX {
+            self.network.send_internal(
+                &SetEdrxSettings {
+                    mode: EdrxMode::Enabled,
+                    act_type: edrx.act_type,
+                    requested_edrx_value: Some(edrx.cycle_bits().as_str()),
+                    requested_paging_time_window: Some(edrx.paging_time_window_bits().as_str()),
+                },
+                false,
+            )?;
+        }
 
         if Config::HEX_MODE {
             self.network.send_internal(
@@ -296,16 +606,32 @@ where
                 false,
             )?;
         }
+        self.network.hex_mode = Config::HEX_MODE;
 
         // Tell module whether we support flow control
         // FIXME: Use AT+IFC=2,2 instead of AT&K here
-        if Config::FLOW_CONTROL {
+        if self.config.flow_control() {
             self.network.send_internal(
                 &SetFlowControl {
                     value: FlowControl::RtsCts,
                 },
                 false,
             )?;
+
+            // If RTS/CTS are not wired, the module stops answering as soon as
+            // flow control is enabled
+            if self.is_alive(2).is_err() {
+                error!("No response with RTS/CTS flow control, check the wiring");
+                self.network
+                    .send_internal(
+                        &SetFlowControl {
+                            value: FlowControl::Disabled,
+                        },
+                        false,
+                    )
+                    .ok();
+                return Err(Error::FlowControlMismatch);
+            }
         } else {
             self.network.send_internal(
                 &SetFlowControl {
@@ -315,7 +641,7 @@ where
             )?;
         }
 
-        self.state = State::AtInitialized;
+        self.set_state(State::AtInitialized);
         Ok(())
     }
 
@@ -345,18 +671,122 @@ where
                 error!("Device not initialized!");
                 return Err(Error::Uninitialized);
             }
-            State::AtInitialized | State::FullyInitialized => {}
+            State::AtInitialized | State::FullyInitialized | State::WaitingForSim => {}
         }
 
         Ok(self.network.send_internal(cmd, true)?)
     }
 
+    /// Send the raw AT command `cmd`, e.g. `"AT+CGMI"`, and copy its raw
+    /// response into `buf`, returning its length
+    ///
+    /// The response includes intermediate lines, but not the final `OK`. A
+    /// final `ERROR` is returned as the error. Meant for bringing up commands
+    /// without a typed equivalent; commands longer than
+    /// [`MAX_RAW_COMMAND`](crate::command::MAX_RAW_COMMAND) bytes fail with
+    /// [`Error::CommandTooLong`], responses not fitting `buf` with
+    /// [`Error::ResponseTooLong`].
+    pub fn send_at_raw_lines(&mut self, cmd: &str, buf: &mut [u8]) -> Result<usize, Error> {
+        if cmd.len() > MAX_RAW_COMMAND {
+            return Err(Error::CommandTooLong);
+        }
+
+        let RawResponse(resp) = self.send_at(&RawCommand { cmd })?;
+        let buf = buf.get_mut(..resp.len()).ok_or(Error::ResponseTooLong)?;
+        buf.copy_from_slice(&resp);
+        Ok(resp.len())
+    }
+
+    /// Send an AT command in any state, e.g. before the module is initialized
+    ///
+    /// Unlike [`send_at`](Device::send_at), this doesn't check that the
+    /// device is initialized, and doesn't handle pending URCs. It is the
+    /// responsibility of the caller that the module is powered and able to
+    /// answer, and that the command does not interfere with the state
+    /// machine, e.g. by changing the functionality level.
+    pub fn send_at_raw<A, const LEN: usize>(&mut self, cmd: &A) -> Result<A::Response, Error>
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        Ok(self.network.send_internal(cmd, false)?)
+    }
+
+    /// Like [`send_at`](Device::send_at), but waiting up to `TIMEOUT_MS` for
+    /// the response in a single attempt, for long running commands
+    pub(crate) fn send_at_timeout<A, const LEN: usize, const TIMEOUT_MS: u32>(
+        &mut self,
+        cmd: &A,
+        timeout: TimeoutMs<TIMEOUT_MS>,
+    ) -> Result<A::Response, Error>
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        if self.state == State::Off {
+            error!("Device not initialized!");
+            return Err(Error::Uninitialized);
+        }
+
+        Ok(self.network.send_internal_timeout(cmd, true, timeout)?)
+    }
+
+    /// Switch the UART data rate of the module (+IPR), and then of the host
+    /// using [`CellularConfig::reconfigure_baud`]
+    fn set_baud_rate(&mut self, rate: BaudRate) -> Result<(), Error> {
+        if self.config.reconfigure_baud().is_none() {
+            error!("Changing the baud rate requires a `reconfigure_baud` callback!");
+            return Err(Error::BaudDetection);
+        }
+
+        let baud_rate = rate.clone() as u32;
+        self.network.send_internal(&SetDataRate { rate }, false)?;
+
+        // The module switches to the new data rate after the response
+        BlockingTimer::after(Duration::from_millis(100)).wait();
+
+        if let Some(reconfigure) = self.config.reconfigure_baud() {
+            reconfigure(baud_rate);
+        }
+
+        self.is_alive(5).map_err(|_| Error::BaudDetection)
+    }
+
     fn select_sim_card(&mut self) -> Result<(), Error> {
+        if self
+            .network
+            .send_internal(
+                &SetSimStatusReporting {
+                    mode: SimStatusReporting::Enabled,
+                },
+                false,
+            )
+            .is_err()
+        {
+            warn!("SIM status reporting set failed");
+        }
+
         for _ in 0..2 {
-            match self.network.send_internal(&GetPinStatus, true) {
+            match self.pin_status() {
+                Err(crate::network::Error::Cme(CME_SIM_NOT_INSERTED)) => {
+                    self.network.sim_status_changed(false);
+                    return Err(Error::SimNotReady);
+                }
                 Ok(PinStatus { code }) if code == PinStatusCode::Ready => {
                     return Ok(());
                 }
+                Ok(PinStatus {
+                    code: PinStatusCode::SimPin,
+                }) => {
+                    if let Some(pin) = self.config.pin() {
+                        return self.enter_pin(pin);
+                    }
+                    error!("SIM PIN required, but no PIN configured!");
+                }
+                Ok(PinStatus {
+                    code: PinStatusCode::SimPuk,
+                }) => {
+                    error!("SIM is PUK locked!");
+                    return Err(Error::PukRequired);
+                }
                 _ => {}
             }
 
@@ -388,6 +818,123 @@ where
         Err(Error::Busy)
     }
 
+    /// Query the PIN status of the SIM, waiting for it up to
+    /// [`SIM_BUSY_RETRIES`](CellularConfig::SIM_BUSY_RETRIES) times while it
+    /// is busy
+    fn pin_status(&mut self) -> Result<PinStatus, crate::network::Error> {
+        let mut retries = 0;
+        loop {
+            match self.network.send_internal(&GetPinStatus, true) {
+                Err(crate::network::Error::Cme(CME_SIM_BUSY))
+                    if retries < Config::SIM_BUSY_RETRIES =>
+                {
+                    debug!("SIM busy, waiting for it");
+                    retries += 1;
+                    BlockingTimer::after(SIM_BUSY_INTERVAL).wait();
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Unlock the SIM with the configured PIN, and confirm that it is ready
+    /// afterwards.
+    fn enter_pin(&mut self, pin: &str) -> Result<(), Error> {
+        // Never log the PIN itself
+        debug!("Entering SIM PIN");
+        self.network.send_internal(&SetPin { pin }, true)?;
+
+        match self.pin_status()? {
+            PinStatus {
+                code: PinStatusCode::Ready,
+            } => Ok(()),
+            PinStatus {
+                code: PinStatusCode::SimPuk,
+            } => {
+                error!("SIM is PUK locked!");
+                Err(Error::PukRequired)
+            }
+            _ => Err(Error::Busy),
+        }
+    }
+
+    /// Select the Radio Access Technology.
+    ///
+    /// Changing the RAT requires the module to be deregistered, so the module
+    /// is left in minimum functionality. The caller is responsible for
+    /// returning to full functionality afterwards.
+    fn select_rat(&mut self, rat: RadioAccessTechnologySelected) -> Result<(), Error> {
+        // The RAT of a runtime config is not checked by `ConfigCheck`
+        if !config::rat_selection_supported(&rat) {
+            error!("RAT {:?} is not supported by the selected module", rat);
+            return Err(Error::UnsupportedByModule);
+        }
+
+        // Avoid needlessly deregistering if the RAT is already selected
+        #[cfg(not(feature = "lara-r6"))]
+        {
+            if let Ok(network_service::responses::RadioAccessTechnology { act }) = self
+                .network
+                .send_internal(&network_service::GetRadioAccessTechnology, true)
+            {
+                if act == rat {
+                    return Ok(());
+                }
+            }
+        }
+
+        self.network.send_internal(
+            &SetModuleFunctionality {
+                fun: Functionality::Minimum,
+                // SARA-R5 This parameter can be used only when <fun> is 1, 4 or 19
+                #[cfg(feature = "sara-r5")]
+                rst: None,
+                #[cfg(not(feature = "sara-r5"))]
+                rst: Some(ResetMode::DontReset),
+            },
+            true,
+        )?;
+
+        #[cfg(not(feature = "lara-r6"))]
+        self.network
+            .send_internal(&SetRadioAccessTechnology { selected_act: rat }, true)?;
+
+        #[cfg(feature = "lara-r6")]
+        {
+            use network_service::types::{
+                FirstRadioAccessTechnology, SecondRadioAccessTechnology, ThirdRadioAccessTechnology,
+            };
+
+            let (first_act, second_act, third_act) = match rat {
+                RadioAccessTechnologySelected::GsmGprsEGprs => {
+                    (FirstRadioAccessTechnology::GsmGprsEGprs, None, None)
+                }
+                RadioAccessTechnologySelected::Umts => {
+                    (FirstRadioAccessTechnology::Umts, None, None)
+                }
+                RadioAccessTechnologySelected::Lte => (FirstRadioAccessTechnology::Lte, None, None),
+                RadioAccessTechnologySelected::GsmUmtsLte(..) => (
+                    FirstRadioAccessTechnology::Lte,
+                    Some(SecondRadioAccessTechnology::Umts),
+                    Some(ThirdRadioAccessTechnology::GsmGprsEGprs),
+                ),
+                // Rejected by `rat_selection_supported`
+                _ => return Err(Error::Generic(GenericError::Unsupported)),
+            };
+
+            self.network.send_internal(
+                &SetRadioAccessTechnology {
+                    first_act,
+                    second_act,
+                    third_act,
+                },
+                true,
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Initialize modem fully
     ///
     /// Turns modem on if it is off, configures it and starts registering to network.
@@ -399,7 +946,7 @@ where
     pub fn initialize(&mut self) -> Result<(), Error> {
         if self.power_state != PowerState::On {
             // Always re-configure the module when power has been off
-            self.state = State::Off;
+            self.set_state(State::Off);
 
             // Catch states where we have no vint sense, and the module is already in powered mode,
             // but for some reason doesn't answer to AT commands.
@@ -414,8 +961,23 @@ where
         }
 
         self.setup_at_commands()?;
+
+        if Config::RECONNECT_JITTER && self.network.jitter_state == 0 {
+            if let Ok(resp) = self.network.send_internal(&GetIMEI { snt: None }, false) {
+                self.network.seed_jitter(resp.imei);
+            }
+        }
+
         self.select_sim_card()?;
 
+        let rat = match Config::RAT_FALLBACK {
+            Some(fallback) if self.network.rat_fallback => Some(fallback.rat),
+            _ => self.config.rat(),
+        };
+        if let Some(rat) = rat {
+            self.select_rat(rat)?;
+        }
+
         // Disable Message Waiting URCs (UMWI)
         #[cfg(any(feature = "toby-r2"))]
         self.network.send_internal(
@@ -425,20 +987,35 @@ where
             false,
         )?;
 
-        self.network.send_internal(
-            &SetAutomaticTimezoneUpdate {
-                on_off: AutomaticTimezone::EnabledLocal,
-            },
-            false,
-        )?;
+        if Config::AUTO_TIMEZONE {
+            self.network.send_internal(
+                &SetAutomaticTimezoneUpdate {
+                    on_off: AutomaticTimezone::EnabledLocal,
+                },
+                false,
+            )?;
+        }
 
-        self.network.send_internal(
-            &SetModuleFunctionality {
-                fun: Functionality::Full,
-                rst: None,
-            },
-            true,
-        )?;
+        if Config::TIME_ZONE_REPORTING {
+            self.network.send_internal(
+                &SetTimeZoneReporting {
+                    reporting: TimeZoneReporting::Extended,
+                },
+                false,
+            )?;
+        }
+
+        // Avoid a redundant +CFUN=1 if the module is already fully
+        // functional, e.g. when re-initializing after a reset
+        if !matches!(self.functionality(), Ok(PowerMode::On)) {
+            self.network.send_internal(
+                &SetModuleFunctionality {
+                    fun: Functionality::Full,
+                    rst: None,
+                },
+                true,
+            )?;
+        }
 
         self.network.status.reset();
         self.network
@@ -447,19 +1024,26 @@ where
 
         self.enable_registration_urcs()?;
 
+        self.network.registration_recorded = false;
+
         // Set automatic operator selection, if not already set
         let OperatorSelection { mode, .. } =
             self.network.send_internal(&GetOperatorSelection, true)?;
 
-        // Only run AT+COPS=0 if currently de-registered, to avoid PLMN reselection
-        if !matches!(
-            mode,
-            OperatorSelectionMode::Automatic | OperatorSelectionMode::Manual
-        ) {
+        // Seed the registration if one was restored. Otherwise only run
+        // AT+COPS=0 if currently de-registered, to avoid PLMN reselection
+        if !self.seed_registration()
+            && !matches!(
+                mode,
+                OperatorSelectionMode::Automatic | OperatorSelectionMode::Manual
+            )
+        {
             self.network.send_internal(
                 &SetOperatorSelection {
                     mode: OperatorSelectionMode::Automatic,
                     format: Some(2),
+                    oper: None,
+                    act: None,
                 },
                 true,
             )?;
@@ -469,7 +1053,7 @@ where
 
         self.network.reset_reg_time()?;
 
-        self.state = State::FullyInitialized;
+        self.set_state(State::FullyInitialized);
         Ok(())
     }
 
@@ -485,17 +1069,17 @@ where
         // if packet domain event reporting is not set it's not a stopper. We
         // might lack some events when we are dropped from the network.
         // TODO: Re-enable this when it works, and is useful!
-        if self
+        self.network.packet_event_reporting = self
             .network
             .send_internal(
                 &SetPacketSwitchedEventReporting {
-                    mode: PSEventReportingMode::CircularBufferUrcs,
+                    mode: Config::PACKET_EVENT_REPORTING,
                     bfr: None,
                 },
                 true,
             )
-            .is_err()
-        {
+            .is_ok();
+        if !self.network.packet_event_reporting {
             warn!("Packet domain event reporting set failed");
         }
 
@@ -529,7 +1113,8 @@ where
         Ok(())
     }
 
-    fn handle_urc_internal(&mut self) -> Result<(), Error> {
+    /// Handle the next pending socket URC, returning whether there was one
+    fn handle_urc_internal(&mut self) -> Result<bool, Error> {
         if let Some(ref mut sockets) = self.sockets.as_deref_mut() {
             if let Some(urc) = self.urc_subscription.try_next_message_pure() {
                 match urc {
@@ -539,6 +1124,11 @@ where
                             sockets.iter_mut().find(|(handle, _)| *handle == socket)
                         {
                             sock.closed_by_remote();
+                            let error = self.network.socket_last_error(socket).ok();
+                            self.network.push_event(Event::SocketClosed {
+                                handle: socket,
+                                error,
+                            });
                         }
                     }
                     Urc::SocketDataAvailable(ip_transport_layer::urc::SocketDataAvailable {
@@ -554,15 +1144,47 @@ where
                             sockets.iter_mut().find(|(handle, _)| *handle == socket)
                         {
                             sock.set_available_data(length);
+                            self.network.push_event(Event::SocketDataAvailable {
+                                handle: socket,
+                                len: length,
+                            });
                         }
                     }
                     _ => {}
                 }
+                return Ok(true);
             }
-            Ok(())
-        } else {
-            Ok(())
         }
+        Ok(false)
+    }
+
+    /// Re-initialize the module if it has not answered any AT command for
+    /// [`CellularConfig::LIVENESS_TIMEOUT`] while connected. Returns whether
+    /// the module is being re-initialized.
+    fn check_liveness(&mut self) -> bool {
+        let Some(timeout) = Config::LIVENESS_TIMEOUT else {
+            return false;
+        };
+
+        if self.state != State::FullyInitialized || !self.network.is_connected().unwrap_or(false) {
+            return false;
+        }
+
+        let unresponsive = self
+            .network
+            .at_tx
+            .last_success
+            .and_then(|last| Instant::now().checked_duration_since(last))
+            .map_or(false, |dur| dur >= timeout);
+
+        if unresponsive {
+            warn!("Module unresponsive for {}, re-initializing", timeout);
+            self.network.push_event(Event::ModuleUnresponsive);
+            self.network.at_tx.last_success = None;
+            self.power_state = PowerState::Off;
+            self.set_state(State::Off);
+        }
+        unresponsive
     }
 
     pub(crate) fn process_events(&mut self) -> Result<(), Error> {
@@ -571,15 +1193,108 @@ where
         }
 
         self.handle_urc_internal()?;
+        if self.check_liveness() {
+            return Ok(());
+        }
 
-        match self.network.process_events() {
+        let result = match self.network.process_events() {
             // Catch "Resetting the modem due to the network registration timeout"
-            // as well as consecutive AT timeouts and do a hard reset.
+            // as well as consecutive AT timeouts and recover.
             Err(crate::network::Error::Generic(GenericError::Timeout)) => {
-                self.hard_reset()?;
+                self.check_rat_fallback();
+                self.check_seeded_registration();
+                self.recover()?;
                 Err(Error::Generic(GenericError::Timeout))
             }
             result => result.map_err(Error::from),
+        };
+
+        if self.check_reboot() {
+            return Ok(());
+        }
+        result
+    }
+
+    /// Recover from a failure, escalating along [`CellularConfig::RECOVERY`]
+    /// with the consecutive failures
+    fn recover(&mut self) -> Result<(), Error> {
+        self.network.recovery_failures = self.network.recovery_failures.saturating_add(1);
+        let level = Config::RECOVERY.level(self.network.recovery_failures);
+        if level > self.network.recovery_level {
+            warn!("Escalating recovery to {:?}", level);
+            self.network.recovery_level = level;
+            self.network.push_event(Event::RecoveryEscalated { level });
+        }
+
+        match level {
+            RecoveryLevel::Retry => {
+                self.network.reset_reg_time()?;
+                return Ok(());
+            }
+            RecoveryLevel::SoftReset => {
+                if self.soft_reset(false).is_err() {
+                    self.hard_reset()?;
+                }
+            }
+            RecoveryLevel::HardReset => match self.hard_reset_strict() {
+                Err(Error::PinUnavailable) => self.power_cycle()?,
+                res => res?,
+            },
+            RecoveryLevel::PowerCycle => self.power_cycle()?,
+        }
+
+        self.set_state(State::Off);
+        Ok(())
+    }
+
+    /// Switch the module off with the `PWR_ON` pin, to be switched on again
+    /// by [`initialize`](Device::initialize). Without a `PWR_ON` pin, the
+    /// module is reset instead.
+    fn power_cycle(&mut self) -> Result<(), Error> {
+        match self.hard_power_off() {
+            Err(Error::Generic(GenericError::Unsupported)) => self.hard_reset()?,
+            res => res?,
+        }
+        self.power_state = PowerState::Off;
+        Ok(())
+    }
+
+    /// Re-initialize the module if it has rebooted since it was set up, as
+    /// announced by its greeting text. Returns whether the module is being
+    /// re-initialized.
+    fn check_reboot(&mut self) -> bool {
+        if !core::mem::take(&mut self.network.rebooted) {
+            return false;
+        }
+
+        warn!("Module rebooted unexpectedly, re-initializing");
+        self.network.push_event(Event::ModuleRebooted);
+        self.network.data_connection_lost();
+        self.network.active_contexts.clear();
+        self.reset_sockets();
+        self.set_state(State::Off);
+        true
+    }
+
+    /// Select [`CellularConfig::RAT_FALLBACK`] for the following registration
+    /// attempts, if registration failed often enough on a weak signal
+    fn check_rat_fallback(&mut self) {
+        let Some(fallback) = Config::RAT_FALLBACK else {
+            return;
+        };
+        if self.network.rat_fallback || self.network.attach_retries < fallback.after_attempts {
+            return;
+        }
+
+        // The signal quality is not known if the module stopped answering
+        if let Ok(quality) = self.signal_quality() {
+            if fallback.is_weak(&quality) {
+                warn!(
+                    "Weak signal after {} registration attempts, falling back to {:?}",
+                    self.network.attach_retries, fallback.rat
+                );
+                self.network.rat_fallback = true;
+            }
         }
     }
 }
@@ -0,0 +1,108 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    client::Device,
+    command::mobile_control::{responses, GetClock},
+    config::CellularConfig,
+    error::{Error, GenericError},
+};
+
+/// Local date and time of the module clock, as returned by
+/// [`network_time`](Device::network_time)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// Difference between local time and GMT, in quarters of an hour
+    pub timezone_quarters: i8,
+}
+
+impl DateTime {
+    /// Parse the `"yy/MM/dd,hh:mm:ss±zz"` format of +CCLK
+    pub fn parse(time: &str) -> Option<Self> {
+        let time = time.trim_matches('"');
+        let (date, clock) = time.split_once(',')?;
+
+        let mut date = date.split('/').map(|v| v.parse::<u8>().ok());
+        let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+        let tz_start = clock.find(|c| c == '+' || c == '-')?;
+        let (clock, tz) = clock.split_at(tz_start);
+        let timezone_quarters = tz.parse::<i8>().ok()?;
+
+        let mut clock = clock.split(':').map(|v| v.parse::<u8>().ok());
+        let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+            return None;
+        }
+
+        Some(Self {
+            year: 2000 + year as u16,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            timezone_quarters,
+        })
+    }
+
+    /// Difference between local time and GMT, in minutes
+    pub fn utc_offset_minutes(&self) -> i16 {
+        self.timezone_quarters as i16 * 15
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Read the module clock (+CCLK?)
+    ///
+    /// The clock is set from the network after registration if
+    /// [`CellularConfig::AUTO_TIMEZONE`] is enabled and the network supports
+    /// NITZ. Fails with [`GenericError::Clock`] if the clock can not be
+    /// parsed.
+    pub fn network_time(&mut self) -> Result<DateTime, Error> {
+        let responses::DateTime { time } = self.send_at(&GetClock)?;
+        DateTime::parse(&time).ok_or(Error::Generic(GenericError::Clock))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_clock() {
+        let time = DateTime::parse("\"23/06/28,14:05:09+08\"").unwrap();
+        assert_eq!(
+            time,
+            DateTime {
+                year: 2023,
+                month: 6,
+                day: 28,
+                hour: 14,
+                minute: 5,
+                second: 9,
+                timezone_quarters: 8,
+            }
+        );
+        assert_eq!(time.utc_offset_minutes(), 120);
+
+        let time = DateTime::parse("04/01/01,00:00:00-14").unwrap();
+        assert_eq!(time.utc_offset_minutes(), -210);
+
+        assert_eq!(DateTime::parse("23/13/28,14:05:09+08"), None);
+        assert_eq!(DateTime::parse("23/06/28,14:05:09"), None);
+    }
+}
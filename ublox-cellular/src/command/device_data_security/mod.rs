@@ -120,6 +120,22 @@ pub struct SendSecurityDataImport<'a> {
     pub data: &'a atat::serde_bytes::Bytes,
 }
 
+/// Part of the data of a +USECMNG import, written without waiting for a
+/// response. The last part is written with [`SendSecurityDataImport`].
+#[derive(Clone, AtatCmd)]
+#[at_cmd(
+    "",
+    NoResponse,
+    value_sep = false,
+    cmd_prefix = "",
+    termination = "",
+    expects_response_code = false
+)]
+pub struct SendSecurityDataChunk<'a> {
+    #[at_arg(position = 0, len = 2048)]
+    pub data: &'a atat::serde_bytes::Bytes,
+}
+
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+USECMNG=2,", NoResponse, value_sep = false)]
 pub struct DeleteSecurityData<'a> {
@@ -136,7 +152,7 @@ pub struct DeleteSecurityData<'a> {
 }
 
 #[derive(Clone, AtatCmd)]
-#[at_cmd("+USECMNG=3", Vec<SecurityData, 3> , value_sep = false)]
+#[at_cmd("+USECMNG=3", Vec<SecurityData, 16> , value_sep = false)]
 pub struct ListSecurityData;
 
 #[derive(Clone, AtatCmd)]
@@ -35,7 +35,7 @@ pub struct SecurityData {
     /// • "VC": signature verification certificate
     /// • "PU": signature verification public key
     #[at_arg(position = 1)]
-    cert_type: String<2>,
+    pub cert_type: String<2>,
     /// Unique identifier of an imported certificate or private key. If an
     /// existing name is used the data will be overridden.
     ///
@@ -43,13 +43,13 @@ pub struct SecurityData {
     /// SARA-G3:**
     /// - The maximum length is 200 characters
     #[at_arg(position = 2)]
-    internal_name: String<200>,
+    pub internal_name: String<200>,
     /// Certificate subject (issued to) common name; applicable only for trusted root and
     /// client certificates.
     #[at_arg(position = 3)]
-    common_name: Option<String<100>>,
+    pub common_name: Option<String<100>>,
     /// Certificate expiration (valid to date); applicable only for trusted root and client
     /// certificates.
     #[at_arg(position = 4)]
-    expiration_date: Option<String<100>>,
+    pub expiration_date: Option<String<100>>,
 }
@@ -3,9 +3,11 @@
 pub mod impl_;
 pub mod responses;
 pub mod types;
+pub mod urc;
 
 use atat::atat_derive::AtatCmd;
 use responses::PinStatus;
+use types::SimStatusReporting;
 
 use super::NoResponse;
 
@@ -26,7 +28,7 @@ pub struct GetPinStatus;
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+CPIN", NoResponse)]
 pub struct SetPin<'a> {
-    #[at_arg(position = 0, len = 4)]
+    #[at_arg(position = 0, len = 8)]
     pub pin: &'a str,
 }
 
@@ -43,3 +45,15 @@ pub struct ChangePin<'a> {
     #[at_arg(position = 1, len = 4)]
     pub newpin: &'a str,
 }
+
+/// SIM states reporting +USIMSTAT
+///
+/// Enables the +UUSIMSTAT URC, reporting changes of the SIM state, e.g. its
+/// removal or insertion. Detecting the SIM insertion requires the SIM
+/// detection to be configured on modules supporting it.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+USIMSTAT", NoResponse)]
+pub struct SetSimStatusReporting {
+    #[at_arg(position = 0)]
+    pub mode: SimStatusReporting,
+}
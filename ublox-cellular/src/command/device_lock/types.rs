@@ -1,4 +1,5 @@
 //! Argument and parameter types used by Device lock Commands and Responses
+use atat::atat_derive::AtatEnum;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -25,3 +26,31 @@ pub enum PinStatusCode {
     /// • PH-SIM PIN: MT is waiting phone to SIM/UICC card password to be given
     PhSimPin,
 }
+
+/// Reporting of SIM state changes with the +UUSIMSTAT URC
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum SimStatusReporting {
+    /// 0: reporting disabled
+    Disabled = 0,
+    /// 1: SIM card initialization status reporting enabled
+    Enabled = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SimState {
+    /// 0: SIM card not present
+    NotPresent = 0,
+    /// 1: PIN needed
+    PinNeeded = 1,
+    /// 2: PIN blocked
+    PinBlocked = 2,
+    /// 3: PUK blocked
+    PukBlocked = 3,
+    /// 4: (U)SIM not operational
+    NotOperational = 4,
+    /// 5: (U)SIM in restricted use (FDN or BDN active)
+    RestrictedUse = 5,
+    /// 6: (U)SIM operational (registration may be initiated)
+    Operational = 6,
+}
@@ -0,0 +1,10 @@
+//! Unsolicited responses for Device lock Commands
+use super::types::SimState;
+use atat::atat_derive::AtatResp;
+
+/// SIM states reporting +UUSIMSTAT
+#[derive(Debug, Clone, AtatResp)]
+pub struct SimStatus {
+    #[at_arg(position = 0)]
+    pub state: SimState,
+}
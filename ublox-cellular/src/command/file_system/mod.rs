@@ -7,7 +7,7 @@ pub mod responses;
 
 use atat::atat_derive::AtatCmd;
 use heapless::{String, Vec};
-use responses::{ReadBlockResponse, ReadFileResponse};
+use responses::{FileSize, ReadBlockResponse, ReadFileResponse};
 
 use super::NoResponse;
 
@@ -91,6 +91,16 @@ pub struct DownloadFile<'a> {
 #[at_cmd("+ULSTFILE=0", Vec<String<248>, 10>, value_sep = false)]
 pub struct ListFiles;
 
+/// 22.3 List files information +ULSTFILE
+///
+/// Size of the file `filename` in bytes.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+ULSTFILE=2,", FileSize, value_sep = false)]
+pub struct GetFileSize<'a> {
+    #[at_arg(position = 0, len = 248)]
+    pub filename: &'a str,
+}
+
 /// 22.4 Read file +URDFILE
 ///
 /// Retrieves a file from the file system.
@@ -3,6 +3,13 @@ use atat::atat_derive::AtatResp;
 use atat::heapless_bytes::Bytes;
 use heapless::String;
 
+/// 22.3 List files information +ULSTFILE
+#[derive(Clone, Debug, PartialEq, Eq, AtatResp)]
+pub struct FileSize {
+    #[at_arg(position = 0)]
+    pub size: usize,
+}
+
 /// 22.4 Read file +URDFILE
 #[derive(Debug, PartialEq, Eq, AtatResp)]
 pub struct ReadFileResponse {
@@ -0,0 +1,54 @@
+//! FTP
+//!
+//! The section describes the u-blox proprietary AT commands that can be used
+//! for transferring files between the file system of the module and a remote
+//! FTP server. A PSD connection must be activated before using FTP AT
+//! commands.
+//!
+//! The final result of the +UFTPC commands is reported with the +UUFTPCR URC,
+//! and the error of the last failed command can be queried using the +UFTPER
+//! AT command.
+pub mod responses;
+pub mod types;
+pub mod urc;
+
+use atat::atat_derive::AtatCmd;
+use responses::FtpError;
+use types::{FtpOperation, FtpProfileOperation};
+
+use super::NoResponse;
+
+/// FTP service configuration +UFTP
+///
+/// Sets up a parameter for FTP service. The parameters are not saved in the
+/// non volatile memory.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UFTP", NoResponse)]
+pub struct SetFtpProfile {
+    #[at_arg(position = 0)]
+    pub operation: FtpProfileOperation,
+}
+
+/// FTP command +UFTPC
+///
+/// Triggers the FTP command `op_code`, using the parameters configured with
+/// +UFTP. For file transfers, `param1` and `param2` are the source and
+/// destination file names, e.g. the remote and the local file name for
+/// +UFTPC: 4 (retrieve file).
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UFTPC", NoResponse)]
+pub struct SendFtpCommand<'a> {
+    #[at_arg(position = 0)]
+    pub op_code: FtpOperation,
+    #[at_arg(position = 1, len = 256)]
+    pub param1: Option<&'a str>,
+    #[at_arg(position = 2, len = 256)]
+    pub param2: Option<&'a str>,
+}
+
+/// FTP error +UFTPER
+///
+/// Retrieves the error class and code of the last failed FTP command.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UFTPER", FtpError)]
+pub struct GetFtpError;
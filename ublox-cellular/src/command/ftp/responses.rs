@@ -0,0 +1,14 @@
+//! Responses for FTP Commands
+use atat::atat_derive::AtatResp;
+
+/// FTP error +UFTPER
+#[derive(Debug, Clone, AtatResp)]
+pub struct FtpError {
+    /// 0 if there was no error, 1 for errors reported by the FTP server, with
+    /// the server reply code as `error_code`, and other values for errors of
+    /// the module
+    #[at_arg(position = 0)]
+    pub error_class: u8,
+    #[at_arg(position = 1)]
+    pub error_code: u16,
+}
@@ -0,0 +1,52 @@
+//! Argument and parameter types used by FTP Commands and Responses
+use atat::atat_derive::AtatEnum;
+use heapless::String;
+
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum FtpProfileOperation {
+    /// - 1: FTP server name, e.g. "ftp.server.com". The maximum length is 128
+    ///   characters.
+    #[at_arg(value = 1)]
+    ServerName(String<128>),
+    /// - 2: username, the maximum length is 30 characters
+    #[at_arg(value = 2)]
+    Username(String<30>),
+    /// - 3: password, the maximum length is 30 characters
+    #[at_arg(value = 3)]
+    Password(String<30>),
+    /// - 6: FTP mode; 0 (factory-programmed value) for active, 1 for passive
+    #[at_arg(value = 6)]
+    Passive(bool),
+    /// - 7: FTP server port, in range 1-65535. The factory-programmed value is
+    ///   21.
+    #[at_arg(value = 7)]
+    ServerPort(u16),
+}
+
+/// FTP command of +UFTPC, as reported by the +UUFTPCR URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FtpOperation {
+    /// • 0: FTP logout; terminates the FTP session
+    Logout = 0,
+    /// • 1: FTP login; connects to the FTP server
+    Login = 1,
+    /// • 2: delete a file from the FTP server
+    DeleteFile = 2,
+    /// • 3: rename a file of the FTP server
+    RenameFile = 3,
+    /// • 4: retrieve a file from the FTP server into the file system
+    RetrieveFile = 4,
+    /// • 5: store a file of the file system on the FTP server
+    StoreFile = 5,
+    /// • 6: retrieve the information of a file or directory
+    FileInfo = 6,
+    /// • 7: list the file names of a directory
+    ListFiles = 7,
+    /// • 8: change the working directory
+    ChangeDirectory = 8,
+    /// • 10: create a directory on the FTP server
+    CreateDirectory = 10,
+    /// • 11: remove a directory from the FTP server
+    RemoveDirectory = 11,
+}
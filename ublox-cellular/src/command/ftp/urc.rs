@@ -0,0 +1,15 @@
+//! Unsolicited responses for FTP Commands
+use super::types::FtpOperation;
+use atat::atat_derive::AtatResp;
+
+/// FTP command result +UUFTPCR
+///
+/// Final result of a +UFTPC command; `ftp_result` is 1 on success, 0
+/// otherwise.
+#[derive(Debug, Clone, AtatResp)]
+pub struct FtpCommandResult {
+    #[at_arg(position = 0)]
+    pub op_code: FtpOperation,
+    #[at_arg(position = 1)]
+    pub ftp_result: u8,
+}
@@ -0,0 +1,69 @@
+use super::responses::GgaSentence;
+use super::GetGgaSentence;
+use atat::{AtatCmd, InternalError};
+use heapless::{String, Vec};
+
+impl AtatCmd<11> for GetGgaSentence {
+    type Response = GgaSentence;
+
+    fn as_bytes(&self) -> Vec<u8, 11> {
+        Vec::from_slice(b"AT+UGGGA?\r\n").unwrap()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        let resp = core::str::from_utf8(resp).map_err(|_| atat::Error::Parse)?;
+        parse_gga_sentence(resp).ok_or(atat::Error::Parse)
+    }
+}
+
+/// Parse `+UGGGA: <mode>,<GGA>`, where `<GGA>` is the NMEA message, or a
+/// placeholder such as "NULL" if no message is available
+fn parse_gga_sentence(resp: &str) -> Option<GgaSentence> {
+    let resp = resp.trim().strip_prefix("+UGGGA:")?.trim_start();
+    let (mode, sentence) = resp.split_once(',').unwrap_or((resp, ""));
+
+    let enabled = match mode {
+        "0" => false,
+        "1" => true,
+        _ => return None,
+    };
+
+    let sentence = if sentence.starts_with('$') {
+        let mut s = String::new();
+        s.push_str(sentence.trim_end()).ok()?;
+        Some(s)
+    } else {
+        None
+    };
+
+    Some(GgaSentence { enabled, sentence })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gga_sentence() {
+        let sentence = "$GPGGA,092725.00,4717.11399,N,00833.91590,E,1,08,1.01,499.6,M,48.0,M,,*5B";
+        let mut resp = std::string::String::from("+UGGGA: 1,");
+        resp.push_str(sentence);
+
+        assert_eq!(
+            parse_gga_sentence(&resp),
+            Some(GgaSentence {
+                enabled: true,
+                sentence: Some(String::from(sentence)),
+            })
+        );
+        assert_eq!(
+            parse_gga_sentence("+UGGGA: 0,NULL"),
+            Some(GgaSentence {
+                enabled: false,
+                sentence: None,
+            })
+        );
+        assert_eq!(parse_gga_sentence("+UGGGA: 2,NULL"), None);
+    }
+}
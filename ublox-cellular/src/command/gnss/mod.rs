@@ -0,0 +1,71 @@
+//! GNSS
+//!
+//! The section describes the u-blox proprietary AT commands that can be used
+//! with the GNSS receiver integrated in, or connected to, the module. The
+//! position can be read from the NMEA messages stored by the module, or
+//! requested with +ULOC, in which case it is reported with the +UULOC URC.
+mod impl_;
+pub mod responses;
+pub mod types;
+pub mod urc;
+
+use atat::atat_derive::AtatCmd;
+use types::{GnssMode, LocationResponseType, LocationSensor};
+
+use super::NoResponse;
+
+/// GNSS power management +UGPS
+///
+/// Switches the GNSS receiver on or off. `aid_mode` is a bitmask of the
+/// aiding types to use, and `systems` a bitmask of the GNSS systems to use;
+/// both are optional and only allowed when switching the receiver on.
+///
+/// **NOTES:**
+/// - Bit 0 of `aid_mode` selects automatic local aiding, where the receiver
+///   is aided with the time and the coarse position known to the module.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UGPS", NoResponse, timeout_ms = 10000)]
+pub struct SetGnssPower {
+    #[at_arg(position = 0)]
+    pub mode: GnssMode,
+    #[at_arg(position = 1)]
+    pub aid_mode: Option<u8>,
+    #[at_arg(position = 2)]
+    pub systems: Option<u8>,
+}
+
+/// Get GPS fix data +UGGGA
+///
+/// Enables or disables the storing of the last $GGA NMEA message, which is
+/// read with [`GetGgaSentence`].
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UGGGA", NoResponse)]
+pub struct SetGgaStorage {
+    #[at_arg(position = 0)]
+    pub enabled: bool,
+}
+
+/// Get GPS fix data +UGGGA
+///
+/// Reads the last $GGA NMEA message. The message contains commas, so the
+/// response is parsed manually (see `impl_`).
+#[derive(Clone)]
+pub struct GetGgaSentence;
+
+/// Ask for localization information +ULOC
+///
+/// Requests a single position from `sensor`, within `timeout` seconds and with
+/// the desired `accuracy` in meters. The command returns as soon as the
+/// request is accepted, the position is reported with the +UULOC URC.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+ULOC=2,", NoResponse, value_sep = false)]
+pub struct RequestLocation {
+    #[at_arg(position = 0)]
+    pub sensor: LocationSensor,
+    #[at_arg(position = 1)]
+    pub response_type: LocationResponseType,
+    #[at_arg(position = 2)]
+    pub timeout: u16,
+    #[at_arg(position = 3)]
+    pub accuracy: u32,
+}
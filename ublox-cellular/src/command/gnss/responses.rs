@@ -0,0 +1,13 @@
+//! Responses for GNSS Commands
+use heapless::String;
+
+/// Get GPS fix data +UGGGA
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GgaSentence {
+    /// Whether the storing of $GGA messages is enabled
+    pub enabled: bool,
+    /// The last $GGA message, `None` if no message is available
+    pub sentence: Option<String<100>>,
+}
+
+impl atat::AtatResp for GgaSentence {}
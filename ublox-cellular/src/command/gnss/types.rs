@@ -0,0 +1,34 @@
+//! Argument and parameter types used by GNSS Commands and Responses
+use atat::atat_derive::AtatEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+pub enum GnssMode {
+    /// • 0 (default value): GNSS receiver powered off
+    Off = 0,
+    /// • 1: GNSS receiver powered on
+    On = 1,
+}
+
+/// Sensors used by +ULOC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LocationSensor {
+    /// • 1: use the GNSS receiver for localization
+    Gnss = 1,
+    /// • 2: use cellular CellLocate® location information
+    CellLocate = 2,
+    /// • 3: hybrid; perform a GNSS fix and a CellLocate® request in parallel,
+    ///   and report the better result
+    Hybrid = 3,
+}
+
+/// Format of the +UULOC URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+pub enum LocationResponseType {
+    /// • 0: standard; a single position, with its date, time, altitude and
+    ///   uncertainty
+    Standard = 0,
+    /// • 1: detailed; additionally reports speed, direction and the used
+    ///   sensor
+    Detailed = 1,
+}
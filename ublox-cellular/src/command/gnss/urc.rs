@@ -0,0 +1,26 @@
+//! Unsolicited responses for GNSS Commands
+use atat::atat_derive::AtatResp;
+use atat::heapless_bytes::Bytes;
+
+/// Localization information +UULOC
+///
+/// Position requested with +ULOC, in the standard response type. The
+/// unquoted date, time and coordinates are kept as reported, e.g.
+/// "22/12/2020", "10:35:20.000", "45.7879".
+#[derive(Debug, Clone, AtatResp)]
+pub struct Location {
+    #[at_arg(position = 0)]
+    pub date: Bytes<10>,
+    #[at_arg(position = 1)]
+    pub time: Bytes<12>,
+    #[at_arg(position = 2)]
+    pub latitude: Bytes<16>,
+    #[at_arg(position = 3)]
+    pub longitude: Bytes<16>,
+    /// Altitude in meters
+    #[at_arg(position = 4)]
+    pub altitude: i32,
+    /// Maximum possible error in meters
+    #[at_arg(position = 5)]
+    pub uncertainty: u32,
+}
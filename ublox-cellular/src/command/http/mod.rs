@@ -19,4 +19,52 @@
 //!   If using `CellLocate`® and HTTP commands HTTP profiles in the range 1-3 must
 //!   be used.
 
+pub mod types;
 pub mod urc;
+
+use atat::atat_derive::AtatCmd;
+use types::{HttpCommand, HttpContentType, HttpProfileId, HttpProfileOperation};
+
+use super::NoResponse;
+
+/// HTTP control +UHTTP
+///
+/// Configures, reads or resets (to the factory-programmed values) the HTTP
+/// application profile parameters. Up to 4 different HTTP profiles can be
+/// defined. The configured HTTP profile parameters are not saved in the non
+/// volatile memory. Without `operation`, the profile is reset.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UHTTP", NoResponse)]
+pub struct SetHttpProfile {
+    #[at_arg(position = 0)]
+    pub profile_id: HttpProfileId,
+    #[at_arg(position = 1)]
+    pub operation: Option<HttpProfileOperation>,
+}
+
+/// HTTP command +UHTTPC
+///
+/// Triggers the HTTP command specified with `command` using the HTTP
+/// application profile parameters. The response of the server is stored in
+/// `response_filename` in the file system, and the final result of the
+/// command is reported with the +UUHTTPCR URC.
+///
+/// `data` and `content_type` are only given for POST commands: the file
+/// holding the data for +UHTTPC: 4 (POST file), or the data itself for
+/// +UHTTPC: 5 (POST data).
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UHTTPC", NoResponse)]
+pub struct SendHttpCommand<'a> {
+    #[at_arg(position = 0)]
+    pub profile_id: HttpProfileId,
+    #[at_arg(position = 1)]
+    pub command: HttpCommand,
+    #[at_arg(position = 2, len = 128)]
+    pub path: &'a str,
+    #[at_arg(position = 3, len = 248)]
+    pub response_filename: &'a str,
+    #[at_arg(position = 4, len = 248)]
+    pub data: Option<&'a str>,
+    #[at_arg(position = 5)]
+    pub content_type: Option<HttpContentType>,
+}
@@ -0,0 +1,64 @@
+//! Argument and parameter types used by HTTP Commands and Responses
+use crate::services::data::ssl::SecurityProfileId;
+use atat::atat_derive::{AtatEnum, AtatLen};
+use heapless::String;
+use serde::{Deserialize, Serialize};
+
+/// HTTP profile identifier, in range 0-3
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, AtatLen)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HttpProfileId(pub u8);
+
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum HttpProfileOperation {
+    /// - 1: HTTP server name, e.g. "http.server.com". The maximum length is
+    ///   128 characters. The factory-programmed value is an empty string.
+    #[at_arg(value = 1)]
+    ServerName(String<128>),
+    /// - 5: HTTP server port, in range 1-65535. The factory-programmed value
+    ///   is 80, or 443 when SSL encryption is enabled.
+    #[at_arg(value = 5)]
+    ServerPort(u16),
+    /// - 6: HTTP secure option (SSL encryption). The factory-programmed value
+    ///   is disabled; the second parameter is the USECMNG profile to use.
+    #[at_arg(value = 6)]
+    Secure(bool, SecurityProfileId),
+}
+
+/// HTTP command of +UHTTPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HttpCommand {
+    /// • 0: HEAD command; issue an HTTP HEAD request
+    Head = 0,
+    /// • 1: GET command; perform an HTTP GET request
+    Get = 1,
+    /// • 2: DELETE command; send an HTTP DELETE request
+    Delete = 2,
+    /// • 3: PUT command; perform an HTTP PUT request using a file
+    Put = 3,
+    /// • 4: POST file command; issue an HTTP POST request for sending the
+    ///   data stored in a file
+    PostFile = 4,
+    /// • 5: POST data command; send an HTTP POST request, the data is given
+    ///   as a parameter
+    PostData = 5,
+}
+
+/// Content type of the data sent with +UHTTPC POST commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HttpContentType {
+    /// • 0: application/x-www-form-urlencoded
+    FormUrlEncoded = 0,
+    /// • 1: text/plain
+    TextPlain = 1,
+    /// • 2: application/octet-stream
+    OctetStream = 2,
+    /// • 3: multipart/form-data
+    MultipartFormData = 3,
+    /// • 4: application/json
+    Json = 4,
+    /// • 5: application/xml
+    Xml = 5,
+}
@@ -1,7 +1,9 @@
 //! Unsolicited responses for HTTP Commands
 use atat::atat_derive::AtatResp;
 
-/// 7.14 Network registration status +CREG
+/// HTTP command result +UUHTTPCR
+///
+/// `http_result` is 1 if the command succeeded, 0 otherwise.
 #[derive(Debug, Clone, AtatResp)]
 pub struct HttpResponse {
     #[at_arg(position = 0)]
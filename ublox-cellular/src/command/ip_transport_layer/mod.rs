@@ -9,9 +9,9 @@ use atat::atat_derive::AtatCmd;
 use embedded_nal::IpAddr;
 use responses::{
     CreateSocketResponse, SocketControlResponse, SocketData, SocketErrorResponse,
-    UDPSendToDataResponse, UDPSocketData, WriteSocketDataResponse,
+    UDPSendToDataResponse, UDPSocketData, UDPSocketDataLength, WriteSocketDataResponse,
 };
-use types::{HexMode, SocketControlParam, SocketProtocol, SslTlsStatus};
+use types::{HexMode, SocketControlParam, SocketOptionLevel, SocketProtocol, SslTlsStatus};
 
 use super::NoResponse;
 use ublox_sockets::SocketHandle;
@@ -74,6 +74,27 @@ pub struct CloseSocket {
     pub socket: SocketHandle,
 }
 
+/// Set socket option +USOSO
+///
+/// Sets the option `option_name` of the protocol `level` of the socket, like
+/// the BSD setsockopt routine. Some of the options are:
+/// - Socket level, 8: SO_KEEPALIVE; 0 disables and 1 enables the keepalive
+///   probes of a TCP connection
+/// - TCP level, 2: TCP_KEEPIDLE; idle time in milliseconds before the first
+///   keepalive probe is sent
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+USOSO", NoResponse)]
+pub struct SetSocketOption {
+    #[at_arg(position = 0)]
+    pub socket: SocketHandle,
+    #[at_arg(position = 1)]
+    pub level: SocketOptionLevel,
+    #[at_arg(position = 2)]
+    pub option_name: u8,
+    #[at_arg(position = 3)]
+    pub option_value: u32,
+}
+
 /// 25.8 Get Socket Error +USOER
 ///
 /// Retrieves the last error occurred in the last socket operation, stored in
@@ -97,7 +118,8 @@ pub struct GetSocketError;
 pub struct ConnectSocket {
     #[at_arg(position = 0)]
     pub socket: SocketHandle,
-    #[at_arg(position = 1, len = 39)]
+    /// IPv4 address, or IPv6 address in colon notation
+    #[at_arg(position = 1, len = 45)]
     pub remote_addr: IpAddr,
     #[at_arg(position = 2)]
     pub remote_port: u16,
@@ -169,8 +191,7 @@ pub struct PrepareWriteSocketDataBinary {
     force_receive_state = true
 )]
 pub struct WriteSocketDataBinary<'a> {
-    // FIXME:
-    // #[at_arg(position = 0, len = EgressChunkSize::to_usize())]
+    // At least `EGRESS_CHUNK_SIZE`, the size of the chunks written
     #[at_arg(position = 0, len = 1024)]
     pub data: &'a atat::serde_bytes::Bytes,
 }
@@ -187,7 +208,8 @@ pub struct WriteSocketDataBinary<'a> {
 pub struct PrepareUDPSendToDataBinary {
     #[at_arg(position = 0)]
     pub socket: SocketHandle,
-    #[at_arg(position = 1, len = 39)]
+    /// IPv4 address, or IPv6 address in colon notation
+    #[at_arg(position = 1, len = 45)]
     pub remote_addr: IpAddr,
     #[at_arg(position = 2)]
     pub remote_port: u16,
@@ -205,7 +227,8 @@ pub struct PrepareUDPSendToDataBinary {
     force_receive_state = true
 )]
 pub struct UDPSendToDataBinary<'a> {
-    #[at_arg(position = 0, len = 512)]
+    // At least `EGRESS_CHUNK_SIZE`, the size of the chunks written
+    #[at_arg(position = 0, len = 1024)]
     pub data: &'a atat::serde_bytes::Bytes,
 }
 
@@ -250,6 +273,19 @@ pub struct ReadUDPSocketData {
     pub length: usize,
 }
 
+/// 25.13 Receive From command (UDP only) +USORF
+///
+/// Query form of [`ReadUDPSocketData`], returning the total amount of unread
+/// data of the UDP socket. `length` must be 0.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+USORF", UDPSocketDataLength)]
+pub struct GetUDPSocketDataLength {
+    #[at_arg(position = 0)]
+    pub socket: SocketHandle,
+    #[at_arg(position = 1)]
+    pub length: usize,
+}
+
 /// 25.16 HEX mode configuration +UDCONF=1
 ///
 /// Enables/disables the HEX mode for +USOWR, +USOST, +USORD and +USORF AT
@@ -272,3 +308,102 @@ pub struct SocketControl {
     #[at_arg(position = 1)]
     pub param_id: SocketControlParam,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use atat::{AtatCmd, Digester};
+    use embedded_nal::Ipv6Addr;
+
+    /// 2001:db8::1, from the IPv6 documentation prefix (RFC 3849)
+    const IPV6: IpAddr = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+
+    #[test]
+    fn ipv6_remote_address() {
+        let connect = ConnectSocket {
+            socket: SocketHandle(0),
+            remote_addr: IPV6,
+            remote_port: 443,
+        };
+        assert_eq!(
+            connect.as_bytes().as_slice(),
+            b"AT+USOCO=0,\"2001:db8::1\",443\r\n"
+        );
+
+        let send_to = PrepareUDPSendToDataBinary {
+            socket: SocketHandle(1),
+            remote_addr: IPV6,
+            remote_port: 5683,
+            length: 4,
+        };
+        assert_eq!(
+            send_to.as_bytes().as_slice(),
+            b"AT+USOST=1,\"2001:db8::1\",5683,4\r\n"
+        );
+    }
+
+    #[test]
+    fn binary_payload() {
+        // NUL, CR, LF, the `@` prompt, Ctrl-Z and non-ASCII bytes are sent as is
+        let payload = [0x00, 0x0D, 0x0A, b'@', 0xFF, 0x1A];
+
+        let prepare = PrepareWriteSocketDataBinary {
+            socket: SocketHandle(0),
+            length: payload.len(),
+        };
+        assert_eq!(prepare.as_bytes().as_slice(), b"AT+USOWR=0,6\r\n");
+
+        let write = WriteSocketDataBinary {
+            data: atat::serde_bytes::Bytes::new(&payload),
+        };
+        assert_eq!(write.as_bytes().as_slice(), &payload);
+
+        let send_to = UDPSendToDataBinary {
+            data: atat::serde_bytes::Bytes::new(&payload),
+        };
+        assert_eq!(send_to.as_bytes().as_slice(), &payload);
+
+        let resp: WriteSocketDataResponse = atat::serde_at::from_slice(b"+USOWR: 0,6").unwrap();
+        assert_eq!(resp.socket, SocketHandle(0));
+        assert_eq!(resp.length, payload.len());
+    }
+
+    /// Digest `response` as the ingress does, and parse it as the response
+    /// to `cmd`
+    fn digest_response<A: AtatCmd<LEN>, const LEN: usize>(cmd: &A, response: &[u8]) -> A::Response {
+        let mut digester = atat::DefaultDigester::<crate::command::Urc>::default();
+        let (result, len) = digester.digest(response);
+        assert_eq!(len, response.len());
+        match result {
+            atat::DigestResult::Response(resp) => cmd.parse(resp).unwrap(),
+            _ => panic!("Not a response"),
+        }
+    }
+
+    #[test]
+    fn binary_payload_received() {
+        let read = ReadSocketData {
+            socket: SocketHandle(0),
+            length: 4,
+        };
+
+        let hex = digest_response(&read, b"\r\n+USORD: 0,4,\"000D0A40\"\r\n\r\nOK\r\n");
+        assert_eq!(hex.socket, SocketHandle(0));
+        assert_eq!(hex.length, 4);
+        assert_eq!(hex.data.unwrap().as_str(), "000D0A40");
+
+        // CR LF within the data does not end the response
+        let binary = digest_response(&read, b"\r\n+USORD: 0,4,\"\x00\r\n@\"\r\n\r\nOK\r\n");
+        assert_eq!(binary.length, 4);
+        assert_eq!(binary.data.unwrap().as_bytes(), &[0x00, 0x0D, 0x0A, b'@']);
+    }
+
+    #[test]
+    fn ipv6_source_address() {
+        let resp: UDPSocketData =
+            atat::serde_at::from_slice(b"+USORF: 1,\"2001:db8::1\",5683,4,\"74657374\"").unwrap();
+        assert_eq!(resp.remote_addr, IPV6);
+        assert_eq!(resp.remote_port, 5683);
+        assert_eq!(resp.length, 4);
+    }
+}
@@ -66,6 +66,15 @@ pub struct UDPSocketData {
     pub data: Option<String<{ INGRESS_CHUNK_SIZE * 2 }>>,
 }
 
+/// 25.13 Unread UDP Socket Data +USORF
+#[derive(Clone, AtatResp)]
+pub struct UDPSocketDataLength {
+    #[at_arg(position = 0)]
+    pub socket: SocketHandle,
+    #[at_arg(position = 1)]
+    pub length: usize,
+}
+
 /// 25.25 Socket control +USOCTL
 #[derive(Clone, AtatResp)]
 pub struct SocketControlResponse {
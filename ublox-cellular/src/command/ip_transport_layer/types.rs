@@ -20,6 +20,18 @@ pub enum SslTlsStatus {
     Enabled(SecurityProfileId),
 }
 
+/// Protocol level of a socket option of +USOSO
+#[derive(Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[at_enum(u16)]
+pub enum SocketOptionLevel {
+    /// 0: IP protocol
+    Ip = 0,
+    /// 6: TCP protocol
+    Tcp = 6,
+    /// 65535: socket level options
+    Socket = 65535,
+}
+
 /// Enables/disables the HEX mode for +USOWR, +USOST, +USORD and +USORF AT
 /// commands.
 #[derive(Clone, PartialEq, Eq, AtatEnum)]
@@ -3,12 +3,16 @@
 
 pub mod responses;
 pub mod types;
+pub mod urc;
 use atat::atat_derive::AtatCmd;
 use responses::{
     DateTime, ExtendedErrorReport, IndicatorControl, ModuleFunctionality,
     ReportMobileTerminationError,
 };
-use types::{AutomaticTimezone, Functionality, ResetMode, TerminationErrorMode};
+use types::{
+    AutomaticTimezone, Functionality, GreetingTextMode, ResetMode, TerminationErrorMode,
+    TimeZoneReporting,
+};
 
 use super::NoResponse;
 
@@ -99,6 +103,17 @@ pub struct SetAutomaticTimezoneUpdate {
     pub on_off: AutomaticTimezone,
 }
 
+/// Time zone reporting +CTZR
+///
+/// Enables the +CTZV / +CTZE URCs, reporting changes of the time zone
+/// provided by the network.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CTZR", NoResponse)]
+pub struct SetTimeZoneReporting {
+    #[at_arg(position = 0)]
+    pub reporting: TimeZoneReporting,
+}
+
 /// 5.19 Report mobile termination error +CMEE
 ///
 /// Configures the formatting of the result code +CME ERROR: <err> as an
@@ -142,3 +157,17 @@ pub struct GetReportMobileTerminationError;
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+CEER", ExtendedErrorReport)]
 pub struct GetExtendedErrorReport;
+
+/// Set greeting text +CSGT
+///
+/// Configures and activates / deactivates the greeting text, which is output
+/// at boot once the module is ready to accept AT commands. The configuration
+/// is stored in the non-volatile memory.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CSGT", NoResponse)]
+pub struct SetGreetingText<'a> {
+    #[at_arg(position = 0)]
+    pub mode: GreetingTextMode,
+    #[at_arg(position = 1, len = 49)]
+    pub text: &'a str,
+}
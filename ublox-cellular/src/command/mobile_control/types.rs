@@ -196,6 +196,17 @@ pub enum AutomaticTimezone {
     EnabledGMT = 2,
 }
 
+/// Time zone reporting
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum TimeZoneReporting {
+    /// 0: time zone reporting disabled
+    Disabled = 0,
+    /// 1: time zone reporting by +CTZV: <tz>
+    Enabled = 1,
+    /// 2: extended time zone reporting by +CTZE: <tz>,<dst>
+    Extended = 2,
+}
+
 #[derive(Clone, PartialEq, Eq, AtatEnum)]
 pub enum TerminationErrorMode {
     /// 0: +CME ERROR: <err> result code disabled and ERROR used
@@ -206,7 +217,8 @@ pub enum TerminationErrorMode {
     Verbose = 2,
 }
 
-#[derive(Clone, PartialEq, Eq, AtatEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PowerMode {
     ///MT is switched on with minimum functionality
     Minimum = 0,
@@ -220,6 +232,15 @@ pub enum PowerMode {
     MinimumWithoutSim = 19,
 }
 
+/// Greeting text mode
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum GreetingTextMode {
+    /// • 0: turn off the greeting text
+    Disabled = 0,
+    /// • 1: turn on the greeting text
+    Enabled = 1,
+}
+
 #[derive(Clone, PartialEq, Eq, AtatEnum)]
 pub enum STKMode {
     ///the SIM-toolkit interface in dedicated mode and fetching of proactive commands by SIM-APPL from the SIM-card are enabled
@@ -0,0 +1,24 @@
+//! Unsolicited responses for Mobile equipment control and status Commands
+use atat::atat_derive::AtatResp;
+use heapless::String;
+
+/// Time zone reporting +CTZV
+#[derive(Debug, Clone, AtatResp)]
+pub struct TimeZone {
+    /// Difference between local time and GMT, in quarters of an hour, e.g.
+    /// "+04"
+    #[at_arg(position = 0)]
+    pub tz: String<4>,
+}
+
+/// Extended time zone reporting +CTZE
+#[derive(Debug, Clone, AtatResp)]
+pub struct ExtendedTimeZone {
+    /// Difference between local time and GMT, in quarters of an hour, e.g.
+    /// "+04"
+    #[at_arg(position = 0)]
+    pub tz: String<4>,
+    /// Daylight saving time adjustment included in `tz`, in hours
+    #[at_arg(position = 1)]
+    pub dst: u8,
+}
@@ -6,17 +6,22 @@ pub mod device_data_security;
 pub mod device_lock;
 pub mod dns;
 pub mod file_system;
+pub mod ftp;
 pub mod general;
+pub mod gnss;
 pub mod gpio;
 pub mod http;
 pub mod ip_transport_layer;
 pub mod mobile_control;
+pub mod mqtt;
 pub mod network_service;
 pub mod psn;
 pub mod sms;
+pub mod supplementary_services;
 pub mod system_features;
 
 use atat::atat_derive::{AtatCmd, AtatResp, AtatUrc};
+use heapless::Vec;
 
 #[derive(Clone, AtatResp)]
 pub struct NoResponse;
@@ -25,6 +30,76 @@ pub struct NoResponse;
 #[at_cmd("", NoResponse)]
 pub struct AT;
 
+/// Maximum length of a [`RawCommand`], excluding the line termination
+pub const MAX_RAW_COMMAND: usize = 128;
+
+/// Maximum length of a [`RawResponse`]
+pub const MAX_RAW_RESPONSE: usize = 1024;
+
+/// Command given as text, e.g. `"AT+CGMI"`, for which the information text
+/// response is returned unparsed
+///
+/// The command must not exceed [`MAX_RAW_COMMAND`] bytes, which is not
+/// checked here.
+#[derive(Clone)]
+pub struct RawCommand<'a> {
+    pub cmd: &'a str,
+}
+
+/// Information text response of a [`RawCommand`], including intermediate
+/// lines, but without the final result code
+#[derive(Clone, Debug)]
+pub struct RawResponse(pub Vec<u8, MAX_RAW_RESPONSE>);
+
+impl atat::AtatResp for RawResponse {}
+
+impl<'a> atat::AtatCmd<{ MAX_RAW_COMMAND + 2 }> for RawCommand<'a> {
+    type Response = RawResponse;
+
+    const MAX_TIMEOUT_MS: u32 = 10_000;
+    const ATTEMPTS: u8 = 1;
+
+    fn as_bytes(&self) -> Vec<u8, { MAX_RAW_COMMAND + 2 }> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(self.cmd.as_bytes()).ok();
+        bytes.extend_from_slice(b"\r\n").ok();
+        bytes
+    }
+
+    fn parse(
+        &self,
+        resp: Result<&[u8], atat::InternalError>,
+    ) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        Vec::from_slice(resp)
+            .map(RawResponse)
+            .map_err(|_| atat::Error::Parse)
+    }
+}
+
+/// Split the parameters of an information text response on commas, except
+/// within quoted strings. Parameters that don't fit in `N` are dropped.
+pub(crate) fn split_fields<const N: usize>(params: &str) -> Vec<&str, N> {
+    let mut fields = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(params[start..i].trim()).ok();
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(params[start..].trim()).ok();
+    fields
+}
+
+/// Greeting text configured with +CSGT, output by the module at boot
+pub(crate) const GREETING: &str = "+UBOOT";
+
 #[derive(Debug, Clone, AtatUrc)]
 pub enum Urc {
     #[at_urc("+CGEV: NW DETACH")]
@@ -51,8 +126,17 @@ pub enum Urc {
     #[at_urc("+UUSOCL")]
     SocketClosed(ip_transport_layer::urc::SocketClosed),
 
+    /// The [`GREETING`] text, i.e. the module has booted
+    #[at_urc("+UBOOT")]
+    ModuleRebooted,
+
+    #[at_urc("+UUSIMSTAT")]
+    SimStatus(device_lock::urc::SimStatus),
+
     #[at_urc("+UMWI")]
     MessageWaitingIndication(sms::urc::MessageWaitingIndication),
+    #[at_urc("+CMTI")]
+    NewMessageIndication(sms::urc::NewMessageIndication),
     // #[at_urc("+CREG")]
     // NetworkRegistration(network_service::urc::NetworkRegistration),
     // #[at_urc("+CGREG")]
@@ -62,6 +146,32 @@ pub enum Urc {
     #[at_urc("+UREG")]
     ExtendedPSNetworkRegistration(psn::urc::ExtendedPSNetworkRegistration),
 
+    #[at_urc("+CUSD")]
+    Ussd(supplementary_services::urc::UnstructuredSupplementaryServiceData),
+
+    #[at_urc("+CTZV")]
+    TimeZone(mobile_control::urc::TimeZone),
+    #[at_urc("+CTZE")]
+    ExtendedTimeZone(mobile_control::urc::ExtendedTimeZone),
+
+    #[at_urc("+UUPSMR")]
+    PsmStatus(system_features::urc::PsmStatus),
+
+    #[at_urc("+UFOTASTAT")]
+    FotaStatus(system_features::urc::FotaStatus),
+
+    #[at_urc("+UUFTPCR")]
+    FtpCommandResult(ftp::urc::FtpCommandResult),
+
+    #[at_urc("+UULOC")]
+    Location(gnss::urc::Location),
+
     #[at_urc("+UUHTTPCR")]
     HttpResponse(http::urc::HttpResponse),
+
+    // Before +UUMQTTC, which is a prefix of it
+    #[at_urc("+UUMQTTCM")]
+    MqttUnreadMessages(mqtt::urc::MqttUnreadMessages),
+    #[at_urc("+UUMQTTC")]
+    MqttCommandResult(mqtt::urc::MqttCommandResult),
 }
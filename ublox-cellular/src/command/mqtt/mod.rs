@@ -0,0 +1,91 @@
+//! MQTT
+//!
+//! The section describes the u-blox proprietary AT commands that can be used
+//! for connecting to an MQTT broker, publishing messages and subscribing to
+//! topics. A PSD connection must be activated before using MQTT AT commands.
+//!
+//! The final result of the +UMQTTC commands is reported with the +UUMQTTC URC,
+//! and received messages are announced with the +UUMQTTCM URC.
+pub mod responses;
+pub mod types;
+pub mod urc;
+
+use atat::atat_derive::AtatCmd;
+use responses::{MqttCommandResponse, MqttMessage};
+use types::{MqttProfileOperation, MqttQos};
+
+use super::NoResponse;
+
+/// MQTT profile configuration +UMQTT
+///
+/// Configures the parameters of the MQTT client profile. The parameters are
+/// not saved in the non volatile memory.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTT", NoResponse)]
+pub struct SetMqttProfile {
+    #[at_arg(position = 0)]
+    pub operation: MqttProfileOperation,
+}
+
+/// MQTT command +UMQTTC
+///
+/// Logs the MQTT client out of the broker.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTTC=0", MqttCommandResponse, value_sep = false)]
+pub struct MqttLogout;
+
+/// MQTT command +UMQTTC
+///
+/// Logs the MQTT client in to the broker configured with +UMQTT. The result is
+/// reported with the +UUMQTTC: 1 URC.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTTC=1", MqttCommandResponse, value_sep = false)]
+pub struct MqttLogin;
+
+/// MQTT command +UMQTTC
+///
+/// Publishes `message` on `topic`. With `hex_mode` enabled, the message is
+/// given as hexadecimal digits, which allows binary payloads.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTTC=2,", MqttCommandResponse, value_sep = false)]
+pub struct MqttPublish<'a> {
+    #[at_arg(position = 0)]
+    pub qos: MqttQos,
+    #[at_arg(position = 1)]
+    pub retain: bool,
+    #[at_arg(position = 2)]
+    pub hex_mode: bool,
+    #[at_arg(position = 3, len = 256)]
+    pub topic: &'a str,
+    #[at_arg(position = 4, len = 1024)]
+    pub message: &'a str,
+}
+
+/// MQTT command +UMQTTC
+///
+/// Subscribes to `topic`, which may contain wildcards.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTTC=4,", MqttCommandResponse, value_sep = false)]
+pub struct MqttSubscribe<'a> {
+    #[at_arg(position = 0)]
+    pub max_qos: MqttQos,
+    #[at_arg(position = 1, len = 256)]
+    pub topic: &'a str,
+}
+
+/// MQTT command +UMQTTC
+///
+/// Unsubscribes from `topic`.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTTC=5,", MqttCommandResponse, value_sep = false)]
+pub struct MqttUnsubscribe<'a> {
+    #[at_arg(position = 0, len = 256)]
+    pub topic: &'a str,
+}
+
+/// MQTT command +UMQTTC
+///
+/// Reads one of the unread messages received on the subscribed topics.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMQTTC=6,1", MqttMessage, value_sep = false)]
+pub struct MqttReadMessage;
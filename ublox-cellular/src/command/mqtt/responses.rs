@@ -0,0 +1,39 @@
+//! Responses for MQTT Commands
+use crate::mqtt::{MAX_MQTT_PAYLOAD, MAX_MQTT_TOPIC};
+use atat::atat_derive::AtatResp;
+use atat::heapless_bytes::Bytes;
+use heapless::String;
+
+/// MQTT command +UMQTTC
+///
+/// Whether the command has been accepted; `result` is 1 on success. The final
+/// result is reported by the +UUMQTTC URC.
+#[derive(Debug, Clone, AtatResp)]
+pub struct MqttCommandResponse {
+    #[at_arg(position = 0)]
+    pub op_code: u8,
+    #[at_arg(position = 1)]
+    pub result: u8,
+}
+
+/// MQTT command +UMQTTC
+///
+/// Message read with +UMQTTC=6
+#[derive(Debug, Clone, AtatResp)]
+pub struct MqttMessage {
+    #[at_arg(position = 0)]
+    pub op_code: u8,
+    #[at_arg(position = 1)]
+    pub qos: u8,
+    #[at_arg(position = 2)]
+    pub topic_msg_length: usize,
+    #[at_arg(position = 3)]
+    pub topic_length: usize,
+    #[at_arg(position = 4)]
+    pub topic: String<MAX_MQTT_TOPIC>,
+    #[at_arg(position = 5)]
+    pub message_length: usize,
+    /// The message, including the surrounding quotes
+    #[at_arg(position = 6)]
+    pub message: Bytes<{ MAX_MQTT_PAYLOAD + 2 }>,
+}
@@ -0,0 +1,42 @@
+//! Argument and parameter types used by MQTT Commands and Responses
+use crate::services::data::ssl::SecurityProfileId;
+use atat::atat_derive::AtatEnum;
+use heapless::String;
+
+/// Quality of service of MQTT messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MqttQos {
+    /// • 0: at most once delivery
+    AtMostOnce = 0,
+    /// • 1: at least once delivery
+    AtLeastOnce = 1,
+    /// • 2: exactly once delivery
+    ExactlyOnce = 2,
+}
+
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum MqttProfileOperation {
+    /// - 0: MQTT client ID, unique on the broker
+    #[at_arg(value = 0)]
+    ClientId(String<256>),
+    /// - 2: MQTT broker server name and port. The factory-programmed port is
+    ///   1883, or 8883 when SSL encryption is enabled.
+    #[at_arg(value = 2)]
+    ServerName(String<128>, u16),
+    /// - 4: username and password for the MQTT broker
+    #[at_arg(value = 4)]
+    UsernamePassword(String<30>, String<30>),
+    /// - 10: inactivity timeout, the keep alive period in seconds. 0 disables
+    ///   the keep alive.
+    #[at_arg(value = 10)]
+    InactivityTimeout(u16),
+    /// - 11: MQTT secure option (SSL encryption); the second parameter is the
+    ///   USECMNG profile to use
+    #[at_arg(value = 11)]
+    Secure(bool, SecurityProfileId),
+    /// - 12: clean session; the broker discards the previous session of the
+    ///   client ID at login
+    #[at_arg(value = 12)]
+    CleanSession(bool),
+}
@@ -0,0 +1,27 @@
+//! Unsolicited responses for MQTT Commands
+use atat::atat_derive::AtatResp;
+
+/// MQTT command result +UUMQTTC
+///
+/// Final result of a +UMQTTC command with `op_code`. For the login command,
+/// `result` is the return code of the broker, 0 meaning that the connection
+/// was accepted. For the other commands, `result` is 1 on success.
+///
+/// An unsolicited +UUMQTTC: 0 is sent when the connection to the broker is
+/// lost.
+#[derive(Debug, Clone, AtatResp)]
+pub struct MqttCommandResult {
+    #[at_arg(position = 0)]
+    pub op_code: u8,
+    #[at_arg(position = 1)]
+    pub result: u8,
+}
+
+/// MQTT unread messages +UUMQTTCM
+#[derive(Debug, Clone, AtatResp)]
+pub struct MqttUnreadMessages {
+    #[at_arg(position = 0)]
+    pub op_code: u8,
+    #[at_arg(position = 1)]
+    pub unread: u16,
+}
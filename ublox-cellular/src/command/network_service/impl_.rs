@@ -1,5 +1,12 @@
-use super::types::NetworkRegistrationStat;
+use super::responses::{
+    BandMaskEntry, BandMasks, CellInfoEntry, CellInfoList, OperatorInfo, OperatorList,
+};
+use super::types::{NetworkRegistrationStat, OperatorStatus, RatAct};
+use super::{GetBandMask, GetCellInfo, GetOperatorList};
+use crate::command::split_fields;
 use crate::network::Error;
+use atat::{AtatCmd, InternalError};
+use heapless::{String, Vec};
 
 impl NetworkRegistrationStat {
     #[must_use]
@@ -14,3 +21,317 @@ impl NetworkRegistrationStat {
         }
     }
 }
+
+impl AtatCmd<11> for GetOperatorList {
+    type Response = OperatorList;
+
+    const MAX_TIMEOUT_MS: u32 = 180_000;
+    const ATTEMPTS: u8 = 1;
+
+    fn as_bytes(&self) -> Vec<u8, 11> {
+        Vec::from_slice(b"AT+COPS=?\r\n").unwrap()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        let resp = core::str::from_utf8(resp).map_err(|_| atat::Error::Parse)?;
+        parse_operator_list(resp).ok_or(atat::Error::Parse)
+    }
+}
+
+impl AtatCmd<15> for GetBandMask {
+    type Response = BandMasks;
+
+    fn as_bytes(&self) -> Vec<u8, 15> {
+        Vec::from_slice(b"AT+UBANDMASK?\r\n").unwrap()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        let resp = core::str::from_utf8(resp).map_err(|_| atat::Error::Parse)?;
+        parse_band_masks(resp).ok_or(atat::Error::Parse)
+    }
+}
+
+/// Parse `+UBANDMASK: <rat>,<bitmask1>[,<bitmask2>][,<rat>,<bitmask1>[,<bitmask2>]]`
+fn parse_band_masks(resp: &str) -> Option<BandMasks> {
+    let resp = resp.trim().strip_prefix("+UBANDMASK:")?;
+
+    let mut values = Vec::<u64, 6>::new();
+    for value in resp.split(',') {
+        values.push(value.trim().parse().ok()?).ok()?;
+    }
+
+    // At most two technologies are reported, so three values per technology
+    // means that `<bitmask2>` is included
+    let with_bitmask2 = matches!(values.len(), 3 | 6);
+    let chunk = if with_bitmask2 { 3 } else { 2 };
+
+    let mut masks = Vec::new();
+    for entry in values.chunks(chunk) {
+        if entry.len() != chunk {
+            return None;
+        }
+        masks
+            .push(BandMaskEntry {
+                rat: u8::try_from(entry[0]).ok()?,
+                bitmask1: entry[1],
+                bitmask2: entry.get(2).copied(),
+            })
+            .ok()?;
+    }
+
+    Some(BandMasks { masks })
+}
+
+impl AtatCmd<15> for GetCellInfo {
+    type Response = CellInfoList;
+
+    fn as_bytes(&self) -> Vec<u8, 15> {
+        Vec::from_slice(b"AT+UCELLINFO?\r\n").unwrap()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        let resp = core::str::from_utf8(resp).map_err(|_| atat::Error::Parse)?;
+        parse_cell_info(resp).ok_or(atat::Error::Parse)
+    }
+}
+
+/// Parse one `+UCELLINFO: <mode>,<type>,<MCC>,<MNC>,<LAC>,<CI>,...` line per
+/// cell, where GSM cells (type 0 and 1) report `<RxLev>` next, and UMTS cells
+/// `<scrambling_code>,<dl_frequency>,<rscp_lev>`. Lines that are not
+/// recognised are skipped.
+fn parse_cell_info(resp: &str) -> Option<CellInfoList> {
+    let mut cells = Vec::new();
+
+    for line in resp
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("+UCELLINFO:"))
+    {
+        let Some(cell) = parse_cell(line) else {
+            debug!("Skipping unrecognised cell {}", line);
+            continue;
+        };
+
+        if cells.push(cell).is_err() {
+            break;
+        }
+    }
+
+    Some(CellInfoList { cells })
+}
+
+fn parse_cell(line: &str) -> Option<CellInfoEntry> {
+    let mut fields = Vec::<&str, 12>::new();
+    for field in line.split(',').take(12) {
+        fields.push(field.trim()).ok();
+    }
+
+    let cell_type = fields.get(1)?.parse().ok()?;
+    let level = match cell_type {
+        0 | 1 => fields.get(6),
+        _ => fields.get(8),
+    };
+
+    Some(CellInfoEntry {
+        cell_type,
+        mcc: fields.get(2)?.parse().ok()?,
+        mnc: fields.get(3)?.parse().ok()?,
+        lac: u32::from_str_radix(fields.get(4)?, 16).ok()?,
+        ci: u32::from_str_radix(fields.get(5)?, 16).ok()?,
+        level: level.and_then(|l| l.parse().ok()),
+    })
+}
+
+/// Parse `+COPS: [(<stat>,<long>,<short>,<numeric>[,<AcT>])[,...]],,(list of
+/// supported <mode>s),(list of supported <format>s)`
+fn parse_operator_list(resp: &str) -> Option<OperatorList> {
+    let mut rest = resp.trim().strip_prefix("+COPS:")?.trim_start();
+    let mut operators = Vec::new();
+
+    while let Some(group) = rest.strip_prefix('(') {
+        let end = closing_paren(group)?;
+        let Some(operator) = parse_operator(&group[..end]) else {
+            // The supported modes and formats lists follow the operators
+            break;
+        };
+
+        if operators.push(operator).is_err() {
+            warn!("Operator scan found more operators than fit");
+            break;
+        }
+
+        rest = &group[end + 1..];
+        match rest.strip_prefix(',') {
+            // An empty field separates the operators from the supported modes
+            Some(r) if !r.starts_with(',') => rest = r,
+            _ => break,
+        }
+    }
+
+    Some(OperatorList { operators })
+}
+
+/// Index of the `)` closing the group, skipping parentheses within quoted
+/// operator names
+fn closing_paren(group: &str) -> Option<usize> {
+    let mut quoted = false;
+    group.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ')' if !quoted => Some(i),
+        _ => None,
+    })
+}
+
+fn parse_operator(group: &str) -> Option<OperatorInfo> {
+    let mut fields = split_fields::<5>(group).into_iter();
+
+    let stat = match fields.next()? {
+        "0" => OperatorStatus::Unknown,
+        "1" => OperatorStatus::Available,
+        "2" => OperatorStatus::Current,
+        "3" => OperatorStatus::Forbidden,
+        _ => return None,
+    };
+    let long = unquote(fields.next()?)?;
+    let short = unquote(fields.next()?)?;
+    let numeric = unquote(fields.next()?)?;
+    let act = fields.next().map(|act| match act {
+        "0" => RatAct::Gsm,
+        "1" => RatAct::GsmCompact,
+        "2" => RatAct::Utran,
+        "3" => RatAct::GsmGprsEdge,
+        "4" => RatAct::UtranHspda,
+        "5" => RatAct::UtranHsupa,
+        "6" => RatAct::UtranHspdaHsupa,
+        "7" => RatAct::Lte,
+        "8" => RatAct::EcGsmIot,
+        "9" => RatAct::Eutran,
+        _ => RatAct::Unknown,
+    });
+
+    Some(OperatorInfo {
+        stat,
+        long,
+        short,
+        numeric,
+        act,
+    })
+}
+
+/// Strip the quotes of a string field, truncating it to fit in `N` bytes
+fn unquote<const N: usize>(field: &str) -> Option<String<N>> {
+    let field = field.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut s = String::new();
+    for c in field.chars() {
+        if s.push(c).is_err() {
+            break;
+        }
+    }
+    Some(s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_cell_environment() {
+        let info = parse_cell_info(
+            "+UCELLINFO: 0,0,222,10,61EF,7D58,36,0,255,255\r\n\
+             +UCELLINFO: 0,1,222,10,61EF,7D59,20\r\n\
+             +UCELLINFO: 0,3,222,1,A3E,1C6D5E,102,10588,40,28",
+        )
+        .unwrap();
+
+        assert_eq!(info.cells.len(), 3);
+        assert_eq!(info.cells[0].lac, 0x61EF);
+        assert_eq!(info.cells[0].ci, 0x7D58);
+        assert_eq!(info.cells[0].level, Some(36));
+        assert_eq!(info.cells[1].cell_type, 1);
+        assert_eq!(info.cells[2].ci, 0x1C6D5E);
+        assert_eq!(info.cells[2].level, Some(40));
+    }
+
+    #[test]
+    fn parse_cell_environment_skips_unrecognised_lines() {
+        let info = parse_cell_info(
+            "+UCELLINFO: 0,0,222,10,61EF,7D58,36,0,255,255\r\n\
+             +UCELLINFO: 1,5\r\n\
+             +UCELLINFO: 0,1,222,10,61EF,7D59,20",
+        )
+        .unwrap();
+
+        assert_eq!(info.cells.len(), 2);
+        assert_eq!(info.cells[1].ci, 0x7D59);
+    }
+
+    #[test]
+    fn parse_band_mask() {
+        let masks = parse_band_masks("+UBANDMASK: 0,524420,1,185").unwrap();
+        assert_eq!(masks.masks.len(), 2);
+        assert_eq!(masks.masks[0].bitmask1, 524420);
+        assert_eq!(masks.masks[1].rat, 1);
+        assert_eq!(masks.masks[1].bitmask2, None);
+
+        let masks = parse_band_masks("+UBANDMASK: 0,524420,0,1,185,0").unwrap();
+        assert_eq!(masks.masks[1].bitmask1, 185);
+        assert_eq!(masks.masks[1].bitmask2, Some(0));
+
+        assert_eq!(parse_band_masks("+UBANDMASK: 0,abc"), None);
+        assert_eq!(parse_band_masks("+UBANDMASK: 0,524420,1,185,0,0,0"), None);
+    }
+
+    #[test]
+    fn parse_operator_scan() {
+        let list = parse_operator_list(
+            "+COPS: (2,\"Telia DK\",\"Telia\",\"23820\",7),(3,\"TDC\",\"TDC\",\"23801\",2),,(0,1,2,3,4),(0,1,2)",
+        )
+        .unwrap();
+
+        assert_eq!(list.operators.len(), 2);
+        assert_eq!(list.operators[0].stat, OperatorStatus::Current);
+        assert_eq!(list.operators[0].long.as_str(), "Telia DK");
+        assert_eq!(list.operators[0].numeric.as_str(), "23820");
+        assert_eq!(list.operators[0].act, Some(RatAct::Lte));
+        assert_eq!(list.operators[1].stat, OperatorStatus::Forbidden);
+        assert_eq!(list.operators[1].act, Some(RatAct::Utran));
+    }
+
+    #[test]
+    fn parse_operator_scan_quoted_names() {
+        let list = parse_operator_list(
+            "+COPS: (1,\"Op, (Test)\",\"Op,T\",\"23899\",7),(2,\"TDC\",\"TDC\",\"23801\",2),,(0,1,2,3,4),(0,1,2)",
+        )
+        .unwrap();
+
+        assert_eq!(list.operators.len(), 2);
+        assert_eq!(list.operators[0].long.as_str(), "Op, (Test)");
+        assert_eq!(list.operators[0].short.as_str(), "Op,T");
+        assert_eq!(list.operators[0].numeric.as_str(), "23899");
+        assert_eq!(list.operators[1].numeric.as_str(), "23801");
+    }
+
+    #[test]
+    fn parse_empty_operator_scan() {
+        let list = parse_operator_list("+COPS: ,,(0,1,2,3,4),(0,1,2)").unwrap();
+        assert!(list.operators.is_empty());
+    }
+
+    #[test]
+    fn parse_operator_scan_overflow() {
+        let mut resp: std::string::String = "+COPS: ".into();
+        for i in 0..10 {
+            resp.push_str(&format!("(1,\"Op{}\",\"Op{}\",\"238{:02}\",7),", i, i, i));
+        }
+        resp.push_str(",(0,1,2,3,4),(0,1,2)");
+
+        let list = parse_operator_list(&resp).unwrap();
+        assert_eq!(list.operators.len(), 8);
+    }
+}
@@ -8,10 +8,20 @@ pub mod urc;
 use super::NoResponse;
 use atat::atat_derive::AtatCmd;
 use responses::{
-    NetworkRegistrationStatus, OperatorSelection, RadioAccessTechnology, SignalQuality,
+    LpwaRadioAccessTechnology, MnoProfileSelection, NetworkRegistrationStatus, OperatorSelection,
+    RadioAccessTechnology, SignalQuality, SignalStrength,
 };
 use types::{NetworkRegistrationStat, NetworkRegistrationUrcConfig, OperatorSelectionMode};
 
+/// 7.3 Signal quality +CSQ
+///
+/// Returns the radio signal strength <`signal_power`> and <qual> from the MT.
+/// Reported values of 99 indicate that the value is not known or not
+/// detectable.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CSQ", SignalStrength)]
+pub struct GetSignalStrength;
+
 /// 7.4 Extended signal quality +CESQ
 ///
 /// Returns the radio signal strength <`signal_power`> and <qual> from the MT.
@@ -44,19 +54,38 @@ use types::{NetworkRegistrationStat, NetworkRegistrationUrcConfig, OperatorSelec
 pub struct GetSignalQuality;
 
 /// 7.5 Operator selection +COPS
+///
+/// Forces an attempt to select and register with the GSM/UMTS/LTE network
+/// operator, that can be chosen in the list of network operators returned by
+/// the test command. `oper` and `act` are only used in manual mode.
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+COPS", NoResponse, attempts = 1, timeout_ms = 180000)]
-pub struct SetOperatorSelection {
+pub struct SetOperatorSelection<'a> {
     #[at_arg(position = 0)]
     pub mode: OperatorSelectionMode,
     #[at_arg(position = 1)]
     pub format: Option<u8>,
+    #[at_arg(position = 2, len = 24)]
+    pub oper: Option<&'a str>,
+    #[at_arg(position = 3)]
+    pub act: Option<types::RatAct>,
 }
 
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+COPS?", OperatorSelection, attempts = 1, timeout_ms = 180000)]
 pub struct GetOperatorSelection;
 
+/// 7.5 Operator selection +COPS
+///
+/// Scans for available network operators (+COPS=?). The scan can take up to
+/// three minutes.
+///
+/// The information text response is a list of operators, followed by the
+/// supported modes and formats, which atat cannot deserialize, so it is parsed
+/// manually (see `impl_`).
+#[derive(Clone)]
+pub struct GetOperatorList;
+
 /// 7.8 Radio Access Technology (RAT) selection +URAT Forces the selection of
 /// the Radio Access Technology (RAT) in the protocol stack. On the subsequent
 /// network registration (+COPS, +CGATT) the selected RAT is used.
@@ -117,6 +146,80 @@ pub struct SetRadioAccessTechnology {
 #[at_cmd("+URAT?", RadioAccessTechnology)]
 pub struct GetRadioAccessTechnology;
 
+/// Radio Access Technology (RAT) selection +URAT, of LPWA modules
+///
+/// Selects LTE Cat M1 and/or NB-IoT. If both are given, `first` is preferred.
+/// The RAT selection must be changed in minimum functionality (AT+CFUN=0), and
+/// takes effect after a reboot.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+URAT", NoResponse)]
+pub struct SetLpwaRadioAccessTechnology {
+    #[at_arg(position = 0)]
+    pub first: types::LpwaRat,
+    #[at_arg(position = 1)]
+    pub second: Option<types::LpwaRat>,
+}
+
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+URAT?", LpwaRadioAccessTechnology)]
+pub struct GetLpwaRadioAccessTechnology;
+
+/// Mobile Network Operator profile selection +UMNOPROF
+///
+/// Automatically configures the module to be compliant to the requirements of
+/// various Mobile Network Operators. The profile must be changed in minimum
+/// functionality (AT+CFUN=0), and takes effect after a reboot.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMNOPROF", NoResponse)]
+pub struct SetMnoProfile {
+    #[at_arg(position = 0)]
+    pub profile: types::MnoProfile,
+}
+
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UMNOPROF?", MnoProfileSelection)]
+pub struct GetMnoProfile;
+
+/// Band selection bitmask +UBANDMASK
+///
+/// Sets the bands the module may use for the given radio access technology,
+/// as a bitmask where bit 0 is band 1. `bitmask2` covers bands 65 to 128.
+///
+/// **NOTES:**
+/// - Depending on the module, the setting is stored in the NVM and only takes
+///   effect after a reboot.
+/// - Bands not supported by the module are ignored.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UBANDMASK", NoResponse)]
+pub struct SetBandMask {
+    #[at_arg(position = 0)]
+    pub rat: types::BandMaskRat,
+    #[at_arg(position = 1)]
+    pub bitmask1: u64,
+    #[at_arg(position = 2)]
+    pub bitmask2: Option<u64>,
+}
+
+/// Band selection bitmask +UBANDMASK
+///
+/// Reads the band selection bitmasks of all radio access technologies. The
+/// number of bitmasks reported per technology depends on the module, so the
+/// response is parsed manually (see `impl_`).
+#[derive(Clone)]
+pub struct GetBandMask;
+
+/// Cell environment description +UCELLINFO
+///
+/// Reads the serving and neighbour cells, one `+UCELLINFO` line per cell.
+/// The parameters reported depend on the cell type, so the response is parsed
+/// manually (see `impl_`).
+///
+/// **NOTES:**
+/// - Only 2G and 3G cells are reported. Modules without 2G and 3G support
+///   return an error result code.
+#[derive(Clone)]
+pub struct GetCellInfo;
+
 /// 7.14 Network registration status +CREG
 ///
 /// Configures the network registration URC related to CS domain. Depending on the <n> parameter value, a URC
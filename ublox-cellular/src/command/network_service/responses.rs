@@ -1,11 +1,21 @@
 //! Responses for Network service Commands
 use super::types::{
-    NetworkRegistrationStat, NetworkRegistrationUrcConfig, OperatorNameFormat,
-    OperatorSelectionMode, RadioAccessTechnologySelected, RatAct,
+    LpwaRat, MnoProfile, NetworkRegistrationStat, NetworkRegistrationUrcConfig, OperatorNameFormat,
+    OperatorSelectionMode, OperatorStatus, RadioAccessTechnologySelected, RatAct,
 };
 use atat::atat_derive::AtatResp;
 use heapless::String;
 
+/// 7.3 Signal quality +CSQ
+#[derive(Debug, Clone, AtatResp)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SignalStrength {
+    #[at_arg(position = 0)]
+    pub signal_power: u8,
+    #[at_arg(position = 1)]
+    pub qual: u8,
+}
+
 /// 7.4 Extended signal quality +CESQ
 #[derive(Debug, Clone, AtatResp)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -35,6 +45,75 @@ pub struct OperatorSelection {
     pub act: Option<RatAct>,
 }
 
+/// Maximum number of operators kept from an operator scan
+pub const MAX_SCANNED_OPERATORS: usize = 8;
+
+/// 7.5 Operator selection +COPS
+///
+/// A single operator found by an operator scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OperatorInfo {
+    pub stat: OperatorStatus,
+    pub long: String<24>,
+    pub short: String<10>,
+    pub numeric: String<6>,
+    pub act: Option<RatAct>,
+}
+
+/// 7.5 Operator selection +COPS
+///
+/// Operators found by an operator scan. Operators that don't fit are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorList {
+    pub operators: heapless::Vec<OperatorInfo, MAX_SCANNED_OPERATORS>,
+}
+
+impl atat::AtatResp for OperatorList {}
+
+/// Band selection bitmask of a radio access technology, see [`BandMasks`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BandMaskEntry {
+    pub rat: u8,
+    /// Bands 1 to 64, bit 0 being band 1
+    pub bitmask1: u64,
+    /// Bands 65 to 128, if reported
+    pub bitmask2: Option<u64>,
+}
+
+/// Band selection bitmasks +UBANDMASK
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BandMasks {
+    pub masks: heapless::Vec<BandMaskEntry, 2>,
+}
+
+impl atat::AtatResp for BandMasks {}
+
+/// Maximum number of cells reported by +UCELLINFO that are kept
+pub const MAX_CELLS: usize = 8;
+
+/// Cell reported by +UCELLINFO
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellInfoEntry {
+    /// 0: GSM serving cell, 1: GSM neighbour cell, 2: UMTS serving cell, 3:
+    /// UMTS neighbour cell, 4: UMTS detected cell
+    pub cell_type: u8,
+    pub mcc: u16,
+    pub mnc: u16,
+    pub lac: u32,
+    pub ci: u32,
+    /// RxLev for GSM, RSCP level for UMTS cells
+    pub level: Option<u8>,
+}
+
+/// Cell environment description +UCELLINFO
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellInfoList {
+    pub cells: heapless::Vec<CellInfoEntry, MAX_CELLS>,
+}
+
+impl atat::AtatResp for CellInfoList {}
+
 /// 7.8 Radio Access Technology (RAT) selection +URAT
 #[derive(Clone, AtatResp)]
 pub struct RadioAccessTechnology {
@@ -42,6 +121,22 @@ pub struct RadioAccessTechnology {
     pub act: RadioAccessTechnologySelected,
 }
 
+/// Radio Access Technology (RAT) selection +URAT, of LPWA modules
+#[derive(Clone, AtatResp)]
+pub struct LpwaRadioAccessTechnology {
+    #[at_arg(position = 0)]
+    pub first: LpwaRat,
+    #[at_arg(position = 1)]
+    pub second: Option<LpwaRat>,
+}
+
+/// Mobile Network Operator profile selection +UMNOPROF
+#[derive(Clone, AtatResp)]
+pub struct MnoProfileSelection {
+    #[at_arg(position = 0)]
+    pub profile: MnoProfile,
+}
+
 /// 7.14 Network registration status +CREG
 #[derive(Clone, AtatResp)]
 pub struct NetworkRegistrationStatus {
@@ -181,3 +181,70 @@ pub enum OperatorNameFormat {
     #[at_arg(value = 2)]
     Numeric(String<6>),
 }
+
+/// Availability of an operator found by an operator scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OperatorStatus {
+    /// • 0: unknown
+    Unknown = 0,
+    /// • 1: available
+    Available = 1,
+    /// • 2: current
+    Current = 2,
+    /// • 3: forbidden
+    Forbidden = 3,
+}
+
+/// Radio access technology a band selection bitmask applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BandMaskRat {
+    /// • 0: LTE Cat M1
+    LteCatM1 = 0,
+    /// • 1: NB-IoT
+    NbIot = 1,
+}
+
+/// Mobile Network Operator profile, selecting the configuration the module
+/// applies for a network operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MnoProfile {
+    /// • 0: SW default
+    SwDefault = 0,
+    /// • 1: SIM ICCID/IMSI select
+    SimSelect = 1,
+    /// • 2: AT&T
+    Att = 2,
+    /// • 3: Verizon
+    Verizon = 3,
+    /// • 4: Telstra
+    Telstra = 4,
+    /// • 5: T-Mobile US
+    TMobileUs = 5,
+    /// • 6: China Telecom
+    ChinaTelecom = 6,
+    /// • 8: Sprint
+    Sprint = 8,
+    /// • 19: Vodafone
+    Vodafone = 19,
+    /// • 21: Telus
+    Telus = 21,
+    /// • 31: Deutsche Telekom
+    DeutscheTelekom = 31,
+    /// • 90: global
+    Global = 90,
+    /// • 100: standard Europe
+    StandardEurope = 100,
+}
+
+/// LTE radio access technology of LPWA modules, as selected with +URAT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LpwaRat {
+    /// • 7: LTE Cat M1
+    LteCatM1 = 7,
+    /// • 8: NB-IoT
+    NbIot = 8,
+}
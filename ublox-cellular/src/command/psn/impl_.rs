@@ -0,0 +1,129 @@
+use super::responses::{
+    is_unspecified, parse_pdp_address, BearerDynamicParameters, PDPContextDynamicParameters,
+};
+use super::GetPDPContextDynamicParameters;
+use crate::command::split_fields;
+use crate::network::ContextId;
+use atat::{AtatCmd, InternalError};
+use core::fmt::Write;
+use embedded_nal::IpAddr;
+use heapless::{String, Vec};
+
+impl AtatCmd<18> for GetPDPContextDynamicParameters {
+    type Response = PDPContextDynamicParameters;
+
+    fn as_bytes(&self) -> Vec<u8, 18> {
+        let mut s = String::<18>::new();
+        write!(s, "AT+CGCONTRDP={}\r\n", self.cid.0).ok();
+        Vec::from_slice(s.as_bytes()).unwrap()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        let resp = core::str::from_utf8(resp).map_err(|_| atat::Error::Parse)?;
+        parse_dynamic_parameters(resp).ok_or(atat::Error::Parse)
+    }
+}
+
+/// Parse one `+CGCONTRDP: <cid>,<bearer_id>,<apn>[,<local_addr_and_subnet_mask>
+/// [,<gw_addr>[,<DNS_prim_addr>[,<DNS_sec_addr>[,...]]]]]` line per bearer,
+/// ignoring the parameters following the DNS servers
+fn parse_dynamic_parameters(resp: &str) -> Option<PDPContextDynamicParameters> {
+    let mut bearers = Vec::new();
+
+    for line in resp
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("+CGCONTRDP:"))
+    {
+        let fields = split_fields::<7>(line);
+        let text = |i: usize| {
+            fields
+                .get(i)
+                .copied()
+                .map(unquote)
+                .filter(|f| !f.is_empty())
+        };
+        let dns = |i: usize| -> Option<IpAddr> {
+            parse_pdp_address(text(i)?).filter(|ip| !is_unspecified(ip))
+        };
+
+        let bearer = BearerDynamicParameters {
+            cid: ContextId(fields.first()?.parse().ok()?),
+            bearer_id: fields.get(1)?.parse().ok()?,
+            apn: truncated(unquote(fields.get(2)?)),
+            local_addr_and_subnet_mask: text(3).map(truncated),
+            gw_addr: text(4).map(truncated),
+            dns_prim_addr: dns(5),
+            dns_sec_addr: dns(6),
+        };
+
+        if bearers.push(bearer).is_err() {
+            break;
+        }
+    }
+
+    Some(PDPContextDynamicParameters { bearers })
+}
+
+fn unquote(field: &str) -> &str {
+    field
+        .strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+fn truncated<const N: usize>(value: &str) -> String<N> {
+    let mut s = String::new();
+    for c in value.chars() {
+        if s.push(c).is_err() {
+            break;
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use embedded_nal::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn parse_ipv4_context() {
+        let params = parse_dynamic_parameters(
+            "+CGCONTRDP: 1,5,\"internet.mnc001.mcc238.gprs\",\"10.52.1.7.255.255.255.0\",\"\",\"8.8.8.8\",\"0.0.0.0\",\"0.0.0.0\",\"0.0.0.0\",0,0,1500",
+        )
+        .unwrap();
+
+        assert_eq!(params.bearers.len(), 1);
+        assert_eq!(params.bearers[0].cid, ContextId(1));
+        assert_eq!(params.bearers[0].bearer_id, 5);
+        assert_eq!(
+            params.bearers[0].apn.as_str(),
+            "internet.mnc001.mcc238.gprs"
+        );
+        assert_eq!(params.bearers[0].gw_addr, None);
+        assert_eq!(
+            params.dns_servers(),
+            (Some(IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8))), None)
+        );
+    }
+
+    #[test]
+    fn parse_dual_stack_context() {
+        let params = parse_dynamic_parameters(
+            "+CGCONTRDP: 1,5,\"internet\",\"10.52.1.7.255.255.255.0\",\"\",\"0.0.0.0\",\"0.0.0.0\",\"\",\"\",0,0\r\n\
+             +CGCONTRDP: 1,5,\"internet\",\"32.1.13.184.0.0.0.0.0.0.0.0.0.0.0.7.255.255.255.255.255.255.255.255.0.0.0.0.0.0.0.0\",\"\",\"32.1.13.184.0.0.0.0.0.0.0.0.0.0.0.1\",\"32.1.13.184.0.0.0.0.0.0.0.0.0.0.0.2\",\"\",\"\",0,0",
+        )
+        .unwrap();
+
+        assert_eq!(params.bearers.len(), 2);
+        assert_eq!(params.bearers[0].dns_prim_addr, None);
+        assert_eq!(
+            params.dns_servers(),
+            (
+                Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))),
+                Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)))
+            )
+        );
+    }
+}
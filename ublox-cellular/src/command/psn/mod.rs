@@ -21,14 +21,15 @@
 //! instruct the GGSN to route down-link packets onto different `QoS` flows
 //! towards the TE.
 
+mod impl_;
 pub mod responses;
 pub mod types;
 pub mod urc;
 use atat::atat_derive::AtatCmd;
 use responses::{
-    EPSNetworkRegistrationStatus, ExtendedPSNetworkRegistrationStatus, GPRSAttached,
-    GPRSNetworkRegistrationStatus, PDPContextState, PacketSwitchedConfig,
-    PacketSwitchedNetworkData,
+    ContextDataCounters, EPSNetworkRegistrationStatus, ExtendedPSNetworkRegistrationStatus,
+    GPRSAttached, GPRSNetworkRegistrationStatus, PDPContextAddress, PDPContextState,
+    PacketSwitchedConfig, PacketSwitchedNetworkAddress, PacketSwitchedNetworkData,
 };
 use types::{
     AuthenticationType, EPSNetworkRegistrationUrcConfig, ExtendedPSNetworkRegistrationUrcConfig,
@@ -167,6 +168,21 @@ pub struct GetPacketSwitchedNetworkData {
     pub param: PacketSwitchedNetworkDataParam,
 }
 
+/// 18.9 Get Packet switched network-assigned data +UPSND
+///
+/// Like [`GetPacketSwitchedNetworkData`], for the address parameters
+/// [`IPAddress`](PacketSwitchedNetworkDataParam::IPAddress),
+/// [`DNS1`](PacketSwitchedNetworkDataParam::DNS1) and
+/// [`DNS2`](PacketSwitchedNetworkDataParam::DNS2).
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UPSND", PacketSwitchedNetworkAddress)]
+pub struct GetPacketSwitchedNetworkAddress {
+    #[at_arg(position = 0)]
+    pub profile_id: ProfileId,
+    #[at_arg(position = 1)]
+    pub param: PacketSwitchedNetworkDataParam,
+}
+
 /// 18.14 Set GPRS attach or detach +CGATT
 ///
 /// Register (attach) the MT to, or deregister (detach) the MT from the GPRS
@@ -266,6 +282,26 @@ pub struct SetPDPContextState {
 #[at_cmd("+CGACT?", heapless::Vec<PDPContextState, 7>, attempts = 1, timeout_ms = 150000, abortable = true)]
 pub struct GetPDPContextState;
 
+/// Show PDP address +CGPADDR
+///
+/// Returns the addresses assigned to the PDP context `cid`.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CGPADDR", PDPContextAddress)]
+pub struct GetPDPContextAddress {
+    #[at_arg(position = 0)]
+    pub cid: ContextId,
+}
+
+/// PDP context read dynamic parameters +CGCONTRDP
+///
+/// Returns the parameters the network assigned to the active PDP context
+/// `cid`, one line per bearer. The number of parameters reported depends on
+/// the module and the line, so the response is parsed manually (see `impl_`).
+#[derive(Clone)]
+pub struct GetPDPContextDynamicParameters {
+    pub cid: ContextId,
+}
+
 /// 18.26 Packet switched event reporting +CGEREP
 ///
 /// Configures sending of URCs from MT to the DTE, in case of certain events
@@ -397,6 +433,29 @@ pub struct SetEPSNetworkRegistrationStatus {
 #[at_cmd("+CEREG?", EPSNetworkRegistrationStatus)]
 pub struct GetEPSNetworkRegistrationStatus;
 
+/// GPRS byte counters reading +UGCNTRD
+///
+/// Reads the bytes sent and received on each active or previously active PDP
+/// context, for the current session and in total.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UGCNTRD", heapless::Vec<ContextDataCounters, 8>)]
+pub struct GetDataCounters;
+
+/// Set/reset GPRS byte counters +UGCNTSET
+///
+/// Sets the total byte counters of the PDP context `cid`, e.g. to 0 to reset
+/// them.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UGCNTSET", NoResponse)]
+pub struct SetDataCounters {
+    #[at_arg(position = 0)]
+    pub cid: ContextId,
+    #[at_arg(position = 1)]
+    pub total_bytes_sent: u64,
+    #[at_arg(position = 2)]
+    pub total_bytes_received: u64,
+}
+
 /// 18.39 Configure the authentication parameters of a PDP/EPS bearer +UAUTHREQ
 ///
 /// Configures the authentication parameters of a defined PDP/EPS bearer. The
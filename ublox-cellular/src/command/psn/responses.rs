@@ -7,6 +7,7 @@ use super::types::{
 };
 use crate::{command::network_service::types::RatAct, network::ProfileId, ContextId};
 use atat::atat_derive::AtatResp;
+use embedded_nal::{IpAddr, Ipv6Addr};
 use heapless::String;
 
 // 18.7 Packet switched data configuration +UPSD Sets or reads all the
@@ -37,6 +38,75 @@ pub struct PacketSwitchedNetworkData {
     pub param_tag: u8, // TODO: Create struct to contain
 }
 
+/// 18.9 Packet switched network-assigned data +UPSND
+///
+/// Response to the address parameters, i.e. the IP address and the DNS
+/// servers of the active PDP context.
+#[derive(Debug, Clone, AtatResp)]
+pub struct PacketSwitchedNetworkAddress {
+    #[at_arg(position = 0)]
+    pub profile: ProfileId,
+    #[at_arg(position = 1)]
+    pub param: PacketSwitchedNetworkDataParam,
+    #[at_arg(position = 2)]
+    pub address: String<64>,
+}
+
+impl PacketSwitchedNetworkAddress {
+    /// The address, or `None` if it is unassigned, e.g. "0.0.0.0"
+    pub fn address(&self) -> Option<IpAddr> {
+        parse_pdp_address(&self.address).filter(|ip| !is_unspecified(ip))
+    }
+}
+
+/// Parameters the network assigned to a bearer of an active PDP context, see
+/// [`PDPContextDynamicParameters`]
+///
+/// Only the parameters up to the DNS servers are decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerDynamicParameters {
+    pub cid: ContextId,
+    pub bearer_id: u8,
+    pub apn: String<99>,
+    pub local_addr_and_subnet_mask: Option<String<128>>,
+    pub gw_addr: Option<String<64>>,
+    /// Primary DNS server, `None` if unassigned
+    pub dns_prim_addr: Option<IpAddr>,
+    /// Secondary DNS server, `None` if unassigned
+    pub dns_sec_addr: Option<IpAddr>,
+}
+
+/// PDP context read dynamic parameters +CGCONTRDP
+///
+/// One entry per line of the response, e.g. an IPv4 and an IPv6 line for a
+/// dual stack context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PDPContextDynamicParameters {
+    pub bearers: heapless::Vec<BearerDynamicParameters, 2>,
+}
+
+impl atat::AtatResp for PDPContextDynamicParameters {}
+
+impl PDPContextDynamicParameters {
+    /// Primary and secondary DNS servers, in the order of the bearers, `None`
+    /// if unassigned
+    pub fn dns_servers(&self) -> (Option<IpAddr>, Option<IpAddr>) {
+        let mut servers = self
+            .bearers
+            .iter()
+            .flat_map(|bearer| [bearer.dns_prim_addr, bearer.dns_sec_addr])
+            .flatten();
+        (servers.next(), servers.next())
+    }
+}
+
+pub(super) fn is_unspecified(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_unspecified(),
+        IpAddr::V6(ip) => ip.is_unspecified(),
+    }
+}
+
 /// 18.14 GPRS attach or detach +CGATT Register (attach) the MT to, or
 /// deregister (detach) the MT from the GPRS service. After this command the MT
 /// remains in AT command mode. If the MT is already in the requested state
@@ -62,6 +132,63 @@ pub struct PDPContextState {
     pub status: PDPContextStatus,
 }
 
+/// Show PDP address +CGPADDR
+///
+/// For dual stack contexts, the IPv4 address is `pdp_addr_1` and the IPv6
+/// address `pdp_addr_2`.
+#[derive(Clone, AtatResp)]
+pub struct PDPContextAddress {
+    #[at_arg(position = 0)]
+    pub cid: ContextId,
+    #[at_arg(position = 1)]
+    pub pdp_addr_1: Option<String<64>>,
+    #[at_arg(position = 2)]
+    pub pdp_addr_2: Option<String<64>>,
+}
+
+impl PDPContextAddress {
+    /// Addresses of the context, in the order reported by the module
+    pub fn addresses(&self) -> impl Iterator<Item = IpAddr> + '_ {
+        [&self.pdp_addr_1, &self.pdp_addr_2]
+            .into_iter()
+            .filter_map(|addr| parse_pdp_address(addr.as_deref()?))
+    }
+}
+
+/// Parse a PDP address, which is either in the usual notation or, for IPv6,
+/// 16 dot-separated decimal bytes
+pub(super) fn parse_pdp_address(addr: &str) -> Option<IpAddr> {
+    if let Ok(ip) = addr.parse() {
+        return Some(ip);
+    }
+
+    let mut octets = [0u8; 16];
+    let mut parts = addr.split('.');
+    for octet in octets.iter_mut() {
+        *octet = parts.next()?.parse().ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(IpAddr::V6(Ipv6Addr::from(octets)))
+}
+
+/// GPRS byte counters reading +UGCNTRD
+#[derive(Clone, AtatResp)]
+pub struct ContextDataCounters {
+    #[at_arg(position = 0)]
+    pub cid: ContextId,
+    #[at_arg(position = 1)]
+    pub sent_session_bytes: u64,
+    #[at_arg(position = 2)]
+    pub received_session_bytes: u64,
+    #[at_arg(position = 3)]
+    pub sent_total_bytes: u64,
+    #[at_arg(position = 4)]
+    pub received_total_bytes: u64,
+}
+
 /// 18.27 GPRS network registration status +CGREG
 #[derive(Clone, AtatResp)]
 pub struct GPRSNetworkRegistrationStatus {
@@ -101,4 +228,32 @@ pub struct EPSNetworkRegistrationStatus {
     pub ci: Option<String<8>>,
     #[at_arg(position = 4)]
     pub act: Option<RatAct>,
+    /// Type of `reject_cause`, 0 for an EMM cause (3GPP TS 24.301)
+    #[at_arg(position = 5)]
+    pub cause_type: Option<u8>,
+    #[at_arg(position = 6)]
+    pub reject_cause: Option<u8>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use embedded_nal::Ipv4Addr;
+
+    #[test]
+    fn pdp_address() {
+        assert_eq!(
+            parse_pdp_address("10.52.1.7"),
+            Some(IpAddr::V4(Ipv4Addr::new(10, 52, 1, 7)))
+        );
+        assert_eq!(
+            parse_pdp_address("2001:db8::1"),
+            Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+        );
+        assert_eq!(
+            parse_pdp_address("32.1.13.184.0.0.0.0.0.0.0.0.0.0.0.1"),
+            Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)))
+        );
+        assert_eq!(parse_pdp_address("0.0.0"), None);
+    }
 }
@@ -531,7 +531,8 @@ pub enum GPRSNetworkRegistrationUrcConfig {
 }
 
 /// Controls the processing of URCs specified within this command.
-#[derive(Clone, PartialEq, Eq, AtatEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PSEventReportingMode {
     /// • 0 (default value): buffer URCs in the MT; if the buffer is full the
     /// oldest ones will be discarded
@@ -611,10 +612,11 @@ pub enum EPSNetworkRegistrationUrcConfig {
     /// • 2: network registration and location information URC +CEREG:
     /// <stat>[,[<tac>],[<ci>],[<AcT>]] enabled
     UrcVerbose = 2,
-    // • 3: network registration, location information and EMM cause value
-    // information URC +CEREG:
-    // <stat>[,[<tac>],[<ci>],[<AcT>][,<cause_type>,<reject_cause>]] enabled •
-    // 4: PSM, network registration and location information information URC
+    /// • 3: network registration, location information and EMM cause value
+    /// information URC +CEREG:
+    /// <stat>[,[<tac>],[<ci>],[<AcT>][,<cause_type>,<reject_cause>]] enabled
+    UrcVerboseCause = 3,
+    // • 4: PSM, network registration and location information information URC
     // +CEREG:
     // <stat>[,[<tac>],[<ci>],[<AcT>][,,[,[<Assigned_Active_Time>[,<Assigned_Periodic_TAU>]]]]]
     // enabled • 5: PSM, network registration, location information and EMM
@@ -0,0 +1,87 @@
+use super::responses::Message;
+use super::ReadMessage;
+use atat::{AtatCmd, InternalError};
+use core::fmt::Write;
+use heapless::{String, Vec};
+
+impl AtatCmd<16> for ReadMessage {
+    type Response = Message;
+
+    const MAX_TIMEOUT_MS: u32 = 10_000;
+
+    fn as_bytes(&self) -> Vec<u8, 16> {
+        let mut s = String::<16>::new();
+        write!(s, "AT+CMGR={}\r\n", self.index).ok();
+        Vec::from_slice(s.as_bytes()).unwrap()
+    }
+
+    fn parse(&self, resp: Result<&[u8], InternalError>) -> Result<Self::Response, atat::Error> {
+        let resp = resp.map_err(atat::Error::from)?;
+        let resp = core::str::from_utf8(resp).map_err(|_| atat::Error::Parse)?;
+        parse_message(resp).ok_or(atat::Error::Parse)
+    }
+}
+
+/// Parse `+CMGR: <stat>,<oa>,[<alpha>],<scts>[,...]<CR><LF><data>`
+fn parse_message(resp: &str) -> Option<Message> {
+    let resp = resp.trim().strip_prefix("+CMGR:")?;
+    let (header, data) = resp.split_once('\n').unwrap_or((resp, ""));
+
+    let mut fields = Vec::<&str, 12>::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in header.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(&header[start..i]).ok();
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&header[start..]).ok();
+
+    Some(Message {
+        stat: truncated(unquote(fields.first()?)),
+        oa: truncated(unquote(fields.get(1)?)),
+        scts: truncated(unquote(fields.get(3)?)),
+        data: truncated(data.trim_end()),
+    })
+}
+
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field
+        .strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+fn truncated<const N: usize>(value: &str) -> String<N> {
+    let mut s = String::new();
+    for c in value.chars() {
+        if s.push(c).is_err() {
+            break;
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_text_message() {
+        let msg = parse_message(
+            "+CMGR: \"REC UNREAD\",\"+393475234652\",,\"14/11/21,11:58:23+01\"\r\nHello world",
+        )
+        .unwrap();
+
+        assert_eq!(msg.stat.as_str(), "REC UNREAD");
+        assert_eq!(msg.oa.as_str(), "+393475234652");
+        assert_eq!(msg.scts.as_str(), "14/11/21,11:58:23+01");
+        assert_eq!(msg.data.as_str(), "Hello world");
+    }
+}
@@ -1,12 +1,118 @@
 //! ### 11 - Short Messages Service
 
+mod impl_;
 pub mod responses;
 pub mod types;
 pub mod urc;
 
 use super::NoResponse;
 use atat::atat_derive::AtatCmd;
-use types::MessageWaitingMode;
+use responses::{MessageFormat, MessageReference, ServiceCenterAddress};
+use types::{MessageFormatMode, MessageWaitingMode};
+
+/// Message format +CMGF
+///
+/// Selects the format of messages used with send, list, read and write
+/// commands and with the URCs generated when receiving messages.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CMGF", NoResponse)]
+pub struct SetMessageFormat {
+    #[at_arg(position = 0)]
+    pub mode: MessageFormatMode,
+}
+
+/// Message format +CMGF
+///
+/// Reads the currently selected message format.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CMGF?", MessageFormat)]
+pub struct GetMessageFormat;
+
+/// Read message +CMGR
+///
+/// Reads the message stored at `index` in the memory storage selected for
+/// reading. In text mode, the information text response consists of a header
+/// line and the message body on the following line, which is parsed manually
+/// (see `impl_`).
+#[derive(Clone)]
+pub struct ReadMessage {
+    pub index: u8,
+}
+
+/// Delete message +CMGD
+///
+/// Deletes the message stored at `index` in the memory storage selected for
+/// reading.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CMGD", NoResponse)]
+pub struct DeleteMessage {
+    #[at_arg(position = 0)]
+    pub index: u8,
+}
+
+/// Send message +CMGS
+///
+/// Sends a message from the DTE to the network, in text mode. The module
+/// answers with a prompt, after which the message body must be written using
+/// [`SendMessageBody`].
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CMGS", NoResponse)]
+pub struct PrepareSendMessage<'a> {
+    /// Destination address, e.g. "+4512345678"
+    #[at_arg(position = 0, len = 20)]
+    pub da: &'a str,
+}
+
+/// Send message +CMGS
+///
+/// Sends a message from the DTE to the network, in PDU mode. The module
+/// answers with a prompt, after which the hex encoded PDU must be written
+/// using [`SendMessageBody`].
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CMGS", NoResponse)]
+pub struct PrepareSendPdu {
+    /// Length of the TPDU in octets, excluding the SMSC address
+    #[at_arg(position = 0)]
+    pub length: usize,
+}
+
+/// Send message +CMGS
+///
+/// Message body, terminated by Ctrl-Z (0x1A), following the prompt of
+/// [`PrepareSendMessage`]. The message reference is returned once the message
+/// is sent to the network.
+#[derive(Clone, AtatCmd)]
+#[at_cmd(
+    "",
+    MessageReference,
+    value_sep = false,
+    cmd_prefix = "",
+    termination = "",
+    force_receive_state = true,
+    timeout_ms = 180000
+)]
+pub struct SendMessageBody<'a> {
+    #[at_arg(position = 0, len = 641)]
+    pub data: &'a atat::serde_bytes::Bytes,
+}
+
+/// Service center address +CSCA
+///
+/// Sets the address of the SMS service center, through which outgoing
+/// messages are sent.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CSCA", NoResponse)]
+pub struct SetServiceCenterAddress<'a> {
+    #[at_arg(position = 0, len = 20)]
+    pub sca: &'a str,
+}
+
+/// Service center address +CSCA
+///
+/// Reads the SMS service center address.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CSCA?", ServiceCenterAddress)]
+pub struct GetServiceCenterAddress;
 
 /// 11.29 Message waiting indication +UMWI
 ///
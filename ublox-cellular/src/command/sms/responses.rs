@@ -1 +1,46 @@
+//! Responses for Short Messages Service Commands
+use super::types;
+use atat::atat_derive::AtatResp;
+use heapless::String;
 
+/// Message format +CMGF
+#[derive(Debug, Clone, AtatResp)]
+pub struct MessageFormat {
+    #[at_arg(position = 0)]
+    pub mode: types::MessageFormatMode,
+}
+
+/// Send message +CMGS
+#[derive(Debug, Clone, AtatResp)]
+pub struct MessageReference {
+    /// Message reference assigned by the network
+    #[at_arg(position = 0)]
+    pub mr: u8,
+}
+
+/// Service center address +CSCA
+#[derive(Debug, Clone, AtatResp)]
+pub struct ServiceCenterAddress {
+    /// Service center address, empty if not provisioned
+    #[at_arg(position = 0)]
+    pub sca: String<20>,
+    /// Type of address, e.g. 145 for international numbers
+    #[at_arg(position = 1)]
+    pub tosca: Option<u8>,
+}
+
+/// Read message +CMGR
+///
+/// Message read in text mode. Fields that don't fit are truncated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// Message status, e.g. "REC UNREAD"
+    pub stat: String<10>,
+    /// Originator address
+    pub oa: String<20>,
+    /// Service center time stamp, "yy/MM/dd,hh:mm:ss+zz"
+    pub scts: String<20>,
+    pub data: String<160>,
+}
+
+impl atat::AtatResp for Message {}
@@ -30,3 +30,12 @@ pub enum MessageWaitingMode {
     #[at_arg(default)]
     Enabled = 1,
 }
+
+/// Format used for messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+pub enum MessageFormatMode {
+    /// • 0 (factory-programmed value): PDU mode
+    Pdu = 0,
+    /// • 1: text mode
+    Text = 1,
+}
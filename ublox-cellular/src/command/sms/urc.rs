@@ -1,6 +1,7 @@
 //! Responses for Internet protocol transport layer Commands
 use super::types;
 use atat::atat_derive::AtatResp;
+use heapless::String;
 
 /// 11.29 Message waiting indication +UMWI
 ///
@@ -22,3 +23,15 @@ pub struct MessageWaitingIndication {
     #[at_arg(position = 1)]
     pub code: types::MessageIndicationType,
 }
+
+/// New message indication +CMTI
+///
+/// Indicates that a new message has been received and stored at `index` in
+/// the memory storage `mem`, e.g. "SM" (SIM) or "ME" (module).
+#[derive(Debug, Clone, AtatResp)]
+pub struct NewMessageIndication {
+    #[at_arg(position = 0)]
+    pub mem: String<2>,
+    #[at_arg(position = 1)]
+    pub index: u8,
+}
@@ -0,0 +1,27 @@
+//! ### Supplementary services
+pub mod types;
+pub mod urc;
+
+use super::NoResponse;
+use atat::atat_derive::AtatCmd;
+use types::UssdMode;
+
+/// Unstructured supplementary service data +CUSD
+///
+/// Controls the Unstructured Supplementary Service Data (USSD), according to
+/// 3GPP TS 22.090. Both network and mobile initiated operations are
+/// supported. The network response is returned with the +CUSD URC.
+///
+/// The parameter `n` is used to disable / enable the presentation of the URC
+/// and to cancel an ongoing USSD session; `str` is the USSD string, and `dcs`
+/// its Cell Broadcast Data Coding Scheme (15: GSM 7 bit default alphabet).
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CUSD", NoResponse, attempts = 1, timeout_ms = 180000)]
+pub struct SetUssd<'a> {
+    #[at_arg(position = 0)]
+    pub n: UssdMode,
+    #[at_arg(position = 1, len = 182)]
+    pub str: Option<&'a str>,
+    #[at_arg(position = 2)]
+    pub dcs: Option<u8>,
+}
@@ -0,0 +1,34 @@
+//! Argument and parameter types used by Supplementary services Commands and Responses
+use atat::atat_derive::AtatEnum;
+
+/// Presentation of the +CUSD URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UssdMode {
+    /// • 0 (default value): disable the result code presentation
+    Disabled = 0,
+    /// • 1: enable the result code presentation
+    Enabled = 1,
+    /// • 2: cancel session (not applicable to read command response)
+    Cancel = 2,
+}
+
+/// Status of the USSD session, as reported by the +CUSD URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UssdStatus {
+    /// • 0: no further user action required (network initiated USSD-Notify,
+    ///   or no further information needed after mobile initiated operation)
+    NoFurtherAction = 0,
+    /// • 1: further user action required (network initiated USSD-Request, or
+    ///   further information needed after mobile initiated operation)
+    FurtherActionRequired = 1,
+    /// • 2: USSD terminated by network
+    TerminatedByNetwork = 2,
+    /// • 3: other local client has responded
+    OtherClientResponded = 3,
+    /// • 4: operation not supported
+    NotSupported = 4,
+    /// • 5: network time out
+    NetworkTimeout = 5,
+}
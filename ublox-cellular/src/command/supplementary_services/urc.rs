@@ -0,0 +1,17 @@
+//! Unsolicited responses for Supplementary services Commands
+use super::types::UssdStatus;
+use atat::atat_derive::AtatResp;
+use heapless::String;
+
+/// Unstructured supplementary service data +CUSD
+///
+/// USSD response from the network, or a network initiated operation.
+#[derive(Debug, Clone, AtatResp)]
+pub struct UnstructuredSupplementaryServiceData {
+    #[at_arg(position = 0)]
+    pub m: UssdStatus,
+    #[at_arg(position = 1)]
+    pub str: Option<String<182>>,
+    #[at_arg(position = 2)]
+    pub dcs: Option<u8>,
+}
@@ -6,9 +6,16 @@
 
 pub mod responses;
 pub mod types;
+pub mod urc;
 use atat::atat_derive::AtatCmd;
-use responses::{FactoryConfiguration, PowerSavingControl};
-use types::{FSFactoryRestoreType, NVMFactoryRestoreType, PowerSavingMode, Seconds};
+use responses::{
+    EdrxDynamicParameters, FactoryConfiguration, FirmwareInstallResult, PowerSavingControl,
+    Temperature,
+};
+use types::{
+    EdrxAccessTechnology, EdrxMode, FSFactoryRestoreType, NVMFactoryRestoreType, PowerSavingMode,
+    PsmMode, PsmReporting, Seconds,
+};
 
 use super::NoResponse;
 
@@ -39,6 +46,74 @@ pub struct SetPowerSavingControl {
 #[at_cmd("+UPSV?", PowerSavingControl)]
 pub struct GetPowerSavingControl;
 
+/// Power Saving Mode setting +CPSMS
+///
+/// Controls whether the UE wants to apply PSM or not, as well as the requested
+/// values of the timers used in PSM. The timers are given as one byte in an 8
+/// bit format, as a string of "0" and "1" characters, coded as the GPRS Timer
+/// 3 (periodic RAU/TAU) and GPRS Timer 2 (GPRS READY timer/active time)
+/// information elements of 3GPP TS 24.008.
+///
+/// **NOTES:**
+/// - The network may grant different timer values than the requested ones.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CPSMS", NoResponse)]
+pub struct SetPowerSavingMode<'a> {
+    #[at_arg(position = 0)]
+    pub mode: PsmMode,
+    #[at_arg(position = 1, len = 8)]
+    pub requested_periodic_rau: Option<&'a str>,
+    #[at_arg(position = 2, len = 8)]
+    pub requested_gprs_ready_timer: Option<&'a str>,
+    #[at_arg(position = 3, len = 8)]
+    pub requested_periodic_tau: Option<&'a str>,
+    #[at_arg(position = 4, len = 8)]
+    pub requested_active_time: Option<&'a str>,
+}
+
+/// eDRX setting +CEDRXS
+///
+/// Controls whether the UE wants to apply extended discontinuous reception
+/// (eDRX) or not, as well as the requested eDRX cycle and paging time window.
+/// Both values are given as half a byte in a 4 bit format, as a string of "0"
+/// and "1" characters, coded as in the Extended DRX parameters information
+/// element of 3GPP TS 24.008.
+///
+/// **NOTES:**
+/// - The requested paging time window is a u-blox extension, that is not
+///   supported by all modules.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CEDRXS", NoResponse)]
+pub struct SetEdrxSettings<'a> {
+    #[at_arg(position = 0)]
+    pub mode: EdrxMode,
+    #[at_arg(position = 1)]
+    pub act_type: EdrxAccessTechnology,
+    #[at_arg(position = 2, len = 4)]
+    pub requested_edrx_value: Option<&'a str>,
+    #[at_arg(position = 3, len = 4)]
+    pub requested_paging_time_window: Option<&'a str>,
+}
+
+/// Power saving mode state reporting +UPSMR
+///
+/// Enables the +UUPSMR URC, reporting the module entering and exiting PSM.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UPSMR", NoResponse)]
+pub struct SetPsmReporting {
+    #[at_arg(position = 0)]
+    pub mode: PsmReporting,
+}
+
+/// eDRX read dynamic parameters +CEDRXRDP
+///
+/// Returns the access technology type, the requested eDRX value, the eDRX
+/// value provided by the network and the paging time window, if eDRX is used
+/// for the cell that the MS is currently registered to.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+CEDRXRDP", EdrxDynamicParameters)]
+pub struct GetEdrxDynamicParameters;
+
 /// 19.25 Restore factory configuration +UFACTORY
 ///
 /// Force, at the next module boot, the restore of the factory configuration for
@@ -59,3 +134,37 @@ pub struct SetFactoryConfiguration {
 #[derive(Clone, AtatCmd)]
 #[at_cmd("+UFACTORY?", FactoryConfiguration)]
 pub struct GetFactoryConfiguration;
+
+/// Firmware update over the air +UFOTA
+///
+/// Starts downloading the firmware update image at `url` into the module.
+/// The command returns as soon as the download is started, its progress and
+/// result are reported with the +UFOTASTAT URC.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UFOTA", NoResponse, attempts = 1)]
+pub struct StartFotaDownload<'a> {
+    #[at_arg(position = 0, len = 256)]
+    pub url: &'a str,
+}
+
+/// Firmware installation +UFWINSTALL
+///
+/// Triggers the installation of the downloaded firmware update image. The
+/// module reboots, and is unresponsive while the image is installed.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UFWINSTALL", NoResponse, attempts = 1, timeout_ms = 10000)]
+pub struct InstallFirmware;
+
+/// Firmware installation +UFWINSTALL
+///
+/// Reads the result of the last firmware installation.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UFWINSTALL?", FirmwareInstallResult)]
+pub struct GetFirmwareInstallResult;
+
+/// Temperature sensor +UTEMP
+///
+/// Reads the internal temperature of the module, in degrees Celsius.
+#[derive(Clone, AtatCmd)]
+#[at_cmd("+UTEMP?", Temperature)]
+pub struct GetTemperature;
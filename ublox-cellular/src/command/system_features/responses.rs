@@ -1,6 +1,9 @@
 //! Responses for System features Commands
-use super::types::{FSFactoryRestoreType, NVMFactoryRestoreType, PowerSavingMode, Seconds};
+use super::types::{
+    EdrxAccessTechnology, FSFactoryRestoreType, NVMFactoryRestoreType, PowerSavingMode, Seconds,
+};
 use atat::atat_derive::AtatResp;
+use heapless::String;
 
 /// 19.8 Power saving control (Power Saving) +UPSV
 #[derive(AtatResp)]
@@ -11,6 +14,19 @@ pub struct PowerSavingControl {
     pub timeout: Option<Seconds>,
 }
 
+/// eDRX read dynamic parameters +CEDRXRDP
+#[derive(Clone, AtatResp)]
+pub struct EdrxDynamicParameters {
+    #[at_arg(position = 0)]
+    pub act_type: EdrxAccessTechnology,
+    #[at_arg(position = 1)]
+    pub requested_edrx_value: Option<String<4>>,
+    #[at_arg(position = 2)]
+    pub nw_provided_edrx_value: Option<String<4>>,
+    #[at_arg(position = 3)]
+    pub paging_time_window: Option<String<4>>,
+}
+
 /// 19.25 Restore factory configuration +UFACTORY
 #[derive(AtatResp)]
 pub struct FactoryConfiguration {
@@ -19,3 +35,19 @@ pub struct FactoryConfiguration {
     #[at_arg(position = 1)]
     pub nvm_op: NVMFactoryRestoreType,
 }
+
+/// Firmware installation +UFWINSTALL
+#[derive(Clone, AtatResp)]
+pub struct FirmwareInstallResult {
+    /// Result code of the last firmware installation, 0 on success
+    #[at_arg(position = 0)]
+    pub result: u16,
+}
+
+/// Temperature sensor +UTEMP
+#[derive(Clone, AtatResp)]
+pub struct Temperature {
+    /// Degrees Celsius
+    #[at_arg(position = 0)]
+    pub temperature: i16,
+}
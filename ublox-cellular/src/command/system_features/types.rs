@@ -36,6 +36,47 @@ pub enum PowerSavingMode {
 #[derive(Clone, PartialEq, Eq, AtatLen, Serialize, Deserialize)]
 pub struct Seconds(pub u32);
 
+/// Power Saving Mode (PSM) state
+#[derive(Clone, Copy, PartialEq, Eq, AtatEnum)]
+pub enum PsmMode {
+    /// • 0 (default value): disable the use of PSM
+    Disabled = 0,
+    /// • 1: enable the use of PSM
+    Enabled = 1,
+}
+
+/// eDRX state
+#[derive(Clone, Copy, PartialEq, Eq, AtatEnum)]
+pub enum EdrxMode {
+    /// • 0 (default value): disable the use of eDRX
+    Disabled = 0,
+    /// • 1: enable the use of eDRX
+    Enabled = 1,
+    /// • 2: enable the use of eDRX and enable the +CEDRXP URC
+    EnabledWithUrc = 2,
+    /// • 3: disable the use of eDRX and discard all parameters for eDRX or, if
+    ///   available, reset to the manufacturer specific default values
+    DisabledAndReset = 3,
+}
+
+/// Access technology type for eDRX
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EdrxAccessTechnology {
+    /// • 0: access technology is not using eDRX
+    NotUsingEdrx = 0,
+    /// • 1: EC-GSM-IoT (A/Gb mode)
+    EcGsmIot = 1,
+    /// • 2: GSM (A/Gb mode)
+    Gsm = 2,
+    /// • 3: UTRAN (Iu mode)
+    Utran = 3,
+    /// • 4: E-UTRAN (WB-S1 mode), i.e. LTE Cat M1
+    LteM = 4,
+    /// • 5: E-UTRAN (NB-S1 mode), i.e. NB-IoT
+    NbIot = 5,
+}
+
 /// FS factory restore type
 #[derive(Clone, Copy, PartialEq, Eq, AtatEnum)]
 pub enum FSFactoryRestoreType {
@@ -57,3 +98,38 @@ pub enum NVMFactoryRestoreType {
     /// • 2: for internal use only
     InternalUseOnly = 2,
 }
+
+/// State of a FOTA download, as reported by the +UFOTASTAT URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FotaState {
+    /// • 0: download in progress, <value> is the downloaded percentage
+    Downloading = 0,
+    /// • 1: download completed, the image is ready to be installed
+    Downloaded = 1,
+    /// • 2: download failed, <value> is the error code
+    DownloadFailed = 2,
+}
+
+/// Power saving mode state reporting
+#[derive(Clone, PartialEq, Eq, AtatEnum)]
+pub enum PsmReporting {
+    /// • 0: +UUPSMR URC disabled
+    Disabled = 0,
+    /// • 1: +UUPSMR URC enabled
+    Enabled = 1,
+}
+
+/// Power saving mode state, as reported by the +UUPSMR URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AtatEnum)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PsmState {
+    /// • 0: the module exited PSM
+    Exited = 0,
+    /// • 1: the module entered PSM
+    Entered = 1,
+    /// • 2: entering PSM was prevented, e.g. by pending activity
+    Prevented = 2,
+    #[at_arg(default)]
+    Unknown,
+}
@@ -0,0 +1,24 @@
+//! Unsolicited responses for System features Commands
+use super::types::{FotaState, PsmState};
+use atat::atat_derive::AtatResp;
+
+/// FOTA status +UFOTASTAT
+///
+/// Progress and result of a FOTA download started with +UFOTA.
+#[derive(Debug, Clone, AtatResp)]
+pub struct FotaStatus {
+    #[at_arg(position = 0)]
+    pub state: FotaState,
+    /// Downloaded percentage, or error code if the download failed
+    #[at_arg(position = 1)]
+    pub value: u16,
+}
+
+/// Power saving mode state +UUPSMR
+///
+/// Reports the module entering and exiting PSM, once enabled with +UPSMR.
+#[derive(Debug, Clone, AtatResp)]
+pub struct PsmStatus {
+    #[at_arg(position = 0)]
+    pub state: PsmState,
+}
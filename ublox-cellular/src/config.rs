@@ -1,4 +1,21 @@
+use core::marker::PhantomData;
+
+use embassy_time::Duration;
 use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+use heapless::String;
+
+use crate::client::State;
+use crate::command::{
+    control::types::BaudRate,
+    network_service::types::RadioAccessTechnologySelected,
+    psn::types::PSEventReportingMode,
+    system_features::{responses::EdrxDynamicParameters, types::EdrxAccessTechnology},
+};
+use crate::module::{Capability, Module};
+use crate::module_timing;
+use crate::network::{ContextId, MAX_CONTEXTS};
+use crate::services::data::{apn::APNInfo, CONTEXT_ID};
+use crate::signal::SignalQuality;
 
 pub struct NoPin;
 
@@ -30,11 +47,942 @@ pub trait CellularConfig {
     type ResetPin: OutputPin;
     type PowerPin: OutputPin;
     type VintPin: InputPin;
+    type DtrPin: OutputPin;
+
+    /// Module variant, deciding which commands are available.
+    ///
+    /// Defaults to the variant selected by feature. Functionality the module
+    /// does not support fails with
+    /// [`Error::UnsupportedByModule`](crate::error::Error::UnsupportedByModule),
+    /// or at compile time for configuration such as [`PSM`](CellularConfig::PSM).
+    const MODULE: Module = Module::from_features();
 
     const FLOW_CONTROL: bool = false;
+    /// Whether sockets use HEX mode (+UDCONF=1) by default, receiving their
+    /// data as hex digits. Data is always written with the binary syntax.
+    ///
+    /// Individual sockets can be opened with a different mode, e.g. with
+    /// [`tcp_connect_with_hex_mode`](crate::DataService::tcp_connect_with_hex_mode),
+    /// in which case the module is switched between modes before reading.
     const HEX_MODE: bool = true;
 
+    /// PIN used to unlock the SIM, if it is PIN protected.
+    const PIN: Option<&'static str> = None;
+
+    /// Number of times the SIM status is queried again while the SIM reports
+    /// being busy, waiting one second in between, before selecting the SIM
+    /// fails. The SIM is typically busy for a couple of seconds after a cold
+    /// boot.
+    const SIM_BUSY_RETRIES: u8 = 5;
+
+    /// Radio Access Technology (RAT) to select during initialization.
+    ///
+    /// If `None`, the RAT selection stored in the module is left untouched.
+    /// Selecting a RAT that the enabled module variant does not support is a
+    /// compile time error.
+    const RAT: Option<RadioAccessTechnologySelected> = None;
+
+    /// RAT selection to fall back to, when registration keeps failing on a
+    /// weak signal, e.g. adding GSM to an LTE only selection.
+    ///
+    /// If `None`, [`RAT`](CellularConfig::RAT) is always used. The fallback
+    /// is subject to the same compile time check as `RAT`.
+    const RAT_FALLBACK: Option<RatFallback> = None;
+
+    /// Power Saving Mode (PSM) timers to request from the network.
+    ///
+    /// If `None`, PSM is left untouched and UART power saving is disabled.
+    const PSM: Option<PsmConfig> = None;
+
+    /// Extended discontinuous reception (eDRX) parameters to request from the
+    /// network.
+    ///
+    /// If `None`, the eDRX settings stored in the module are left untouched.
+    const EDRX: Option<EdrxConfig> = None;
+
+    /// UART data rate to switch the module to (+IPR) during initialization.
+    ///
+    /// Requires [`reconfigure_baud`](CellularConfig::reconfigure_baud) to
+    /// switch the host side of the UART. If `None`, the data rate is left
+    /// untouched.
+    const BAUD_RATE: Option<BaudRate> = None;
+
+    /// Called with the old and new [`State`] whenever the device changes
+    /// state, e.g. to log transitions or feed a watchdog.
+    const ON_STATE_CHANGE: Option<fn(State, State)> = None;
+
+    /// Re-initialize the module if it has not answered any AT command for
+    /// this long while connected.
+    ///
+    /// If `None`, the liveness watchdog is disabled.
+    const LIVENESS_TIMEOUT: Option<Duration> = None;
+
+    /// Escalation of the recovery from consecutive failures, i.e. AT and
+    /// registration timeouts, from retrying up to power cycling the module.
+    ///
+    /// By default every failure resets the module with the `RESET_N` pin.
+    const RECOVERY: RecoveryLadder = RecoveryLadder::DEFAULT;
+
+    /// Number of times an AT command is attempted while it times out, before
+    /// failing. Abortable commands and commands with a response timeout of
+    /// 10 s or more are attempted once. Must be at least 1, which is checked
+    /// at compile time.
+    const AT_RETRIES: u8 = 5;
+
+    /// Time to try registering on the network, before the module is reset
+    /// and registration is attempted again.
+    const REGISTRATION_TIMEOUT: Duration = Duration::from_secs(3 * 60);
+
+    /// Number of times registration is re-attempted after
+    /// [`REGISTRATION_TIMEOUT`](CellularConfig::REGISTRATION_TIMEOUT), before
+    /// [`spin`](crate::GsmClient::spin) fails with
+    /// [`Error::RegistrationTimeout`](crate::error::Error::RegistrationTimeout),
+    /// or [`Error::RegistrationDenied`](crate::error::Error::RegistrationDenied)
+    /// if the network denied the registration.
+    ///
+    /// If `None`, registration is retried indefinitely.
+    const MAX_ATTACH_RETRIES: Option<u8> = None;
+
+    /// Time waited after the first failed registration attempt, before
+    /// registration is attempted again. The backoff doubles with every
+    /// following failed attempt, up to
+    /// [`RECONNECT_BACKOFF_MAX`](CellularConfig::RECONNECT_BACKOFF_MAX).
+    const RECONNECT_BACKOFF_BASE: Duration = Duration::from_secs(10);
+
+    /// Maximum backoff between registration attempts.
+    const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(10 * 60);
+
+    /// Spread the backoff randomly over its upper half, seeded from the IMEI,
+    /// so devices losing the network at the same time do not retry in
+    /// lockstep.
+    const RECONNECT_JITTER: bool = false;
+
+    /// Allow registering on roaming networks.
+    ///
+    /// If `false`, the module deregisters from roaming networks, and
+    /// [`spin`](crate::GsmClient::spin) fails with
+    /// [`Error::RoamingNotAllowed`](crate::error::Error::RoamingNotAllowed).
+    const ROAMING_ALLOWED: bool = true;
+
+    /// APN used by [`configured_data_service`](crate::GsmClient::configured_data_service).
+    ///
+    /// An APN, user name or password exceeding the module limits is a compile
+    /// time error.
+    const APN: APNInfo<'static> = APNInfo::automatic();
+
+    /// Additional PDP contexts, e.g. to separate management from user
+    /// traffic, activated independently with
+    /// [`activate_context`](crate::GsmClient::activate_context).
+    ///
+    /// The primary context 1 uses [`APN`](CellularConfig::APN), and is the
+    /// one the sockets use. Reusing context 1, duplicate context ids, or
+    /// more than 7 additional contexts are a compile time error.
+    const CONTEXTS: &'static [ContextConfig] = &[];
+
+    /// Let the UART DTR line control power saving (+UPSV: 3), instead of
+    /// ignoring changes to DTR.
+    ///
+    /// While DTR is OFF the module may enter power saving, see
+    /// [`set_dtr`](crate::GsmClient::set_dtr).
+    const DTR_POWER_SAVING: bool = false;
+
+    /// Let the module enter power saving once the UART has been idle for the
+    /// given timeout (+UPSV: 1).
+    ///
+    /// The timeout is rounded down to GSM frames of 4.615 ms, and must be
+    /// within 40 and 65000 frames (~0.2 s to ~300 s). As the first character
+    /// sent to a sleeping module is lost, a throwaway `AT` is sent ahead of
+    /// the first command after the timeout. Can not be combined with
+    /// [`DTR_POWER_SAVING`](CellularConfig::DTR_POWER_SAVING), and is not
+    /// used with [`PSM`](CellularConfig::PSM).
+    const UART_POWER_SAVING: Option<Duration> = None;
+
+    /// Packet domain event reporting mode (+CGEREP), controlling how +CGEV
+    /// URCs are reported as [`Event::PacketDomain`](crate::Event::PacketDomain).
+    ///
+    /// With the default [`PSEventReportingMode::CircularBufferUrcs`], the URCs
+    /// are buffered in the module rather than reported.
+    const PACKET_EVENT_REPORTING: PSEventReportingMode = PSEventReportingMode::CircularBufferUrcs;
+
+    /// Update the module clock and time zone from the network (NITZ, +CTZU)
+    /// during initialization, see
+    /// [`network_time`](crate::GsmClient::network_time).
+    const AUTO_TIMEZONE: bool = true;
+
+    /// Report time zone changes provided by the network as
+    /// [`Event::TimeZoneChanged`](crate::Event::TimeZoneChanged) (+CTZR).
+    const TIME_ZONE_REPORTING: bool = false;
+
+    /// Aid the GNSS receiver with the time and the coarse position known to
+    /// the module, for a faster first fix, see
+    /// [`gnss_power`](crate::GsmClient::gnss_power).
+    const GNSS_LOCAL_AIDING: bool = true;
+
+    /// Low time of the `PWR_ON` pin to switch the module on.
+    ///
+    /// Defaults to the timing of the module variant selected by feature.
+    const PWR_ON_TIME: Duration = module_timing::pwr_on_time();
+
+    /// Low time of the `PWR_ON` pin to gracefully switch the module off.
+    const PWR_OFF_TIME: Duration = module_timing::pwr_off_time();
+
+    /// Low time of the `RESET_N` pin to reset the module.
+    const RESET_TIME: Duration = module_timing::reset_time();
+
+    /// Time to wait after a reset, before the module is expected to answer AT
+    /// commands.
+    ///
+    /// Increase this for modules that boot slower than the module variant
+    /// selected by feature, as the module is considered unresponsive if it
+    /// does not answer shortly after this.
+    const BOOT_WAIT_TIME: Duration = module_timing::boot_wait_time();
+
     fn reset_pin(&mut self) -> Option<&mut Self::ResetPin>;
     fn power_pin(&mut self) -> Option<&mut Self::PowerPin>;
     fn vint_pin(&mut self) -> Option<&mut Self::VintPin>;
+    fn dtr_pin(&mut self) -> Option<&mut Self::DtrPin> {
+        None
+    }
+
+    /// Callback switching the host side of the UART to the given data rate,
+    /// after the module has been switched to it.
+    fn reconfigure_baud(&mut self) -> Option<&mut dyn FnMut(u32)> {
+        None
+    }
+
+    /// APN used by [`configured_data_service`](crate::GsmClient::configured_data_service),
+    /// [`APN`](CellularConfig::APN) unless the config is given at runtime
+    fn apn(&self) -> APNInfo<'static> {
+        Self::APN
+    }
+
+    /// RAT to select during initialization, [`RAT`](CellularConfig::RAT)
+    /// unless the config is given at runtime
+    fn rat(&self) -> Option<RadioAccessTechnologySelected> {
+        Self::RAT
+    }
+
+    /// PIN used to unlock the SIM, [`PIN`](CellularConfig::PIN) unless the
+    /// config is given at runtime
+    fn pin(&self) -> Option<&'static str> {
+        Self::PIN
+    }
+
+    /// Whether to enable RTS/CTS flow control,
+    /// [`FLOW_CONTROL`](CellularConfig::FLOW_CONTROL) unless the config is
+    /// given at runtime
+    fn flow_control(&self) -> bool {
+        Self::FLOW_CONTROL
+    }
+
+    /// UART data rate to switch to,
+    /// [`BAUD_RATE`](CellularConfig::BAUD_RATE) unless the config is given at
+    /// runtime
+    fn baud_rate(&self) -> Option<BaudRate> {
+        Self::BAUD_RATE
+    }
+}
+
+/// [`CellularConfig`] with the settings given at runtime
+///
+/// The APN, RAT, SIM PIN, flow control and baud rate are stored in the
+/// config rather than being associated consts, and all other settings keep
+/// their defaults. Pins that are not wired are left as [`NoPin`].
+///
+/// # Examples
+///
+/// ```ignore
+/// let config = Config::new(APNInfo::new("em"))
+///     .with_rat(RadioAccessTechnologySelected::Lte)
+///     .with_pin("1234")
+///     .with_flow_control(true)
+///     .with_reset_pin(reset);
+/// let device = GsmClient::new(client, &URC_CHANNEL, config);
+/// ```
+pub struct Config<R = NoPin, P = NoPin, V = NoPin, D = NoPin> {
+    apn: APNInfo<'static>,
+    rat: Option<RadioAccessTechnologySelected>,
+    pin: Option<&'static str>,
+    flow_control: bool,
+    baud_rate: Option<(BaudRate, fn(u32))>,
+    reset_pin: Option<R>,
+    power_pin: Option<P>,
+    vint_pin: Option<V>,
+    dtr_pin: Option<D>,
+}
+
+impl Config {
+    pub const fn new(apn: APNInfo<'static>) -> Self {
+        Self {
+            apn,
+            rat: None,
+            pin: None,
+            flow_control: false,
+            baud_rate: None,
+            reset_pin: None,
+            power_pin: None,
+            vint_pin: None,
+            dtr_pin: None,
+        }
+    }
+}
+
+impl<R, P, V, D> Config<R, P, V, D> {
+    /// RAT to select during initialization, see [`CellularConfig::RAT`]
+    ///
+    /// Selecting a RAT that the enabled module variant does not support
+    /// fails the initialization with
+    /// [`Error::UnsupportedByModule`](crate::error::Error::UnsupportedByModule).
+    pub fn with_rat(mut self, rat: RadioAccessTechnologySelected) -> Self {
+        self.rat = Some(rat);
+        self
+    }
+
+    /// PIN used to unlock the SIM, see [`CellularConfig::PIN`]
+    pub fn with_pin(mut self, pin: &'static str) -> Self {
+        self.pin = Some(pin);
+        self
+    }
+
+    /// Enable RTS/CTS flow control, see [`CellularConfig::FLOW_CONTROL`]
+    pub fn with_flow_control(mut self, enabled: bool) -> Self {
+        self.flow_control = enabled;
+        self
+    }
+
+    /// UART data rate to switch to during initialization, calling
+    /// `reconfigure` to switch the host side of the UART, see
+    /// [`CellularConfig::BAUD_RATE`]
+    pub fn with_baud_rate(mut self, rate: BaudRate, reconfigure: fn(u32)) -> Self {
+        self.baud_rate = Some((rate, reconfigure));
+        self
+    }
+
+    pub fn with_reset_pin<R2: OutputPin>(self, pin: R2) -> Config<R2, P, V, D> {
+        Config {
+            apn: self.apn,
+            rat: self.rat,
+            pin: self.pin,
+            flow_control: self.flow_control,
+            baud_rate: self.baud_rate,
+            reset_pin: Some(pin),
+            power_pin: self.power_pin,
+            vint_pin: self.vint_pin,
+            dtr_pin: self.dtr_pin,
+        }
+    }
+
+    pub fn with_power_pin<P2: OutputPin>(self, pin: P2) -> Config<R, P2, V, D> {
+        Config {
+            apn: self.apn,
+            rat: self.rat,
+            pin: self.pin,
+            flow_control: self.flow_control,
+            baud_rate: self.baud_rate,
+            reset_pin: self.reset_pin,
+            power_pin: Some(pin),
+            vint_pin: self.vint_pin,
+            dtr_pin: self.dtr_pin,
+        }
+    }
+
+    pub fn with_vint_pin<V2: InputPin>(self, pin: V2) -> Config<R, P, V2, D> {
+        Config {
+            apn: self.apn,
+            rat: self.rat,
+            pin: self.pin,
+            flow_control: self.flow_control,
+            baud_rate: self.baud_rate,
+            reset_pin: self.reset_pin,
+            power_pin: self.power_pin,
+            vint_pin: Some(pin),
+            dtr_pin: self.dtr_pin,
+        }
+    }
+
+    pub fn with_dtr_pin<D2: OutputPin>(self, pin: D2) -> Config<R, P, V, D2> {
+        Config {
+            apn: self.apn,
+            rat: self.rat,
+            pin: self.pin,
+            flow_control: self.flow_control,
+            baud_rate: self.baud_rate,
+            reset_pin: self.reset_pin,
+            power_pin: self.power_pin,
+            vint_pin: self.vint_pin,
+            dtr_pin: Some(pin),
+        }
+    }
+}
+
+impl<R, P, V, D> CellularConfig for Config<R, P, V, D>
+where
+    R: OutputPin,
+    P: OutputPin,
+    V: InputPin,
+    D: OutputPin,
+{
+    type ResetPin = R;
+    type PowerPin = P;
+    type VintPin = V;
+    type DtrPin = D;
+
+    fn reset_pin(&mut self) -> Option<&mut Self::ResetPin> {
+        self.reset_pin.as_mut()
+    }
+
+    fn power_pin(&mut self) -> Option<&mut Self::PowerPin> {
+        self.power_pin.as_mut()
+    }
+
+    fn vint_pin(&mut self) -> Option<&mut Self::VintPin> {
+        self.vint_pin.as_mut()
+    }
+
+    fn dtr_pin(&mut self) -> Option<&mut Self::DtrPin> {
+        self.dtr_pin.as_mut()
+    }
+
+    fn reconfigure_baud(&mut self) -> Option<&mut dyn FnMut(u32)> {
+        self.baud_rate
+            .as_mut()
+            .map(|(_, reconfigure)| reconfigure as &mut dyn FnMut(u32))
+    }
+
+    fn apn(&self) -> APNInfo<'static> {
+        self.apn.clone()
+    }
+
+    fn rat(&self) -> Option<RadioAccessTechnologySelected> {
+        self.rat.clone()
+    }
+
+    fn pin(&self) -> Option<&'static str> {
+        self.pin
+    }
+
+    fn flow_control(&self) -> bool {
+        self.flow_control
+    }
+
+    fn baud_rate(&self) -> Option<BaudRate> {
+        self.baud_rate.as_ref().map(|(rate, _)| rate.clone())
+    }
+}
+
+/// Additional PDP context with its own APN, see [`CellularConfig::CONTEXTS`]
+#[derive(Debug, Clone)]
+pub struct ContextConfig {
+    pub cid: ContextId,
+    pub apn: APNInfo<'static>,
+}
+
+/// Fallback of the RAT selection, see [`CellularConfig::RAT_FALLBACK`]
+///
+/// Once registration failed `after_attempts` times, the signal quality is
+/// sampled. If it is below the thresholds, `rat` is selected for the
+/// following attempts, until the device is dropped.
+#[derive(Debug, Clone)]
+pub struct RatFallback {
+    pub rat: RadioAccessTechnologySelected,
+    pub after_attempts: u8,
+    /// RSRP below which the signal is considered weak, in dBm, if the
+    /// serving cell is an LTE cell
+    pub rsrp_threshold: i16,
+    /// RSSI below which the signal is considered weak, in dBm, if no RSRP is
+    /// reported
+    pub rssi_threshold: i16,
+}
+
+impl RatFallback {
+    /// Whether `quality` is below the thresholds. No signal at all is weak.
+    pub(crate) fn is_weak(&self, quality: &SignalQuality) -> bool {
+        match (quality.rsrp, quality.rssi) {
+            (Some(rsrp), _) => rsrp < self.rsrp_threshold,
+            (None, Some(rssi)) => rssi < self.rssi_threshold,
+            (None, None) => true,
+        }
+    }
+}
+
+/// Recovery action taken for a failure, see [`RecoveryLadder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecoveryLevel {
+    /// Retry the failed operation
+    Retry,
+    /// Restart the module with +CFUN
+    SoftReset,
+    /// Reset the module with the `RESET_N` pin
+    HardReset,
+    /// Switch the module off and on again with the `PWR_ON` pin
+    PowerCycle,
+}
+
+/// Escalation of the recovery from consecutive failures, see
+/// [`CellularConfig::RECOVERY`]
+///
+/// The first `retries` failures are retried, the following `soft_resets`
+/// failures restart the module with +CFUN, and the next `hard_resets`
+/// failures reset it with the `RESET_N` pin. Any further failure power cycles
+/// the module. The count starts over once the module is connected again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryLadder {
+    pub retries: u8,
+    pub soft_resets: u8,
+    pub hard_resets: u8,
+}
+
+impl RecoveryLadder {
+    /// Reset the module with the `RESET_N` pin on every failure
+    pub const DEFAULT: Self = Self {
+        retries: 0,
+        soft_resets: 0,
+        hard_resets: u8::MAX,
+    };
+
+    /// Recovery action for the `failure`-th consecutive failure, counting
+    /// from 1
+    pub(crate) const fn level(&self, failure: u16) -> RecoveryLevel {
+        let retries = self.retries as u16;
+        let soft_resets = retries + self.soft_resets as u16;
+        let hard_resets = soft_resets + self.hard_resets as u16;
+
+        if failure <= retries {
+            RecoveryLevel::Retry
+        } else if failure <= soft_resets {
+            RecoveryLevel::SoftReset
+        } else if failure <= hard_resets {
+            RecoveryLevel::HardReset
+        } else {
+            RecoveryLevel::PowerCycle
+        }
+    }
+}
+
+/// Power Saving Mode (PSM) timers requested from the network with +CPSMS
+///
+/// The durations are rounded to the nearest value representable by the 3GPP
+/// timer encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PsmConfig {
+    /// Requested periodic TAU (T3412 extended)
+    pub periodic_tau: Duration,
+    /// Requested active time (T3324)
+    pub active_time: Duration,
+}
+
+/// GPRS Timer 3 units (3GPP TS 24.008 10.5.7.4a), as (bits 8-6, seconds)
+const GPRS_TIMER_3_UNITS: [(u8, u64); 7] = [
+    (0b011, 2),
+    (0b100, 30),
+    (0b101, 60),
+    (0b000, 10 * 60),
+    (0b001, 60 * 60),
+    (0b010, 10 * 60 * 60),
+    (0b110, 320 * 60 * 60),
+];
+
+/// GPRS Timer 2 units (3GPP TS 24.008 10.5.7.4), as (bits 8-6, seconds)
+const GPRS_TIMER_2_UNITS: [(u8, u64); 3] = [(0b000, 2), (0b001, 60), (0b010, 6 * 60)];
+
+impl PsmConfig {
+    pub(crate) fn periodic_tau_bits(&self) -> String<8> {
+        encode_gprs_timer(self.periodic_tau, &GPRS_TIMER_3_UNITS)
+    }
+
+    pub(crate) fn active_time_bits(&self) -> String<8> {
+        encode_gprs_timer(self.active_time, &GPRS_TIMER_2_UNITS)
+    }
+}
+
+/// Encode `duration` as a GPRS timer byte, using the unit and 5 bit value
+/// that gives the nearest representable duration.
+fn encode_gprs_timer(duration: Duration, units: &[(u8, u64)]) -> String<8> {
+    let secs = duration.as_secs();
+
+    let mut best = (0, 0, u64::MAX);
+    for &(unit, unit_secs) in units {
+        let value = ((secs + unit_secs / 2) / unit_secs).min(31);
+        let error = secs.abs_diff(value * unit_secs);
+        if error < best.2 {
+            best = (unit, value as u8, error);
+        }
+    }
+
+    to_bits(best.0 << 5 | best.1)
+}
+
+/// Extended discontinuous reception (eDRX) parameters requested from the
+/// network with +CEDRXS
+///
+/// The durations are rounded to the nearest value representable for the given
+/// access technology. Only the E-UTRAN access technologies (LTE-M and NB-IoT)
+/// are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdrxConfig {
+    pub act_type: EdrxAccessTechnology,
+    /// Requested eDRX cycle length
+    pub cycle: Duration,
+    /// Requested paging time window
+    pub paging_time_window: Duration,
+}
+
+/// E-UTRAN eDRX cycle lengths in ms (3GPP TS 24.008 10.5.5.32), indexed by
+/// their 4 bit value
+const EDRX_CYCLES_MS: [u64; 16] = [
+    5_120, 10_240, 20_480, 40_960, 61_440, 81_920, 102_400, 122_880, 143_360, 163_840, 327_680,
+    655_360, 1_310_720, 2_621_440, 5_242_880, 10_485_760,
+];
+
+/// eDRX cycle values that are valid in NB-S1 mode
+const NB_IOT_EDRX_CYCLES: [u8; 10] = [2, 3, 5, 9, 10, 11, 12, 13, 14, 15];
+
+impl EdrxConfig {
+    pub(crate) fn cycle_bits(&self) -> String<4> {
+        let ms = self.cycle.as_millis();
+        let nearest = |value: &u8| ms.abs_diff(EDRX_CYCLES_MS[*value as usize]);
+
+        let value = if self.act_type == EdrxAccessTechnology::NbIot {
+            NB_IOT_EDRX_CYCLES.iter().copied().min_by_key(nearest)
+        } else {
+            (0..16).min_by_key(nearest)
+        };
+        to_bits(value.unwrap_or(0))
+    }
+
+    pub(crate) fn paging_time_window_bits(&self) -> String<4> {
+        let step = paging_time_window_step_ms(self.act_type);
+        let value = ((self.paging_time_window.as_millis() + step / 2) / step).clamp(1, 16) - 1;
+        to_bits(value as u8)
+    }
+}
+
+/// Paging time window is given in steps of 1.28s for WB-S1 mode and 2.56s for
+/// NB-S1 mode (3GPP TS 24.008 10.5.5.32)
+fn paging_time_window_step_ms(act_type: EdrxAccessTechnology) -> u64 {
+    if act_type == EdrxAccessTechnology::NbIot {
+        2_560
+    } else {
+        1_280
+    }
+}
+
+/// eDRX parameters currently in use, as provided by the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdrxStatus {
+    pub act_type: EdrxAccessTechnology,
+    /// eDRX cycle length requested by the module
+    pub requested_cycle: Option<Duration>,
+    /// eDRX cycle length granted by the network
+    pub network_cycle: Option<Duration>,
+    /// Paging time window granted by the network
+    pub paging_time_window: Option<Duration>,
+}
+
+impl From<EdrxDynamicParameters> for EdrxStatus {
+    fn from(v: EdrxDynamicParameters) -> Self {
+        let decode_cycle = |bits: Option<String<4>>| {
+            from_bits(bits?)
+                .map(|value| Duration::from_millis(EDRX_CYCLES_MS[value as usize & 0xF]))
+        };
+        let step = paging_time_window_step_ms(v.act_type);
+
+        Self {
+            act_type: v.act_type,
+            requested_cycle: decode_cycle(v.requested_edrx_value),
+            network_cycle: decode_cycle(v.nw_provided_edrx_value),
+            paging_time_window: v
+                .paging_time_window
+                .and_then(from_bits)
+                .map(|value| Duration::from_millis((value as u64 + 1) * step)),
+        }
+    }
+}
+
+/// Format the `N` lowest bits of `value` as a string of "0" and "1"
+fn to_bits<const N: usize>(value: u8) -> String<N> {
+    let mut bits = String::new();
+    for i in (0..N).rev() {
+        bits.push(if value & (1 << i) != 0 { '1' } else { '0' })
+            .ok();
+    }
+    bits
+}
+
+fn from_bits<const N: usize>(bits: String<N>) -> Option<u8> {
+    u8::from_str_radix(bits.as_str(), 2).ok()
+}
+
+/// Whether the enabled module variant supports the given RAT selection.
+const fn rat_supported(rat: &Option<RadioAccessTechnologySelected>) -> bool {
+    match rat {
+        None => true,
+        Some(rat) => rat_selection_supported(rat),
+    }
+}
+
+/// Whether the enabled module variant supports the RAT of the fallback.
+const fn rat_fallback_supported(fallback: &Option<RatFallback>) -> bool {
+    match fallback {
+        None => true,
+        Some(fallback) => rat_selection_supported(&fallback.rat),
+    }
+}
+
+pub(crate) const fn rat_selection_supported(rat: &RadioAccessTechnologySelected) -> bool {
+    let gsm_only = cfg!(any(
+        feature = "leon-g1",
+        feature = "sara-g3",
+        feature = "sara-g4"
+    ));
+    let no_lte = gsm_only
+        || cfg!(any(
+            feature = "sara-u1",
+            feature = "sara-u2",
+            feature = "lisa-u2"
+        ));
+    let lte_only = cfg!(feature = "sara-r5");
+
+    match rat {
+        RadioAccessTechnologySelected::GsmGprsEGprs => !lte_only,
+        RadioAccessTechnologySelected::Umts | RadioAccessTechnologySelected::GsmUmts(_) => {
+            !gsm_only && !lte_only
+        }
+        RadioAccessTechnologySelected::Lte => !no_lte,
+        RadioAccessTechnologySelected::GsmUmtsLte(..) => !no_lte && !lte_only,
+        RadioAccessTechnologySelected::GsmLte(_) | RadioAccessTechnologySelected::UmtsLte(_) => {
+            !no_lte && !lte_only && !cfg!(feature = "lara-r6")
+        }
+    }
+}
+
+/// Whether the additional contexts have unique ids, other than the primary
+/// context, and APNs within the module limits
+const fn contexts_valid(contexts: &[ContextConfig]) -> bool {
+    if contexts.len() >= MAX_CONTEXTS {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < contexts.len() {
+        let cid = contexts[i].cid.0;
+        if cid == 0 || cid == CONTEXT_ID.0 || !contexts[i].apn.fits() {
+            return false;
+        }
+
+        let mut j = i + 1;
+        while j < contexts.len() {
+            if contexts[j].cid.0 == cid {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Length of a GSM frame, in microseconds
+const GSM_FRAME_US: u64 = 4615;
+
+/// `duration` in GSM frames, the unit of the +UPSV timeout
+pub(crate) const fn gsm_frames(duration: Duration) -> u64 {
+    duration.as_micros() / GSM_FRAME_US
+}
+
+/// Whether the UART power saving timeout is within the +UPSV limits, and not
+/// combined with DTR controlled power saving
+const fn uart_power_saving_valid(timeout: Option<Duration>, dtr: bool) -> bool {
+    match timeout {
+        Some(timeout) => !dtr && matches!(gsm_frames(timeout), 40..=65000),
+        None => true,
+    }
+}
+
+/// Compile time validation of [`CellularConfig`] constants.
+pub(crate) struct ConfigCheck<C>(PhantomData<C>);
+
+impl<C: CellularConfig> ConfigCheck<C> {
+    /// All checks below, evaluated once by
+    /// [`Device::new`](crate::GsmClient::new), so an invalid configuration
+    /// fails the build whether or not the functionality is used
+    pub(crate) const ALL: () = {
+        let ((), (), (), (), (), (), ()) = (
+            Self::RAT,
+            Self::APN,
+            Self::CONTEXTS,
+            Self::AT_RETRIES,
+            Self::UART_POWER_SAVING,
+            Self::PSM,
+            Self::EDRX,
+        );
+    };
+
+    const RAT: () = assert!(
+        rat_supported(&C::RAT) && rat_fallback_supported(&C::RAT_FALLBACK),
+        "CellularConfig::RAT or RAT_FALLBACK is not supported by the selected module"
+    );
+
+    const APN: () = assert!(
+        C::APN.fits(),
+        "CellularConfig::APN exceeds the APN, user name or password length limit"
+    );
+
+    const CONTEXTS: () = assert!(
+        contexts_valid(C::CONTEXTS),
+        "CellularConfig::CONTEXTS has an invalid or duplicate context id, or an APN exceeding the length limits"
+    );
+
+    const AT_RETRIES: () = assert!(
+        C::AT_RETRIES >= 1,
+        "CellularConfig::AT_RETRIES must be at least 1"
+    );
+
+    const UART_POWER_SAVING: () = assert!(
+        uart_power_saving_valid(C::UART_POWER_SAVING, C::DTR_POWER_SAVING),
+        "CellularConfig::UART_POWER_SAVING must be within 40 and 65000 GSM frames, without DTR_POWER_SAVING"
+    );
+
+    const PSM: () = assert!(
+        C::PSM.is_none() || C::MODULE.supports(Capability::Psm),
+        "CellularConfig::PSM is not supported by the selected module"
+    );
+
+    const EDRX: () = assert!(
+        C::EDRX.is_none() || C::MODULE.supports(Capability::Edrx),
+        "CellularConfig::EDRX is not supported by the selected module"
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::services::data::apn::Apn;
+
+    #[test]
+    fn rat_fallback_on_weak_signal() {
+        let fallback = RatFallback {
+            rat: RadioAccessTechnologySelected::GsmLte(
+                crate::command::network_service::types::RatPreferred::Lte,
+            ),
+            after_attempts: 2,
+            rsrp_threshold: -120,
+            rssi_threshold: -100,
+        };
+        let quality = |rssi, rsrp| SignalQuality {
+            rssi,
+            rsrp,
+            ..SignalQuality::default()
+        };
+
+        assert!(fallback.is_weak(&quality(Some(-70), Some(-125))));
+        assert!(!fallback.is_weak(&quality(Some(-105), Some(-110))));
+        assert!(fallback.is_weak(&quality(Some(-105), None)));
+        assert!(!fallback.is_weak(&quality(Some(-90), None)));
+        assert!(fallback.is_weak(&quality(None, None)));
+    }
+
+    #[test]
+    fn config_builder() {
+        let mut config = Config::new(APNInfo::new("em"))
+            .with_rat(RadioAccessTechnologySelected::Lte)
+            .with_pin("1234")
+            .with_flow_control(true)
+            .with_baud_rate(BaudRate::B115200, |_| {});
+
+        assert!(matches!(config.apn().apn, Apn::Given("em")));
+        assert_eq!(config.rat(), Some(RadioAccessTechnologySelected::Lte));
+        assert_eq!(config.pin(), Some("1234"));
+        assert!(config.flow_control());
+        assert!(config.baud_rate() == Some(BaudRate::B115200));
+        assert!(config.reconfigure_baud().is_some());
+        assert!(config.reset_pin().is_none());
+
+        let mut config = config.with_reset_pin(NoPin);
+        assert!(config.reset_pin().is_some());
+        assert!(config.dtr_pin().is_none());
+    }
+
+    #[test]
+    fn recovery_ladder_escalates() {
+        let ladder = RecoveryLadder {
+            retries: 2,
+            soft_resets: 1,
+            hard_resets: 1,
+        };
+        let levels: [RecoveryLevel; 6] = core::array::from_fn(|i| ladder.level(i as u16 + 1));
+        assert_eq!(
+            levels,
+            [
+                RecoveryLevel::Retry,
+                RecoveryLevel::Retry,
+                RecoveryLevel::SoftReset,
+                RecoveryLevel::HardReset,
+                RecoveryLevel::PowerCycle,
+                RecoveryLevel::PowerCycle,
+            ]
+        );
+
+        assert_eq!(RecoveryLadder::DEFAULT.level(1), RecoveryLevel::HardReset);
+    }
+
+    #[test]
+    fn psm_timer_encoding() {
+        let psm = PsmConfig {
+            periodic_tau: Duration::from_secs(60 * 60),
+            active_time: Duration::from_secs(60),
+        };
+        assert_eq!(psm.periodic_tau_bits().as_str(), "00000110");
+        assert_eq!(psm.active_time_bits().as_str(), "00011110");
+    }
+
+    #[test]
+    fn psm_timer_rounds_to_nearest() {
+        let psm = PsmConfig {
+            // 95 minutes is nearest to 10 x 10 minutes
+            periodic_tau: Duration::from_secs(95 * 60),
+            // 100s is nearest to 2 x 1 minute
+            active_time: Duration::from_secs(100),
+        };
+        assert_eq!(psm.periodic_tau_bits().as_str(), "00001010");
+        assert_eq!(psm.active_time_bits().as_str(), "00100010");
+    }
+
+    #[test]
+    fn edrx_encoding() {
+        let edrx = EdrxConfig {
+            act_type: EdrxAccessTechnology::LteM,
+            cycle: Duration::from_secs(80),
+            paging_time_window: Duration::from_secs(5),
+        };
+        assert_eq!(edrx.cycle_bits().as_str(), "0101");
+        assert_eq!(edrx.paging_time_window_bits().as_str(), "0011");
+
+        // 10.24s is not a valid NB-IoT cycle length
+        let edrx = EdrxConfig {
+            act_type: EdrxAccessTechnology::NbIot,
+            cycle: Duration::from_millis(10_240),
+            paging_time_window: Duration::from_secs(5),
+        };
+        assert_eq!(edrx.cycle_bits().as_str(), "0010");
+        assert_eq!(edrx.paging_time_window_bits().as_str(), "0001");
+    }
+
+    #[test]
+    fn edrx_status_decoding() {
+        let status = EdrxStatus::from(EdrxDynamicParameters {
+            act_type: EdrxAccessTechnology::LteM,
+            requested_edrx_value: Some(String::from("0101")),
+            nw_provided_edrx_value: Some(String::from("0010")),
+            paging_time_window: Some(String::from("0011")),
+        });
+
+        assert_eq!(status.requested_cycle, Some(Duration::from_millis(81_920)));
+        assert_eq!(status.network_cycle, Some(Duration::from_millis(20_480)));
+        assert_eq!(
+            status.paging_time_window,
+            Some(Duration::from_millis(5_120))
+        );
+    }
 }
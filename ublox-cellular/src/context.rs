@@ -0,0 +1,94 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    client::Device,
+    command::psn::{
+        types::{AuthenticationType, PDPContextStatus},
+        SetAuthParameters, SetPDPContextDefinition, SetPDPContextState,
+    },
+    config::CellularConfig,
+    error::Error,
+    network::{ContextId, Error as NetworkError},
+    registration::ConnectionState,
+    services::data::apn::Apn,
+};
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Define and activate the additional PDP context `cid` of
+    /// [`CellularConfig::CONTEXTS`] (+CGDCONT, +CGACT)
+    ///
+    /// Requires the module to be registered, failing with
+    /// [`Error::NotConnected`] otherwise. If the network deactivates the
+    /// context, it is reactivated by [`spin`](Device::spin).
+    ///
+    /// Sockets are not bound to the additional contexts, and keep using the
+    /// primary context 1.
+    pub fn activate_context(&mut self, cid: ContextId) -> Result<(), Error> {
+        let context = Config::CONTEXTS
+            .iter()
+            .find(|context| context.cid == cid)
+            .ok_or(Error::Network(NetworkError::UnknownProfile))?;
+
+        if self.network.status.conn_state != ConnectionState::Connected {
+            return Err(Error::NotConnected);
+        }
+        if self.network.active_contexts.contains(&cid) {
+            return Ok(());
+        }
+
+        // Unlike the primary context, additional contexts are not predefined
+        // by the module, so they are defined even with an automatic APN
+        let apn = &context.apn;
+        self.send_at(&SetPDPContextDefinition {
+            cid,
+            pdp_type: apn.pdp_type.as_str(),
+            apn: match apn.apn {
+                Apn::Given(name) => name,
+                Apn::Automatic => "",
+            },
+        })?;
+
+        if apn.user_name.is_some() || apn.password.is_some() {
+            self.send_at(&SetAuthParameters {
+                cid,
+                auth_type: AuthenticationType::Auto,
+                username: apn.user_name.unwrap_or_default(),
+                password: apn.password.unwrap_or_default(),
+            })?;
+        }
+
+        self.send_at(&SetPDPContextState {
+            status: PDPContextStatus::Activated,
+            cid: Some(cid),
+        })?;
+        self.network.context_activated(cid);
+        Ok(())
+    }
+
+    /// Deactivate the additional PDP context `cid` (+CGACT)
+    ///
+    /// The context is no longer reactivated by [`spin`](Device::spin).
+    pub fn deactivate_context(&mut self, cid: ContextId) -> Result<(), Error> {
+        if !Config::CONTEXTS.iter().any(|context| context.cid == cid) {
+            return Err(Error::Network(NetworkError::UnknownProfile));
+        }
+
+        self.send_at(&SetPDPContextState {
+            status: PDPContextStatus::Deactivated,
+            cid: Some(cid),
+        })?;
+        self.network.context_released(cid);
+        Ok(())
+    }
+
+    /// Whether the PDP context `cid` is known to be active
+    pub fn context_active(&self, cid: ContextId) -> bool {
+        self.network.active_contexts.contains(&cid)
+    }
+}
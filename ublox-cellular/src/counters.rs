@@ -0,0 +1,67 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    client::Device,
+    command::psn::{responses::ContextDataCounters, GetDataCounters, SetDataCounters},
+    config::CellularConfig,
+    error::Error,
+    network::ContextId,
+};
+
+/// Bytes sent and received on a PDP context, as counted by the module, see
+/// [`data_counters`](Device::data_counters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DataCounters {
+    /// Bytes sent during the current, or last, activation of the context
+    pub session_tx: u64,
+    /// Bytes received during the current, or last, activation of the context
+    pub session_rx: u64,
+    /// Bytes sent since the counters were last reset
+    pub total_tx: u64,
+    /// Bytes received since the counters were last reset
+    pub total_rx: u64,
+}
+
+impl From<ContextDataCounters> for DataCounters {
+    fn from(v: ContextDataCounters) -> Self {
+        Self {
+            session_tx: v.sent_session_bytes,
+            session_rx: v.received_session_bytes,
+            total_tx: v.sent_total_bytes,
+            total_rx: v.received_total_bytes,
+        }
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Read the byte counters of the PDP context `cid` (+UGCNTRD)
+    ///
+    /// The counters are kept by the module, independent of any sockets. A
+    /// context that has not been activated yet has all counters at 0.
+    pub fn data_counters(&mut self, cid: ContextId) -> Result<DataCounters, Error> {
+        let counters = self.send_at(&GetDataCounters)?;
+
+        Ok(counters
+            .into_iter()
+            .find(|counters| counters.cid == cid)
+            .map(DataCounters::from)
+            .unwrap_or_default())
+    }
+
+    /// Reset the total byte counters of the PDP context `cid` (+UGCNTSET)
+    pub fn reset_data_counters(&mut self, cid: ContextId) -> Result<(), Error> {
+        self.send_at(&SetDataCounters {
+            cid,
+            total_bytes_sent: 0,
+            total_bytes_received: 0,
+        })?;
+        Ok(())
+    }
+}
@@ -14,14 +14,87 @@ pub enum GenericError {
 pub enum Error {
     // General device errors
     BaudDetection,
+    /// AT communication stopped after enabling RTS/CTS flow control, which
+    /// usually means the RTS/CTS lines are not wired
+    FlowControlMismatch,
     Busy,
+    PinUnavailable,
+    /// The command is not supported by the configured
+    /// [`Module`](crate::Module)
+    UnsupportedByModule,
     Uninitialized,
     StateTimeout,
+    /// A raw AT command exceeds
+    /// [`MAX_RAW_COMMAND`](crate::command::MAX_RAW_COMMAND) bytes
+    CommandTooLong,
+    /// The response to a raw AT command does not fit the given buffer
+    ResponseTooLong,
+
+    // SIM errors
+    PukRequired,
+    SimNotReady,
+    InvalidIccid,
+
+    // SMS errors
+    MessageTooLong,
 
     // Network errors
+    /// The data connection is not up
+    NotConnected,
+    /// The data connection is already up, so its configuration can not be
+    /// changed
+    ContextActive,
+    DnsResolutionFailed,
+    /// No bands selected, which would prevent registration
+    EmptyBandMask,
+    /// Registration was denied by the network, with the reject cause of
+    /// 3GPP TS 24.008 / 24.301, e.g. 2 for "IMSI unknown in HLR" or 22 for
+    /// "congestion". The cause is 0 if the module did not report one.
+    RegistrationDenied {
+        cause: u8,
+    },
+    /// Registration did not succeed within
+    /// [`CellularConfig::MAX_ATTACH_RETRIES`](crate::CellularConfig::MAX_ATTACH_RETRIES)
+    /// attempts
+    RegistrationTimeout,
+    /// Only a roaming network is available, while
+    /// [`CellularConfig::ROAMING_ALLOWED`](crate::CellularConfig::ROAMING_ALLOWED)
+    /// is `false`
+    RoamingNotAllowed,
+    /// `+CME ERROR: <err>` reported by the module, e.g. 14 for "SIM busy"
+    Cme(u16),
+    /// `+CMS ERROR: <err>` reported by the module for SMS commands
+    Cms(u16),
     Network(NetworkError),
 
+    // Socket errors
+    /// The local port requested for a socket is bound by another socket
+    PortInUse,
+
+    // Security errors
+    /// The MD5 of imported security data reported by the module does not
+    /// match the data
+    ChecksumMismatch,
+    /// The SSL/TLS handshake failed, e.g. as the server hostname does not
+    /// match its certificate, with the socket error code of +USOER
+    TlsHandshakeFailed(u8),
+
+    // GNSS errors
+    /// The GNSS receiver has no fix yet
+    GnssNoFix,
+
+    // FOTA errors
+    /// The FOTA download failed with the given module error code
+    FotaDownloadFailed(u16),
+
     // Service specific errors
+    /// No HTTP response was received, e.g. as the server could not be reached
+    HttpRequestFailed,
+    /// The MQTT broker refused the connection or a request, or the module
+    /// did not accept the MQTT command
+    MqttRequestFailed,
+    /// An FTP command failed, with the error class and code of +UFTPER
+    FtpFailed(u8, u16),
     DataService(DataServiceError),
 
     // Generic shared errors, e.g. from `core::`
@@ -30,15 +103,50 @@ pub enum Error {
     _Unknown,
 }
 
+impl Error {
+    /// Whether retrying can not resolve the error, e.g. a PUK locked SIM
+    pub(crate) fn is_fatal(&self) -> bool {
+        matches!(self, Self::PukRequired | Self::FlowControlMismatch)
+    }
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for Error {
     fn format(&self, f: defmt::Formatter<'_>) {
         match self {
             Self::BaudDetection => defmt::write!(f, "BaudDetection"),
+            Self::FlowControlMismatch => defmt::write!(f, "FlowControlMismatch"),
             Self::Busy => defmt::write!(f, "Busy"),
+            Self::PinUnavailable => defmt::write!(f, "PinUnavailable"),
+            Self::UnsupportedByModule => defmt::write!(f, "UnsupportedByModule"),
             Self::Uninitialized => defmt::write!(f, "Uninitialized"),
             Self::StateTimeout => defmt::write!(f, "StateTimeout"),
+            Self::CommandTooLong => defmt::write!(f, "CommandTooLong"),
+            Self::ResponseTooLong => defmt::write!(f, "ResponseTooLong"),
+            Self::PukRequired => defmt::write!(f, "PukRequired"),
+            Self::SimNotReady => defmt::write!(f, "SimNotReady"),
+            Self::InvalidIccid => defmt::write!(f, "InvalidIccid"),
+            Self::MessageTooLong => defmt::write!(f, "MessageTooLong"),
+            Self::NotConnected => defmt::write!(f, "NotConnected"),
+            Self::ContextActive => defmt::write!(f, "ContextActive"),
+            Self::DnsResolutionFailed => defmt::write!(f, "DnsResolutionFailed"),
+            Self::EmptyBandMask => defmt::write!(f, "EmptyBandMask"),
+            Self::RegistrationDenied { cause } => {
+                defmt::write!(f, "RegistrationDenied {{ cause: {} }}", cause)
+            }
+            Self::RegistrationTimeout => defmt::write!(f, "RegistrationTimeout"),
+            Self::RoamingNotAllowed => defmt::write!(f, "RoamingNotAllowed"),
+            Self::Cme(code) => defmt::write!(f, "Cme({})", code),
+            Self::Cms(code) => defmt::write!(f, "Cms({})", code),
             Self::Network(e) => defmt::write!(f, "Network({:?})", e),
+            Self::PortInUse => defmt::write!(f, "PortInUse"),
+            Self::ChecksumMismatch => defmt::write!(f, "ChecksumMismatch"),
+            Self::TlsHandshakeFailed(code) => defmt::write!(f, "TlsHandshakeFailed({})", code),
+            Self::GnssNoFix => defmt::write!(f, "GnssNoFix"),
+            Self::FotaDownloadFailed(code) => defmt::write!(f, "FotaDownloadFailed({})", code),
+            Self::HttpRequestFailed => defmt::write!(f, "HttpRequestFailed"),
+            Self::MqttRequestFailed => defmt::write!(f, "MqttRequestFailed"),
+            Self::FtpFailed(class, code) => defmt::write!(f, "FtpFailed({}, {})", class, code),
             Self::DataService(e) => defmt::write!(f, "DataService({:?})", e),
             Self::Generic(e) => defmt::write!(f, "Generic({:?})", e),
             Self::_Unknown => defmt::write!(f, "_Unknown"),
@@ -52,7 +160,7 @@ impl From<DataServiceError> for Error {
         // Unwrap generic and network errors
         match e {
             DataServiceError::Generic(g) => Self::Generic(g),
-            DataServiceError::Network(g) => Self::Network(g),
+            DataServiceError::Network(g) => g.into(),
             _ => Self::DataService(e),
         }
     }
@@ -63,6 +171,11 @@ impl From<NetworkError> for Error {
         // Unwrap generic errors
         match e {
             NetworkError::Generic(g) => Self::Generic(g),
+            NetworkError::RegistrationDenied { cause } => Self::RegistrationDenied { cause },
+            NetworkError::RegistrationTimeout => Self::RegistrationTimeout,
+            NetworkError::RoamingNotAllowed => Self::RoamingNotAllowed,
+            NetworkError::Cme(code) => Self::Cme(code),
+            NetworkError::Cms(code) => Self::Cms(code),
             _ => Self::Network(e),
         }
     }
@@ -0,0 +1,113 @@
+use atat::blocking::AtatClient;
+use heapless::{String, Vec};
+
+use crate::{
+    client::Device,
+    command::file_system::{
+        responses::{FileSize, ReadBlockResponse},
+        DeleteFile, DownloadFile, GetFileSize, ListFiles, PrepareDownloadFile, ReadBlock,
+    },
+    config::CellularConfig,
+    error::Error,
+};
+
+/// Maximum number of files returned by [`list_files`](Device::list_files)
+pub const MAX_FILES: usize = 10;
+
+/// Size of the parts a file is read in with +URDBLOCK
+const READ_BLOCK_SIZE: usize = 512;
+
+/// Size of the parts a file is written in with +UDWNFILE
+const WRITE_CHUNK_SIZE: usize = 2048;
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Write `data` to the file `name` of the module file system, replacing
+    /// it if it exists (+UDWNFILE)
+    pub fn write_file(&mut self, name: &str, data: &[u8]) -> Result<(), Error> {
+        // +UDWNFILE appends to existing files
+        self.send_at(&DeleteFile { filename: name }).ok();
+        self.append_file(name, data)
+    }
+
+    /// Append `data` to the file `name`, which is created if it does not exist
+    /// (+UDWNFILE)
+    ///
+    /// The data is written after the '>' prompt of the module, in parts of at
+    /// most 2048 bytes.
+    pub fn append_file(&mut self, name: &str, data: &[u8]) -> Result<(), Error> {
+        for chunk in data.chunks(WRITE_CHUNK_SIZE) {
+            self.send_at(&PrepareDownloadFile {
+                filename: name,
+                size: chunk.len(),
+            })?;
+            self.send_at(&DownloadFile {
+                text: atat::serde_bytes::Bytes::new(chunk),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Read the file `name` into `buf` (+URDBLOCK)
+    ///
+    /// Returns the number of bytes read, which is less than the size of the
+    /// file if `buf` is too small to hold it.
+    pub fn read_file(&mut self, name: &str, buf: &mut [u8]) -> Result<usize, Error> {
+        let mut len = 0;
+        self.read_file_with(name, |data| {
+            let n = data.len().min(buf.len() - len);
+            buf[len..len + n].copy_from_slice(&data[..n]);
+            len += n;
+            len < buf.len()
+        })?;
+        Ok(len)
+    }
+
+    /// Delete the file `name` (+UDELFILE)
+    pub fn delete_file(&mut self, name: &str) -> Result<(), Error> {
+        self.send_at(&DeleteFile { filename: name })?;
+        Ok(())
+    }
+
+    /// List the files of the module file system (+ULSTFILE)
+    pub fn list_files(&mut self) -> Result<Vec<String<248>, MAX_FILES>, Error> {
+        Ok(self.send_at(&ListFiles)?)
+    }
+
+    /// Read the file `name` in parts of up to 512 bytes, passing each part to
+    /// `f` until it returns `false` or the end of the file is reached
+    /// (+ULSTFILE, +URDBLOCK)
+    pub(crate) fn read_file_with<F>(&mut self, name: &str, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8]) -> bool,
+    {
+        let FileSize { size: file_size } = self.send_at(&GetFileSize { filename: name })?;
+        let mut offset = 0;
+
+        while offset < file_size {
+            let ReadBlockResponse { size, data, .. } = self.send_at(&ReadBlock {
+                filename: name,
+                offset,
+                size: READ_BLOCK_SIZE.min(file_size - offset),
+            })?;
+
+            // The data is quoted
+            let data = &data[..];
+            let data = data
+                .strip_prefix(b"\"")
+                .and_then(|d| d.strip_suffix(b"\""))
+                .unwrap_or(data);
+
+            offset += size;
+            if size == 0 || !f(data) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    client::{Device, State},
+    command::system_features::{
+        responses::FirmwareInstallResult, types::FotaState, urc::FotaStatus,
+        GetFirmwareInstallResult, InstallFirmware, StartFotaDownload,
+    },
+    config::CellularConfig,
+    error::Error,
+    module::Capability,
+};
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Download the firmware update image at `url` into the module (+UFOTA)
+    ///
+    /// The first call starts the download, and following calls return
+    /// `WouldBlock` until it is completed, e.g. with `nb::block!`. The
+    /// progress is reported with [`Event::FotaProgress`](crate::Event::FotaProgress).
+    /// A failed download is returned as [`Error::FotaDownloadFailed`] with
+    /// the error code of the module.
+    ///
+    /// The downloaded image is installed with
+    /// [`fota_install`](Device::fota_install).
+    pub fn fota_download(&mut self, url: &str) -> nb::Result<(), Error> {
+        self.require(Capability::Fota)?;
+        if self.network.fota_status.is_none() {
+            self.send_at(&StartFotaDownload { url })?;
+            self.network.fota_status = Some(FotaStatus {
+                state: FotaState::Downloading,
+                value: 0,
+            });
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.network.handle_urc().ok();
+
+        match self.network.fota_status.as_ref().map(|status| status.state) {
+            Some(FotaState::Downloaded) => {
+                self.network.fota_status = None;
+                Ok(())
+            }
+            Some(FotaState::DownloadFailed) => {
+                let code = self.network.fota_status.take().map_or(0, |s| s.value);
+                error!("FOTA download failed: {}", code);
+                Err(nb::Error::Other(Error::FotaDownloadFailed(code)))
+            }
+            _ => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    /// Install the downloaded firmware update image (+UFWINSTALL)
+    ///
+    /// The module reboots to install the image, which can take several
+    /// minutes, and has to be initialized again afterwards. The result of
+    /// the installation is read with [`fota_result`](Device::fota_result).
+    pub fn fota_install(&mut self) -> Result<(), Error> {
+        self.require(Capability::Fota)?;
+        self.send_at(&InstallFirmware)?;
+
+        self.clear_buffers()?;
+        self.set_state(State::Off);
+        Ok(())
+    }
+
+    /// Result code of the last firmware installation, 0 on success
+    /// (+UFWINSTALL?)
+    pub fn fota_result(&mut self) -> Result<u16, Error> {
+        self.require(Capability::Fota)?;
+        let FirmwareInstallResult { result } = self.send_at(&GetFirmwareInstallResult)?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,151 @@
+use atat::blocking::AtatClient;
+use embassy_time::{Duration, Instant};
+
+use crate::{
+    blocking_timer::BlockingTimer,
+    client::Device,
+    command::ftp::{
+        responses::FtpError,
+        types::{FtpOperation, FtpProfileOperation},
+        GetFtpError, SendFtpCommand, SetFtpProfile,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+    module::Capability,
+    security::bounded,
+};
+
+/// Time to wait for the +UUFTPCR URC of a login or logout
+const FTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// FTP file transfer in progress, polled with
+/// [`ftp_poll`](Device::ftp_poll)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[must_use]
+pub struct FtpTransfer {
+    op: FtpOperation,
+}
+
+impl FtpTransfer {
+    pub fn op(&self) -> FtpOperation {
+        self.op
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Log in to the FTP server `host` on port 21, in passive mode
+    /// (+UFTP, +UFTPC)
+    pub fn ftp_login(&mut self, host: &str, username: &str, password: &str) -> Result<(), Error> {
+        self.require(Capability::Ftp)?;
+        self.send_at(&SetFtpProfile {
+            operation: FtpProfileOperation::ServerName(bounded(host)?),
+        })?;
+        self.send_at(&SetFtpProfile {
+            operation: FtpProfileOperation::Username(bounded(username)?),
+        })?;
+        self.send_at(&SetFtpProfile {
+            operation: FtpProfileOperation::Password(bounded(password)?),
+        })?;
+        self.send_at(&SetFtpProfile {
+            operation: FtpProfileOperation::Passive(true),
+        })?;
+
+        let transfer = self.ftp_command(FtpOperation::Login, None, None)?;
+        self.ftp_wait(transfer)
+    }
+
+    /// Log out of the FTP server
+    pub fn ftp_logout(&mut self) -> Result<(), Error> {
+        let transfer = self.ftp_command(FtpOperation::Logout, None, None)?;
+        self.ftp_wait(transfer)
+    }
+
+    /// Start retrieving the file `remote` of the FTP server into the file
+    /// `local` of the module file system
+    ///
+    /// The transfer completes in the background, see
+    /// [`ftp_poll`](Device::ftp_poll).
+    pub fn ftp_get(&mut self, remote: &str, local: &str) -> Result<FtpTransfer, Error> {
+        self.ftp_command(FtpOperation::RetrieveFile, Some(remote), Some(local))
+    }
+
+    /// Start storing the file `local` of the module file system on the FTP
+    /// server as `remote`
+    ///
+    /// The transfer completes in the background, see
+    /// [`ftp_poll`](Device::ftp_poll).
+    pub fn ftp_put(&mut self, local: &str, remote: &str) -> Result<FtpTransfer, Error> {
+        self.ftp_command(FtpOperation::StoreFile, Some(local), Some(remote))
+    }
+
+    /// Poll the FTP `transfer`, returning `WouldBlock` until it has completed
+    ///
+    /// The completion is also reported with
+    /// [`Event::FtpTransferComplete`](crate::Event::FtpTransferComplete). A
+    /// failed transfer is returned as [`Error::FtpFailed`], with the error
+    /// class and code of +UFTPER, e.g. the reply code of the FTP server.
+    pub fn ftp_poll(&mut self, transfer: &FtpTransfer) -> nb::Result<(), Error> {
+        self.network.handle_urc().ok();
+
+        match self.network.ftp_result.take() {
+            Some(result) if result.op_code == transfer.op => {
+                if result.ftp_result == 1 {
+                    return Ok(());
+                }
+
+                let FtpError {
+                    error_class,
+                    error_code,
+                } = self.send_at(&GetFtpError)?;
+                error!(
+                    "FTP command {:?} failed: {} {}",
+                    transfer.op, error_class, error_code
+                );
+                Err(nb::Error::Other(Error::FtpFailed(error_class, error_code)))
+            }
+            Some(result) => {
+                warn!("Unexpected FTP result of {:?}", result.op_code);
+                Err(nb::Error::WouldBlock)
+            }
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    fn ftp_command(
+        &mut self,
+        op: FtpOperation,
+        param1: Option<&str>,
+        param2: Option<&str>,
+    ) -> Result<FtpTransfer, Error> {
+        self.require(Capability::Ftp)?;
+        self.network.ftp_result = None;
+
+        self.send_at(&SendFtpCommand {
+            op_code: op,
+            param1,
+            param2,
+        })?;
+        Ok(FtpTransfer { op })
+    }
+
+    fn ftp_wait(&mut self, transfer: FtpTransfer) -> Result<(), Error> {
+        let start = Instant::now();
+        while start.elapsed() < FTP_TIMEOUT {
+            match self.ftp_poll(&transfer) {
+                Err(nb::Error::WouldBlock) => {}
+                Err(nb::Error::Other(e)) => return Err(e),
+                Ok(()) => return Ok(()),
+            }
+            BlockingTimer::after(Duration::from_millis(100)).wait();
+        }
+
+        Err(Error::Generic(GenericError::Timeout))
+    }
+}
@@ -0,0 +1,317 @@
+use atat::blocking::AtatClient;
+use embassy_time::{Duration, Instant};
+
+use crate::{
+    client::Device,
+    command::gnss::{
+        responses::GgaSentence,
+        types::{GnssMode, LocationResponseType, LocationSensor},
+        urc::Location,
+        GetGgaSentence, RequestLocation, SetGgaStorage, SetGnssPower,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+    module::Capability,
+    services::data::ContextState,
+};
+
+/// +UGPS aiding mode bit of automatic local aiding
+const AID_LOCAL: u8 = 1;
+
+/// Longest timeout of a +ULOC request, in seconds
+const MAX_LOCATION_TIMEOUT_S: u16 = 999;
+
+/// Quality of a GNSS fix, as reported in the $GGA NMEA message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FixQuality {
+    Gps,
+    DifferentialGps,
+    Rtk,
+    FloatRtk,
+    /// Dead reckoning
+    Estimated,
+    /// Network based CellLocate position, see
+    /// [`cell_locate`](crate::GsmClient::cell_locate)
+    CellLocate,
+    Unknown,
+}
+
+impl FixQuality {
+    /// `None` for 0, which means there is no fix
+    fn from_gga(quality: u8) -> Option<Self> {
+        match quality {
+            0 => None,
+            1 => Some(Self::Gps),
+            2 => Some(Self::DifferentialGps),
+            4 => Some(Self::Rtk),
+            5 => Some(Self::FloatRtk),
+            6 => Some(Self::Estimated),
+            _ => Some(Self::Unknown),
+        }
+    }
+}
+
+/// Position of a GNSS fix
+///
+/// Coordinates are in units of 1e-7 degrees, positive to the north and the
+/// east.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Position {
+    pub latitude: i32,
+    pub longitude: i32,
+    /// Altitude above mean sea level in meters
+    pub altitude: Option<i32>,
+    pub fix_quality: FixQuality,
+    /// Maximum possible error in meters, if reported
+    pub uncertainty: Option<u32>,
+}
+
+impl Position {
+    pub fn latitude_deg(&self) -> f64 {
+        f64::from(self.latitude) / 1e7
+    }
+
+    pub fn longitude_deg(&self) -> f64 {
+        f64::from(self.longitude) / 1e7
+    }
+
+    /// Parse a $GGA NMEA message, `None` if it has no fix
+    pub(crate) fn from_gga(sentence: &str) -> Option<Self> {
+        let sentence = sentence.split('*').next()?;
+        let mut fields = sentence.split(',');
+
+        if !fields.next()?.ends_with("GGA") {
+            return None;
+        }
+        let _time = fields.next()?;
+        let latitude = parse_nmea_coordinate(fields.next()?, fields.next()?)?;
+        let longitude = parse_nmea_coordinate(fields.next()?, fields.next()?)?;
+        let fix_quality = FixQuality::from_gga(fields.next()?.parse().ok()?)?;
+        let _satellites = fields.next()?;
+        let _hdop = fields.next()?;
+        let altitude = parse_fixed(fields.next()?, 0).and_then(|alt| i32::try_from(alt).ok());
+
+        Some(Self {
+            latitude,
+            longitude,
+            altitude,
+            fix_quality,
+            uncertainty: None,
+        })
+    }
+
+    /// Convert a +UULOC URC
+    pub(crate) fn from_location(location: &Location, fix_quality: FixQuality) -> Option<Self> {
+        let degrees = |value: &[u8]| {
+            let value = parse_fixed(core::str::from_utf8(value).ok()?, 7)?;
+            i32::try_from(value).ok()
+        };
+
+        Some(Self {
+            latitude: degrees(&location.latitude)?,
+            longitude: degrees(&location.longitude)?,
+            altitude: Some(location.altitude),
+            fix_quality,
+            uncertainty: Some(location.uncertainty),
+        })
+    }
+}
+
+/// Parse the decimal number `value`, scaled by 10^`digits` and truncated
+fn parse_fixed(value: &str, digits: u32) -> Option<i64> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+    let (int, frac) = value.split_once('.').unwrap_or((value, ""));
+    if int.is_empty() && frac.is_empty() {
+        return None;
+    }
+
+    let mut result: i64 = if int.is_empty() { 0 } else { int.parse().ok()? };
+    let mut frac = frac.bytes();
+    for _ in 0..digits {
+        let digit = match frac.next() {
+            Some(b @ b'0'..=b'9') => i64::from(b - b'0'),
+            Some(_) => return None,
+            None => 0,
+        };
+        result = result.checked_mul(10)?.checked_add(digit)?;
+    }
+
+    Some(if negative { -result } else { result })
+}
+
+/// Parse an NMEA coordinate `(d)ddmm.mmmm` with its hemisphere into 1e-7
+/// degrees
+fn parse_nmea_coordinate(value: &str, hemisphere: &str) -> Option<i32> {
+    let value = parse_fixed(value, 7)?;
+    let degrees = value / 1_000_000_000;
+    let minutes = value % 1_000_000_000;
+    let result = i32::try_from(degrees * 10_000_000 + minutes / 60).ok()?;
+
+    match hemisphere {
+        "N" | "E" => Some(result),
+        "S" | "W" => Some(-result),
+        _ => None,
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Switch the GNSS receiver on or off (+UGPS)
+    ///
+    /// When switched on, the storing of $GGA messages for
+    /// [`gnss_position`](Device::gnss_position) is enabled, and the receiver
+    /// is aided as configured with [`CellularConfig::GNSS_LOCAL_AIDING`].
+    pub fn gnss_power(&mut self, on: bool) -> Result<(), Error> {
+        self.require(Capability::Location)?;
+        if !on {
+            self.send_at(&SetGnssPower {
+                mode: GnssMode::Off,
+                aid_mode: None,
+                systems: None,
+            })?;
+            return Ok(());
+        }
+
+        self.send_at(&SetGnssPower {
+            mode: GnssMode::On,
+            aid_mode: Config::GNSS_LOCAL_AIDING.then_some(AID_LOCAL),
+            systems: None,
+        })?;
+        self.send_at(&SetGgaStorage { enabled: true })?;
+        Ok(())
+    }
+
+    /// Position of the last fix of the GNSS receiver (+UGGGA)
+    ///
+    /// Fails with [`Error::GnssNoFix`] while the receiver has no fix.
+    pub fn gnss_position(&mut self) -> Result<Position, Error> {
+        self.require(Capability::Location)?;
+        let GgaSentence { sentence, .. } = self.send_at(&GetGgaSentence)?;
+
+        sentence
+            .as_deref()
+            .and_then(Position::from_gga)
+            .ok_or(Error::GnssNoFix)
+    }
+
+    /// Request a single GNSS fix, within `timeout` seconds (1 to 999) and
+    /// with the desired `accuracy` in meters (+ULOC)
+    ///
+    /// The fix is reported with [`Event::GnssFix`](crate::Event::GnssFix).
+    pub fn gnss_request_fix(&mut self, timeout: u16, accuracy: u32) -> Result<(), Error> {
+        self.require(Capability::Location)?;
+        let timeout = timeout.clamp(1, MAX_LOCATION_TIMEOUT_S);
+        self.send_at(&RequestLocation {
+            sensor: LocationSensor::Gnss,
+            response_type: LocationResponseType::Standard,
+            timeout,
+            accuracy,
+        })?;
+        self.network.location_request = Some((
+            LocationSensor::Gnss,
+            Instant::now() + Duration::from_secs(timeout.into()),
+        ));
+        Ok(())
+    }
+
+    /// Coarse position of the module from the cellular network with
+    /// CellLocate, with the desired `accuracy_m` in meters (+ULOC)
+    ///
+    /// The first call starts the request, and following calls return
+    /// `WouldBlock` until the position is received, or `timeout` has expired.
+    /// The module accepts timeouts of 1 to 999 seconds, longer ones are
+    /// clamped. The uncertainty radius is returned in [`Position::uncertainty`].
+    /// CellLocate needs the data connection, so this fails with
+    /// [`Error::NotConnected`] while it is not up.
+    pub fn cell_locate(
+        &mut self,
+        accuracy_m: u16,
+        timeout: Duration,
+    ) -> nb::Result<Position, Error> {
+        self.require(Capability::Location)?;
+        if self.network.context_state != ContextState::Active {
+            return Err(nb::Error::Other(Error::NotConnected));
+        }
+
+        match self.network.location_request {
+            Some((LocationSensor::CellLocate, deadline)) => {
+                self.network.handle_urc().ok();
+
+                if let Some(position) = self.network.location.take() {
+                    self.network.location_request = None;
+                    Ok(position)
+                } else if Instant::now() >= deadline {
+                    self.network.location_request = None;
+                    Err(nb::Error::Other(Error::Generic(GenericError::Timeout)))
+                } else {
+                    Err(nb::Error::WouldBlock)
+                }
+            }
+            _ => {
+                let secs = timeout
+                    .as_secs()
+                    .clamp(1, u64::from(MAX_LOCATION_TIMEOUT_S)) as u16;
+                self.send_at(&RequestLocation {
+                    sensor: LocationSensor::CellLocate,
+                    response_type: LocationResponseType::Standard,
+                    timeout: secs,
+                    accuracy: accuracy_m.into(),
+                })?;
+
+                // Leave some time for the URC after the timeout of the module
+                self.network.location = None;
+                self.network.location_request = Some((
+                    LocationSensor::CellLocate,
+                    Instant::now() + Duration::from_secs(u64::from(secs) + 5),
+                ));
+                Err(nb::Error::WouldBlock)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gga_position() {
+        let position = Position::from_gga(
+            "$GPGGA,092725.00,4717.11399,N,00833.91590,E,1,08,1.01,499.6,M,48.0,M,,*5B",
+        )
+        .unwrap();
+
+        assert_eq!(position.latitude, 472_852_331);
+        assert_eq!(position.longitude, 85_652_650);
+        assert_eq!(position.altitude, Some(499));
+        assert_eq!(position.fix_quality, FixQuality::Gps);
+
+        let south_west =
+            Position::from_gga("$GNGGA,,3351.0000,S,15112.0000,W,2,05,,-3.2,M,,M,,").unwrap();
+        assert_eq!(south_west.latitude, -338_500_000);
+        assert_eq!(south_west.longitude, -1_512_000_000);
+        assert_eq!(south_west.altitude, Some(-3));
+        assert_eq!(south_west.fix_quality, FixQuality::DifferentialGps);
+
+        assert_eq!(Position::from_gga("$GPGGA,,,,,,0,00,99.99,,,,,,*48"), None);
+    }
+
+    #[test]
+    fn fixed_point() {
+        assert_eq!(parse_fixed("45.7879", 7), Some(457_879_000));
+        assert_eq!(parse_fixed("-13.26", 2), Some(-1326));
+        assert_eq!(parse_fixed("7", 1), Some(70));
+        assert_eq!(parse_fixed("", 1), None);
+        assert_eq!(parse_fixed("1.2x", 3), None);
+    }
+}
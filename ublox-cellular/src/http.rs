@@ -0,0 +1,256 @@
+use atat::blocking::AtatClient;
+use embassy_time::{Duration, Instant};
+use heapless::Vec;
+
+use crate::{
+    blocking_timer::BlockingTimer,
+    client::Device,
+    command::http::{
+        types::{HttpCommand, HttpContentType, HttpProfileId, HttpProfileOperation},
+        SendHttpCommand, SetHttpProfile,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+    module::Capability,
+    security::bounded,
+    services::data::ssl::SecurityProfileId,
+};
+
+/// Maximum size of the body of an [`HttpResponse`]
+pub const MAX_HTTP_BODY: usize = 1024;
+
+/// Time to wait for the +UUHTTPCR URC, up to the HTTP timeout of the module
+const HTTP_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// File the module stores the server response in, including the headers
+const HTTP_RESPONSE_FILE: &str = "http_resp";
+
+/// File the body of POST requests is written to
+const HTTP_REQUEST_FILE: &str = "http_req";
+
+/// Response of the HTTP server, see [`http_get`](Device::http_get)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// HTTP status code, e.g. 200
+    pub status: u16,
+    pub body: Vec<u8, MAX_HTTP_BODY>,
+    /// Whether the body was longer than [`MAX_HTTP_BODY`], and has been cut
+    pub truncated: bool,
+}
+
+impl HttpResponse {
+    /// Whether the status code is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Parses the response file written by the module, which can be read in
+/// parts of any size
+#[derive(Default)]
+struct ResponseParser {
+    status: Option<u16>,
+    /// Number of bytes of the header terminator "\r\n\r\n" matched so far
+    terminator: usize,
+    truncated: bool,
+}
+
+impl ResponseParser {
+    fn feed(&mut self, mut data: &[u8], body: &mut Vec<u8, MAX_HTTP_BODY>) {
+        if self.status.is_none() {
+            self.status = Some(parse_status_line(data).unwrap_or(0));
+        }
+
+        while self.terminator < 4 {
+            let Some((&byte, rest)) = data.split_first() else {
+                return;
+            };
+            data = rest;
+            self.terminator = match byte {
+                b'\r' if self.terminator % 2 == 0 => self.terminator + 1,
+                b'\n' if self.terminator % 2 == 1 => self.terminator + 1,
+                b'\r' => 1,
+                _ => 0,
+            };
+        }
+
+        let len = data.len().min(body.capacity() - body.len());
+        body.extend_from_slice(&data[..len]).ok();
+        self.truncated |= len < data.len();
+    }
+}
+
+/// Status code of an HTTP status line, e.g. "HTTP/1.1 404 Not Found"
+fn parse_status_line(data: &[u8]) -> Option<u16> {
+    let rest = data.strip_prefix(b"HTTP/")?;
+    let start = rest.iter().position(|&b| b == b' ')? + 1;
+    let code = rest.get(start..start + 3)?;
+
+    core::str::from_utf8(code).ok()?.parse().ok()
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Configure the HTTP `profile` to connect to `host` on `port` (+UHTTP)
+    ///
+    /// With `security`, the connection is encrypted with the given security
+    /// profile, see
+    /// [`configure_security_profile`](Device::configure_security_profile).
+    pub fn set_http_server(
+        &mut self,
+        profile: HttpProfileId,
+        host: &str,
+        port: u16,
+        security: Option<SecurityProfileId>,
+    ) -> Result<(), Error> {
+        self.require(Capability::Http)?;
+        self.send_at(&SetHttpProfile {
+            profile_id: profile,
+            operation: None,
+        })?;
+
+        self.send_at(&SetHttpProfile {
+            profile_id: profile,
+            operation: Some(HttpProfileOperation::ServerName(bounded(host)?)),
+        })?;
+
+        self.send_at(&SetHttpProfile {
+            profile_id: profile,
+            operation: Some(HttpProfileOperation::Secure(
+                security.is_some(),
+                security.unwrap_or(SecurityProfileId(0)),
+            )),
+        })?;
+
+        self.send_at(&SetHttpProfile {
+            profile_id: profile,
+            operation: Some(HttpProfileOperation::ServerPort(port)),
+        })?;
+        Ok(())
+    }
+
+    /// Send an HTTP GET request for `path` to the server of `profile`
+    /// (+UHTTPC)
+    ///
+    /// Any status code returned by the server is an `Ok` response, see
+    /// [`HttpResponse::is_success`]. [`Error::HttpRequestFailed`] is only
+    /// returned if no response was received.
+    pub fn http_get(&mut self, profile: HttpProfileId, path: &str) -> Result<HttpResponse, Error> {
+        self.http_request(profile, HttpCommand::Get, path, None)
+    }
+
+    /// Send an HTTP POST request with `body` for `path` to the server of
+    /// `profile` (+UHTTPC)
+    ///
+    /// The body is stored in the file system of the module first. Status codes
+    /// are handled as in [`http_get`](Device::http_get).
+    pub fn http_post(
+        &mut self,
+        profile: HttpProfileId,
+        path: &str,
+        content_type: HttpContentType,
+        body: &[u8],
+    ) -> Result<HttpResponse, Error> {
+        self.require(Capability::Http)?;
+        self.write_file(HTTP_REQUEST_FILE, body)?;
+
+        self.http_request(
+            profile,
+            HttpCommand::PostFile,
+            path,
+            Some((HTTP_REQUEST_FILE, content_type)),
+        )
+    }
+
+    fn http_request(
+        &mut self,
+        profile: HttpProfileId,
+        command: HttpCommand,
+        path: &str,
+        data: Option<(&str, HttpContentType)>,
+    ) -> Result<HttpResponse, Error> {
+        self.require(Capability::Http)?;
+        self.network.http_result = None;
+
+        self.send_at(&SendHttpCommand {
+            profile_id: profile,
+            command,
+            path,
+            response_filename: HTTP_RESPONSE_FILE,
+            data: data.map(|(filename, _)| filename),
+            content_type: data.map(|(_, content_type)| content_type),
+        })?;
+
+        // The result is returned with the +UUHTTPCR URC
+        let start = Instant::now();
+        let result = loop {
+            self.network.handle_urc().ok();
+            if let Some(result) = self.network.http_result.take() {
+                if result.profile_id == profile.0 && result.http_command == command as u8 {
+                    break result;
+                }
+            }
+            if start.elapsed() >= HTTP_TIMEOUT {
+                return Err(Error::Generic(GenericError::Timeout));
+            }
+            BlockingTimer::after(Duration::from_millis(100)).wait();
+        };
+
+        if result.http_result != 1 {
+            error!("HTTP request on profile {} failed", profile.0);
+            return Err(Error::HttpRequestFailed);
+        }
+
+        let response = self.read_http_response();
+        self.delete_file(HTTP_RESPONSE_FILE).ok();
+        response
+    }
+
+    fn read_http_response(&mut self) -> Result<HttpResponse, Error> {
+        let mut parser = ResponseParser::default();
+        let mut body = Vec::new();
+
+        self.read_file_with(HTTP_RESPONSE_FILE, |data| {
+            parser.feed(data, &mut body);
+            !parser.truncated
+        })?;
+
+        Ok(HttpResponse {
+            status: parser.status.unwrap_or(0),
+            body,
+            truncated: parser.truncated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn status_line() {
+        assert_eq!(parse_status_line(b"HTTP/1.1 200 OK\r\n"), Some(200));
+        assert_eq!(parse_status_line(b"HTTP/1.0 404 Not Found\r\n"), Some(404));
+        assert_eq!(parse_status_line(b"HTTP/1.1 20"), None);
+        assert_eq!(parse_status_line(b"garbage"), None);
+    }
+
+    #[test]
+    fn response_in_parts() {
+        let mut parser = ResponseParser::default();
+        let mut body = Vec::new();
+
+        parser.feed(b"HTTP/1.1 201 Created\r\nContent-Length: 2\r", &mut body);
+        parser.feed(b"\n\r", &mut body);
+        parser.feed(b"\n{}", &mut body);
+
+        assert_eq!(parser.status, Some(201));
+        assert_eq!(&body[..], b"{}");
+        assert!(!parser.truncated);
+    }
+}
@@ -0,0 +1,168 @@
+use atat::blocking::AtatClient;
+use core::fmt::Write;
+use heapless::String;
+
+use crate::{
+    client::{Device, State},
+    command::{
+        device_lock::{responses::PinStatus, types::PinStatusCode, GetPinStatus},
+        general::{GetCCID, GetCIMI, GetFirmwareVersion, GetIMEI, GetManufacturerId, GetModelId},
+    },
+    config::CellularConfig,
+    error::Error,
+};
+
+/// Identification of the module, as returned by
+/// [`identify`](Device::identify)
+///
+/// Fields that could not be queried are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleIdentity {
+    pub manufacturer: String<10>,
+    pub model: String<16>,
+    /// Firmware revision
+    pub firmware: String<10>,
+    pub imei: String<15>,
+}
+
+/// International Mobile Subscriber Identity (IMSI) of the SIM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Imsi(String<15>);
+
+impl Imsi {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+/// Integrated Circuit Card ID (ICCID), the serial number of the SIM
+///
+/// Always 19 or 20 decimal digits, with a valid Luhn check digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Iccid(String<20>);
+
+impl Iccid {
+    /// Validate an ICCID given as decimal digits
+    pub fn new(digits: &str) -> Option<Self> {
+        if !matches!(digits.len(), 19 | 20) || !luhn_valid(digits) {
+            return None;
+        }
+        Some(Self(String::from(digits)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+/// Verify the Luhn check digit, i.e. the last digit, of `digits`
+fn luhn_valid(digits: &str) -> bool {
+    let mut sum = 0;
+    for (i, b) in digits.bytes().rev().enumerate() {
+        if !b.is_ascii_digit() {
+            return false;
+        }
+        let mut d = u32::from(b - b'0');
+        if i % 2 == 1 {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+    }
+    sum % 10 == 0
+}
+
+/// Convert the text of an information text response into a string
+fn to_string<const N: usize>(bytes: &[u8]) -> String<N> {
+    let mut s = String::new();
+    if let Ok(text) = core::str::from_utf8(bytes) {
+        s.push_str(text.trim()).ok();
+    }
+    s
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Identify the module, reading the manufacturer (+CGMI), model (+CGMM),
+    /// firmware revision (+CGMR) and IMEI (+CGSN)
+    ///
+    /// Requires the AT interface to be initialized. Failing queries are
+    /// logged and leave the corresponding field empty.
+    pub fn identify(&mut self) -> Result<ModuleIdentity, Error> {
+        if self.state == State::Off {
+            return Err(Error::Uninitialized);
+        }
+
+        let mut identity = ModuleIdentity::default();
+
+        match self.send_at(&GetManufacturerId) {
+            Ok(resp) => identity.manufacturer = to_string(&resp.manufacturer),
+            Err(e) => warn!("Failed to read manufacturer: {:?}", e),
+        }
+
+        match self.send_at(&GetModelId) {
+            Ok(resp) => identity.model = to_string(&resp.model),
+            Err(e) => warn!("Failed to read model: {:?}", e),
+        }
+
+        match self.send_at(&GetFirmwareVersion) {
+            Ok(resp) => identity.firmware = to_string(&resp.version),
+            Err(e) => warn!("Failed to read firmware version: {:?}", e),
+        }
+
+        match self.send_at(&GetIMEI { snt: None }) {
+            Ok(resp) => {
+                write!(identity.imei, "{:015}", resp.imei).ok();
+            }
+            Err(e) => warn!("Failed to read IMEI: {:?}", e),
+        }
+
+        Ok(identity)
+    }
+
+    /// Read the IMSI of the SIM (+CIMI)
+    ///
+    /// Fails with [`Error::SimNotReady`] if the SIM is not unlocked yet, as
+    /// the module can answer with garbage before then.
+    pub fn imsi(&mut self) -> Result<Imsi, Error> {
+        let PinStatus { code } = self.send_at(&GetPinStatus)?;
+        if code != PinStatusCode::Ready {
+            return Err(Error::SimNotReady);
+        }
+
+        let mut imsi = String::new();
+        write!(imsi, "{}", self.send_at(&GetCIMI)?.imsi).map_err(|_| Error::_Unknown)?;
+        Ok(Imsi(imsi))
+    }
+
+    /// Read the ICCID of the SIM (+CCID)
+    ///
+    /// Fails with [`Error::InvalidIccid`] if the ICCID is not 19 or 20 digits
+    /// long, or its check digit is wrong.
+    pub fn iccid(&mut self) -> Result<Iccid, Error> {
+        let mut digits = String::<39>::new();
+        write!(digits, "{}", self.send_at(&GetCCID)?.ccid).map_err(|_| Error::InvalidIccid)?;
+        Iccid::new(&digits).ok_or(Error::InvalidIccid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iccid_validation() {
+        let iccid = Iccid::new("89882390000006963907").unwrap();
+        assert_eq!(iccid.as_str(), "89882390000006963907");
+        assert!(Iccid::new("8944500102198304826").is_some());
+        assert!(Iccid::new("89882390000006963908").is_none());
+        assert!(Iccid::new("123").is_none());
+    }
+}
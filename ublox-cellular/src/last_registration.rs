@@ -0,0 +1,158 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    band::BandMask,
+    client::Device,
+    command::network_service::{
+        types::{BandMaskRat, OperatorSelectionMode, RatAct},
+        SetOperatorSelection,
+    },
+    config::CellularConfig,
+    error::Error,
+    module::Capability,
+    operator::{Operator, Plmn, OPERATOR_SELECTION_TIMEOUT},
+};
+
+/// Operator the module last registered on, see
+/// [`last_registration`](Device::last_registration)
+///
+/// Meant to be persisted by the application, and restored with
+/// [`restore_registration`](Device::restore_registration) after the next boot
+/// to skip the full scan of operators and bands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LastRegistration {
+    pub plmn: Plmn,
+    /// Access technology registered on
+    pub act: Option<RatAct>,
+    /// LTE band registered on. The module does not report it, so it is only
+    /// known if set by the application.
+    pub band: Option<u8>,
+}
+
+impl LastRegistration {
+    /// RAT of the band mask of the access technology, for LTE Cat M1 and
+    /// NB-IoT
+    const fn band_mask_rat(&self) -> Option<BandMaskRat> {
+        match self.act {
+            Some(RatAct::Lte) => Some(BandMaskRat::LteCatM1),
+            Some(RatAct::Eutran) => Some(BandMaskRat::NbIot),
+            _ => None,
+        }
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Get the operator the module last registered on, recorded by
+    /// [`spin`](Device::spin) once registered
+    pub fn last_registration(&self) -> Option<LastRegistration> {
+        self.network.last_registration
+    }
+
+    /// Seed the following registrations with `last`, e.g. as persisted from
+    /// [`last_registration`](Device::last_registration) before a power off
+    ///
+    /// When initialized, the module selects the operator of `last` with
+    /// manual/automatic selection (+COPS=4), and falls back to automatic
+    /// selection if it is not available. If the band of `last` is known, the
+    /// bands of LPWA modules are limited to it until registered. Should the
+    /// seeded registration time out, the bands are restored and the following
+    /// attempts scan for all operators.
+    pub fn restore_registration(&mut self, last: LastRegistration) {
+        self.network.registration_hint = Some(last);
+    }
+
+    /// Select the operator, and band if known, of the registration hint.
+    /// Returns whether the registration is seeded.
+    pub(crate) fn seed_registration(&mut self) -> bool {
+        let Some(hint) = self.network.registration_hint else {
+            return false;
+        };
+
+        if let (Some(rat), Some(band), Ok(())) = (
+            hint.band_mask_rat(),
+            hint.band,
+            self.require(Capability::Lpwa),
+        ) {
+            if let Ok(bands) = self.bands(rat) {
+                if bands.contains(band) && self.set_bands(rat, BandMask::band(band)).is_ok() {
+                    self.network.hint_bands = Some((rat, bands));
+                }
+            }
+        }
+
+        let numeric = hint.plmn.to_numeric();
+        match self.network.send_internal_timeout(
+            &SetOperatorSelection {
+                mode: OperatorSelectionMode::ManualAutomatic,
+                format: Some(2),
+                oper: Some(numeric.as_str()),
+                act: hint.act,
+            },
+            true,
+            OPERATOR_SELECTION_TIMEOUT,
+        ) {
+            Ok(_) => {
+                debug!("Registration seeded with {}", numeric.as_str());
+                true
+            }
+            Err(e) => {
+                warn!("Failed to seed the registration: {:?}", e);
+                self.end_seeded_registration();
+                self.network.registration_hint = None;
+                false
+            }
+        }
+    }
+
+    /// Record the operator registered on, once per registration
+    pub(crate) fn record_registration(&mut self) {
+        if self.network.registration_recorded {
+            return;
+        }
+        self.network.registration_recorded = true;
+        self.end_seeded_registration();
+
+        if let Ok(Operator::Registered {
+            plmn: Some(plmn),
+            act,
+            ..
+        }) = self.operator()
+        {
+            let band = self
+                .network
+                .registration_hint
+                .filter(|hint| hint.plmn == plmn && hint.act == act)
+                .and_then(|hint| hint.band);
+            let last = LastRegistration { plmn, act, band };
+            self.network.last_registration = Some(last);
+            // Seed re-registrations, e.g. after a reset, with the operator
+            // registered on now
+            if self.network.registration_hint.is_some() {
+                self.network.registration_hint = Some(last);
+            }
+        }
+    }
+
+    /// Give up on the registration hint after a failed registration, so the
+    /// following attempts scan for all operators and bands
+    pub(crate) fn check_seeded_registration(&mut self) {
+        if self.network.registration_hint.take().is_some() {
+            warn!("Seeded registration failed, falling back to a full scan");
+            self.end_seeded_registration();
+        }
+    }
+
+    /// Restore the bands limited by the registration hint
+    fn end_seeded_registration(&mut self) {
+        if let Some((rat, bands)) = self.network.hint_bands.take() {
+            self.set_bands(rat, bands).ok();
+        }
+    }
+}
@@ -78,28 +78,76 @@
 // This mod MUST go first, so that the others see its macros.
 pub(crate) mod fmt;
 
+mod address;
+mod band;
 mod blocking_timer;
+mod cell;
 mod client;
+mod clock;
 pub mod command;
 mod config;
+mod context;
+mod counters;
 pub mod error;
+mod file_system;
+mod fota;
+mod ftp;
+mod gnss;
+mod http;
+mod identity;
+mod last_registration;
+mod md5;
+mod mno;
+mod module;
 mod module_timing;
+mod mqtt;
 mod network;
+mod operator;
+mod pdu;
 mod power;
 mod registration;
+mod security;
+mod self_test;
 mod services;
+mod signal;
+mod sms;
+mod ussd;
 
 pub use atat::serde_bytes;
 use client::{URC_CAPACITY, URC_SUBSCRIBERS};
 use command::Urc;
 pub use ublox_sockets as sockets;
 
+pub use address::PdpAddress;
+pub use band::BandMask;
+pub use cell::{Cell, CellInfo};
 pub use client::Device as GsmClient;
-pub use config::NoPin;
-pub use network::{ContextId, ProfileId};
-pub use services::data::apn::{APNInfo, Apn};
+pub use client::State;
+pub use clock::DateTime;
+pub use command::network_service::types::MnoProfile;
+pub use config::{Config, NoPin};
+pub use counters::DataCounters;
+pub use file_system::MAX_FILES;
+pub use ftp::FtpTransfer;
+pub use gnss::{FixQuality, Position};
+pub use http::{HttpResponse, MAX_HTTP_BODY};
+pub use identity::{Iccid, Imsi, ModuleIdentity};
+pub use last_registration::LastRegistration;
+pub use mno::LteMode;
+pub use module::{Capability, Module};
+pub use mqtt::{MqttConfig, MAX_MQTT_PAYLOAD, MAX_MQTT_TOPIC};
+pub use network::{ContextId, Event, PacketDomainEvent, ProfileId};
+pub use operator::{Operator, OperatorScanResult, Plmn};
+pub use pdu::MAX_SMS_PARTS;
+pub use registration::Status as RegistrationStatus;
+pub use security::{CertKind, Certificate, SecurityProfile, MAX_CERTIFICATE_SIZE};
+pub use self_test::SelfTestReport;
+pub use services::data::apn::{APNInfo, Apn, PdpType};
 pub use services::data::ssl::SecurityProfileId;
-pub use services::data::DataService;
+pub use services::data::{DataService, SocketPollResults, SocketState, MAX_SOCKETS};
+pub use signal::SignalQuality;
+pub use sms::{MessageStorage, SmsMessage, SmscAddress, MAX_SMS_LENGTH};
+pub use ussd::UssdResponse;
 
 // Re-export atat
 pub use atat;
@@ -118,7 +166,50 @@ pub type UbloxCellularIngress<'a, const INGRESS_BUF_SIZE: usize> = atat::Ingress
 
 pub type UbloxCellularUrcChannel = atat::UrcChannel<Urc, URC_CAPACITY, URC_SUBSCRIBERS>;
 
-pub use config::CellularConfig;
+/// Default size of the receive buffer of each socket
+pub const SOCKET_BUFFER_LEN: usize = 1024;
+
+/// Socket storage of a [`Device6`], see [`socket_set!`]
+pub type SocketSet6 = sockets::SocketSet<MAX_SOCKETS, SOCKET_BUFFER_LEN>;
+
+/// [`GsmClient`] with storage for the maximum of [`MAX_SOCKETS`] sockets, of
+/// [`SOCKET_BUFFER_LEN`] bytes each
+pub type Device6<'buf, 'sub, AtCl, AtUrcCh, Config> =
+    GsmClient<'buf, 'sub, AtCl, AtUrcCh, Config, MAX_SOCKETS, SOCKET_BUFFER_LEN>;
+
+/// Create the socket storage for
+/// [`set_socket_storage`](GsmClient::set_socket_storage)
+///
+/// Without arguments, the storage of a [`Device6`] is created. Otherwise the
+/// number of sockets and the buffer length are given, e.g.
+/// `socket_set!(2, 512)`. Each invocation creates its storage only once, and
+/// panics if it is reached again.
+///
+/// ```ignore
+/// modem.set_socket_storage(ublox_cellular::socket_set!());
+/// ```
+#[macro_export]
+macro_rules! socket_set {
+    () => {
+        $crate::socket_set!($crate::MAX_SOCKETS, $crate::SOCKET_BUFFER_LEN)
+    };
+    ($n:expr, $l:expr) => {{
+        static TAKEN: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+        static mut SOCKET_SET: Option<$crate::sockets::SocketSet<{ $n }, { $l }>> = None;
+
+        if TAKEN.swap(true, core::sync::atomic::Ordering::AcqRel) {
+            panic!("socket_set! storage is already in use");
+        }
+
+        // SAFETY: `TAKEN` ensures this is the only reference to `SOCKET_SET`
+        unsafe { (*core::ptr::addr_of_mut!(SOCKET_SET)).insert($crate::sockets::SocketSet::new()) }
+    }};
+}
+
+pub use config::{
+    CellularConfig, ContextConfig, EdrxConfig, EdrxStatus, PsmConfig, RatFallback, RecoveryLadder,
+    RecoveryLevel,
+};
 
 /// Prelude - Include traits
 pub mod prelude {
@@ -0,0 +1,118 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    client::Device,
+    command::{
+        mobile_control::{
+            types::{Functionality, ResetMode},
+            SetModuleFunctionality,
+        },
+        network_service::{
+            responses::{LpwaRadioAccessTechnology, MnoProfileSelection},
+            types::{LpwaRat, MnoProfile},
+            GetLpwaRadioAccessTechnology, GetMnoProfile, SetLpwaRadioAccessTechnology,
+            SetMnoProfile,
+        },
+    },
+    config::CellularConfig,
+    error::Error,
+    module::Capability,
+};
+
+/// LTE radio access technologies an LPWA module may register on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LteMode {
+    /// LTE Cat M1 only
+    CatM1,
+    /// NB-IoT only
+    NbIot,
+    /// LTE Cat M1, falling back to NB-IoT
+    CatM1NbIot,
+}
+
+impl LteMode {
+    /// Preferred and second RAT of the mode, as given to +URAT
+    const fn rats(self) -> (LpwaRat, Option<LpwaRat>) {
+        match self {
+            Self::CatM1 => (LpwaRat::LteCatM1, None),
+            Self::NbIot => (LpwaRat::NbIot, None),
+            Self::CatM1NbIot => (LpwaRat::LteCatM1, Some(LpwaRat::NbIot)),
+        }
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Select the Mobile Network Operator profile (+UMNOPROF)
+    ///
+    /// Should be called before registering, e.g. after
+    /// [`setup_at_commands`](Device::setup_at_commands). The module is
+    /// rebooted to apply a new profile, and registers again on the next
+    /// [`spin`](Device::spin). Nothing is done if `profile` is already
+    /// selected.
+    pub fn set_mno_profile(&mut self, profile: MnoProfile) -> Result<(), Error> {
+        self.require(Capability::Lpwa)?;
+
+        if let Ok(MnoProfileSelection { profile: current }) = self.send_at(&GetMnoProfile) {
+            if current == profile {
+                return Ok(());
+            }
+        }
+
+        self.minimum_functionality()?;
+        self.send_at(&SetMnoProfile { profile })?;
+        self.reboot()
+    }
+
+    /// Select the LTE radio access technologies to register on (+URAT)
+    ///
+    /// Should be called before registering, e.g. after
+    /// [`setup_at_commands`](Device::setup_at_commands). The module is
+    /// rebooted to apply a new selection, and registers again on the next
+    /// [`spin`](Device::spin). Nothing is done if `mode` is already selected.
+    pub fn set_lte_mode(&mut self, mode: LteMode) -> Result<(), Error> {
+        self.require(Capability::Lpwa)?;
+
+        let (first, second) = mode.rats();
+        if let Ok(LpwaRadioAccessTechnology {
+            first: current_first,
+            second: current_second,
+        }) = self.send_at(&GetLpwaRadioAccessTechnology)
+        {
+            if current_first == first && current_second == second {
+                return Ok(());
+            }
+        }
+
+        self.minimum_functionality()?;
+        self.send_at(&SetLpwaRadioAccessTechnology { first, second })?;
+        self.reboot()
+    }
+
+    /// Deregister, as required to change the MNO profile and RAT selection
+    fn minimum_functionality(&mut self) -> Result<(), Error> {
+        self.send_at(&SetModuleFunctionality {
+            fun: Functionality::Minimum,
+            // SARA-R5 This parameter can be used only when <fun> is 1, 4 or 19
+            #[cfg(feature = "sara-r5")]
+            rst: None,
+            #[cfg(not(feature = "sara-r5"))]
+            rst: Some(ResetMode::DontReset),
+        })?;
+        Ok(())
+    }
+
+    /// Reboot the module to apply a new configuration, and set up the AT
+    /// interface again once it is back
+    fn reboot(&mut self) -> Result<(), Error> {
+        self.network.data_connection_lost();
+        self.soft_reset(false)?;
+        self.setup_at_commands()
+    }
+}
@@ -0,0 +1,137 @@
+use atat::blocking::AtatClient;
+
+use crate::{client::Device, config::CellularConfig, error::Error};
+
+/// u-blox module variant, see [`CellularConfig::MODULE`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Module {
+    LaraR2,
+    LaraR6,
+    LeonG1,
+    LisaU2,
+    MpciL2,
+    SaraG3,
+    SaraG4,
+    SaraR5,
+    SaraU1,
+    SaraU2,
+    TobyL2,
+    TobyL4,
+    TobyR2,
+    /// No module variant selected. All commands are assumed to be supported.
+    Any,
+}
+
+/// Functionality not supported by all module variants, see
+/// [`Module::supports`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Capability {
+    /// Airplane mode (+CFUN=4)
+    AirplaneMode,
+    /// Power Saving Mode (+CPSMS)
+    Psm,
+    /// Extended discontinuous reception (+CEDRXS)
+    Edrx,
+    /// MQTT client (+UMQTT)
+    Mqtt,
+    /// LTE Cat M1 / NB-IoT selection and MNO profiles (+URAT, +UMNOPROF)
+    Lpwa,
+    /// Internal temperature sensor (+UTEMP)
+    Temperature,
+    /// HTTP client (+UHTTP)
+    Http,
+    /// FTP client (+UFTP)
+    Ftp,
+    /// GNSS receiver connected to the module and CellLocate (+UGPS, +ULOC)
+    Location,
+    /// Firmware update over the air (+UFOTA, +UFWINSTALL)
+    Fota,
+}
+
+impl Module {
+    /// Module variant selected by the enabled module feature, e.g.
+    /// `sara-r5`
+    pub const fn from_features() -> Self {
+        if cfg!(feature = "lara-r2") {
+            Self::LaraR2
+        } else if cfg!(feature = "lara-r6") {
+            Self::LaraR6
+        } else if cfg!(feature = "leon-g1") {
+            Self::LeonG1
+        } else if cfg!(feature = "lisa-u2") {
+            Self::LisaU2
+        } else if cfg!(feature = "mpci-l2") {
+            Self::MpciL2
+        } else if cfg!(feature = "sara-g3") {
+            Self::SaraG3
+        } else if cfg!(feature = "sara-g4") {
+            Self::SaraG4
+        } else if cfg!(feature = "sara-r5") {
+            Self::SaraR5
+        } else if cfg!(feature = "sara-u1") {
+            Self::SaraU1
+        } else if cfg!(feature = "sara-u2") {
+            Self::SaraU2
+        } else if cfg!(feature = "toby-l2") {
+            Self::TobyL2
+        } else if cfg!(feature = "toby-l4") {
+            Self::TobyL4
+        } else if cfg!(feature = "toby-r2") {
+            Self::TobyR2
+        } else {
+            Self::Any
+        }
+    }
+
+    /// Whether the module supports `capability`
+    pub const fn supports(self, capability: Capability) -> bool {
+        match capability {
+            Capability::AirplaneMode => matches!(
+                self,
+                Self::TobyL2
+                    | Self::MpciL2
+                    | Self::LisaU2
+                    | Self::SaraU2
+                    | Self::TobyR2
+                    | Self::LaraR2
+                    | Self::LaraR6
+                    | Self::TobyL4
+                    | Self::SaraR5
+                    | Self::Any
+            ),
+            Capability::Psm | Capability::Edrx | Capability::Mqtt | Capability::Temperature => {
+                matches!(self, Self::SaraR5 | Self::LaraR6 | Self::Any)
+            }
+            Capability::Lpwa => matches!(self, Self::SaraR5 | Self::Any),
+            Capability::Http | Capability::Ftp => true,
+            // MPCI-L2 has no DDC (I2C) interface for a GNSS receiver
+            Capability::Location => !matches!(self, Self::MpciL2),
+            // LEON-G1, SARA-G3, SARA-G4 and SARA-U1 are only updated over the AT
+            // interface (+UFWUPD)
+            Capability::Fota => !matches!(
+                self,
+                Self::LeonG1 | Self::SaraG3 | Self::SaraG4 | Self::SaraU1
+            ),
+        }
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Fail with [`Error::UnsupportedByModule`] if the configured module does
+    /// not support `capability`
+    pub(crate) fn require(&self, capability: Capability) -> Result<(), Error> {
+        if Config::MODULE.supports(capability) {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedByModule)
+        }
+    }
+}
@@ -1,40 +1,50 @@
-#![allow(clippy::if_same_then_else)]
+//! Timings of the control pins of the module variant selected by feature.
+//! These are the defaults of the corresponding [`CellularConfig`] consts,
+//! which can be overridden for custom hardware.
+//!
+//! [`CellularConfig`]: crate::config::CellularConfig
 
 use embassy_time::Duration;
 
 /// Low time of `PWR_ON` pin to trigger module switch on from power off mode
-pub fn pwr_on_time() -> Duration {
-    if cfg!(feature = "lara-r6") {
+pub const fn pwr_on_time() -> Duration {
+    if cfg!(any(feature = "lara-r6", feature = "sara-r5")) {
         Duration::from_millis(150)
-    } else if cfg!(feature = "toby-r2") {
-        Duration::from_micros(50)
     } else {
         Duration::from_micros(50)
     }
 }
 
 /// Low time of `PWR_ON` pin to trigger module graceful switch off
-pub fn pwr_off_time() -> Duration {
-    if cfg!(feature = "lara-r6") {
+pub const fn pwr_off_time() -> Duration {
+    if cfg!(any(feature = "lara-r6", feature = "sara-r5")) {
         Duration::from_millis(1500)
-    } else if cfg!(feature = "toby-r2") {
-        Duration::from_secs(1)
     } else {
         Duration::from_secs(1)
     }
 }
 
 /// Low time of `RESET_N` pin to trigger module reset (reboot)
-pub fn reset_time() -> Duration {
+pub const fn reset_time() -> Duration {
     if cfg!(feature = "lara-r6") {
         Duration::from_millis(10)
-    } else if cfg!(feature = "toby-r2") {
-        Duration::from_millis(50)
+    } else if cfg!(feature = "sara-r5") {
+        Duration::from_millis(100)
     } else {
         Duration::from_millis(50)
     }
 }
 
+/// Time from the end of the `RESET_N` pulse until the module answers AT
+/// commands
+pub const fn boot_wait_time() -> Duration {
+    if cfg!(any(feature = "lara-r6", feature = "sara-r5")) {
+        Duration::from_secs(10)
+    } else {
+        Duration::from_secs(5)
+    }
+}
+
 /// Low time of `RESET_N` pin to trigger module abrupt emergency switch off
 ///
 /// NOTE: Not all modules support this operation from `RESET_N`
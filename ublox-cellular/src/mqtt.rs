@@ -0,0 +1,213 @@
+use atat::blocking::AtatClient;
+use core::fmt::Write;
+use embassy_time::{Duration, Instant};
+use heapless::String;
+
+use crate::{
+    blocking_timer::BlockingTimer,
+    client::Device,
+    command::mqtt::{
+        responses::MqttCommandResponse,
+        types::{MqttProfileOperation, MqttQos},
+        MqttLogin, MqttLogout, MqttPublish, MqttSubscribe, MqttUnsubscribe, SetMqttProfile,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+    module::Capability,
+    security::bounded,
+    services::data::{ssl::SecurityProfileId, Error as DataServiceError},
+};
+
+/// Maximum length of the topic of a received MQTT message
+pub const MAX_MQTT_TOPIC: usize = 64;
+
+/// Maximum size of the payload of published and received MQTT messages
+pub const MAX_MQTT_PAYLOAD: usize = 256;
+
+/// +UMQTTC operation codes, as reported by the +UUMQTTC URC
+pub(crate) const MQTT_LOGOUT: u8 = 0;
+const MQTT_LOGIN: u8 = 1;
+const MQTT_PUBLISH: u8 = 2;
+
+/// Time to wait for the +UUMQTTC URC
+const MQTT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// MQTT broker connection, see [`mqtt_connect`](Device::mqtt_connect)
+#[derive(Debug, Clone)]
+pub struct MqttConfig<'a> {
+    pub client_id: &'a str,
+    pub host: &'a str,
+    pub port: u16,
+    /// Username and password
+    pub credentials: Option<(&'a str, &'a str)>,
+    /// Keep alive period in seconds, `None` for the default of the module
+    pub keep_alive: Option<u16>,
+    /// Security profile of the SSL/TLS connection, see
+    /// [`configure_security_profile`](Device::configure_security_profile)
+    pub security: Option<SecurityProfileId>,
+    pub clean_session: bool,
+}
+
+impl<'a> MqttConfig<'a> {
+    /// Unencrypted connection to `host` on port 1883
+    pub const fn new(client_id: &'a str, host: &'a str) -> Self {
+        Self {
+            client_id,
+            host,
+            port: 1883,
+            credentials: None,
+            keep_alive: None,
+            security: None,
+            clean_session: true,
+        }
+    }
+}
+
+/// Fail with [`Error::MqttRequestFailed`] if the module did not accept a
+/// +UMQTTC command
+fn accepted(response: MqttCommandResponse) -> Result<(), Error> {
+    if response.result == 1 {
+        Ok(())
+    } else {
+        Err(Error::MqttRequestFailed)
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Connect to the MQTT broker of `config` (+UMQTT, +UMQTTC)
+    ///
+    /// Blocks until the broker accepted the connection, which fails with
+    /// [`Error::MqttRequestFailed`] otherwise. A lost connection is reported
+    /// with [`Event::MqttDisconnected`](crate::Event::MqttDisconnected).
+    pub fn mqtt_connect(&mut self, config: MqttConfig) -> Result<(), Error> {
+        self.require(Capability::Mqtt)?;
+        self.send_at(&SetMqttProfile {
+            operation: MqttProfileOperation::ClientId(bounded(config.client_id)?),
+        })?;
+
+        if let Some(profile_id) = config.security {
+            self.send_at(&SetMqttProfile {
+                operation: MqttProfileOperation::Secure(true, profile_id),
+            })?;
+        }
+
+        self.send_at(&SetMqttProfile {
+            operation: MqttProfileOperation::ServerName(bounded(config.host)?, config.port),
+        })?;
+
+        if let Some((username, password)) = config.credentials {
+            self.send_at(&SetMqttProfile {
+                operation: MqttProfileOperation::UsernamePassword(
+                    bounded(username)?,
+                    bounded(password)?,
+                ),
+            })?;
+        }
+
+        if let Some(keep_alive) = config.keep_alive {
+            self.send_at(&SetMqttProfile {
+                operation: MqttProfileOperation::InactivityTimeout(keep_alive),
+            })?;
+        }
+
+        self.send_at(&SetMqttProfile {
+            operation: MqttProfileOperation::CleanSession(config.clean_session),
+        })?;
+
+        self.network.mqtt_result = None;
+        accepted(self.send_at(&MqttLogin)?)?;
+
+        // The return code of the broker, 0 if the connection is accepted
+        match self.wait_mqtt_result(MQTT_LOGIN)? {
+            0 => Ok(()),
+            code => {
+                error!("MQTT connection refused: {}", code);
+                Err(Error::MqttRequestFailed)
+            }
+        }
+    }
+
+    /// Disconnect from the MQTT broker
+    pub fn mqtt_disconnect(&mut self) -> Result<(), Error> {
+        self.require(Capability::Mqtt)?;
+        accepted(self.send_at(&MqttLogout)?)
+    }
+
+    /// Publish `payload` on `topic`
+    ///
+    /// With a QoS above [`MqttQos::AtMostOnce`], this blocks until the
+    /// broker acknowledged the message. Payloads longer than
+    /// [`MAX_MQTT_PAYLOAD`] are rejected.
+    pub fn mqtt_publish(&mut self, topic: &str, payload: &[u8], qos: MqttQos) -> Result<(), Error> {
+        self.require(Capability::Mqtt)?;
+        if payload.len() > MAX_MQTT_PAYLOAD {
+            return Err(Error::DataService(DataServiceError::BadLength));
+        }
+
+        // Published in hex mode, to allow binary payloads
+        let mut message = String::<{ MAX_MQTT_PAYLOAD * 2 }>::new();
+        for byte in payload {
+            write!(message, "{:02X}", byte).ok();
+        }
+
+        self.network.mqtt_result = None;
+        accepted(self.send_at(&MqttPublish {
+            qos,
+            retain: false,
+            hex_mode: true,
+            topic,
+            message: &message,
+        })?)?;
+
+        if qos == MqttQos::AtMostOnce {
+            return Ok(());
+        }
+
+        match self.wait_mqtt_result(MQTT_PUBLISH)? {
+            1 => Ok(()),
+            _ => Err(Error::MqttRequestFailed),
+        }
+    }
+
+    /// Subscribe to `topic`, up to the QoS `qos`
+    ///
+    /// Received messages are reported with
+    /// [`Event::MqttMessage`](crate::Event::MqttMessage).
+    pub fn mqtt_subscribe(&mut self, topic: &str, qos: MqttQos) -> Result<(), Error> {
+        self.require(Capability::Mqtt)?;
+        accepted(self.send_at(&MqttSubscribe {
+            max_qos: qos,
+            topic,
+        })?)
+    }
+
+    pub fn mqtt_unsubscribe(&mut self, topic: &str) -> Result<(), Error> {
+        self.require(Capability::Mqtt)?;
+        accepted(self.send_at(&MqttUnsubscribe { topic })?)
+    }
+
+    /// Wait for the +UUMQTTC URC of `op_code`, returning its result
+    fn wait_mqtt_result(&mut self, op_code: u8) -> Result<u8, Error> {
+        let start = Instant::now();
+        while start.elapsed() < MQTT_TIMEOUT {
+            self.network.handle_urc().ok();
+            match self.network.mqtt_result.take() {
+                Some(result) if result.op_code == op_code => return Ok(result.result),
+                // The connection has been lost while waiting
+                Some(result) if result.op_code == MQTT_LOGOUT => {
+                    return Err(Error::MqttRequestFailed)
+                }
+                _ => {}
+            }
+            BlockingTimer::after(Duration::from_millis(100)).wait();
+        }
+
+        Err(Error::Generic(GenericError::Timeout))
+    }
+}
@@ -1,44 +1,198 @@
 use crate::{
+    band::BandMask,
     client::{URC_CAPACITY, URC_SUBSCRIBERS},
     command::{
+        device_lock::{self, types::SimState},
+        ftp,
         general::GetCIMI,
+        gnss::{self, types::LocationSensor},
+        http,
+        ip_transport_layer::{
+            responses::SocketControlResponse,
+            types::{HexMode, SocketControlParam},
+            SetHexMode, SocketControl,
+        },
         mobile_control::{
+            self,
             types::{Functionality, ResetMode},
             GetExtendedErrorReport, SetModuleFunctionality,
         },
+        mqtt,
         network_service::{
-            types::OperatorSelectionMode, GetNetworkRegistrationStatus, SetOperatorSelection,
+            types::{BandMaskRat, OperatorSelectionMode},
+            GetNetworkRegistrationStatus, SetOperatorSelection,
         },
         psn::{
-            self, types::PDPContextStatus, GetEPSNetworkRegistrationStatus,
-            GetGPRSNetworkRegistrationStatus, GetPDPContextState, SetPDPContextState,
+            self,
+            types::{
+                EPSNetworkRegistrationStat, EPSNetworkRegistrationUrcConfig, PDPContextStatus,
+            },
+            GetEPSNetworkRegistrationStatus, GetGPRSNetworkRegistrationStatus,
+            GetPDPContextAddress, GetPDPContextState, SetEPSNetworkRegistrationStatus,
+            SetPDPContextState,
         },
+        sms, supplementary_services,
+        system_features::{self, types::PsmState},
         Urc, AT,
     },
+    config::RecoveryLevel,
     error::GenericError,
+    gnss::{FixQuality, Position},
+    last_registration::LastRegistration,
+    mqtt::{MAX_MQTT_PAYLOAD, MAX_MQTT_TOPIC, MQTT_LOGOUT},
     registration::{self, ConnectionState, RegistrationState},
-    services::data::{ContextState, PROFILE_ID},
+    services::data::{ContextState, CONTEXT_ID},
+    sms::MessageStorage,
 };
 use atat::{atat_derive::AtatLen, blocking::AtatClient, UrcSubscription};
 use embassy_time::{Duration, Instant};
+use embedded_nal::{IpAddr, Ipv4Addr};
 use hash32_derive::Hash32;
+use heapless::{Deque, String, Vec};
 use serde::{Deserialize, Serialize};
+use ublox_sockets::SocketHandle;
 
 const REGISTRATION_CHECK_INTERVAL: Duration = Duration::from_secs(15);
-const REGISTRATION_TIMEOUT: Duration = Duration::from_secs(3 * 60);
 const CHECK_IMSI_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Number of [`Event`]s buffered until they are retrieved
+pub(crate) const EVENT_CAPACITY: usize = 8;
+
+/// Maximum number of PDP contexts tracked at the same time
+pub(crate) const MAX_CONTEXTS: usize = 8;
+
 #[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
     Generic(GenericError),
     AT(atat::Error),
-    RegistrationDenied,
+    /// `+CME ERROR: <err>` reported by the module
+    Cme(u16),
+    /// `+CMS ERROR: <err>` reported by the module
+    Cms(u16),
+    /// Registration was denied by the network, see
+    /// [`Error::RegistrationDenied`](crate::error::Error::RegistrationDenied)
+    RegistrationDenied {
+        cause: u8,
+    },
+    RegistrationTimeout,
+    RoamingNotAllowed,
     UnknownProfile,
     ActivationFailed,
     _Unknown,
 }
 
+/// Events raised by URCs from the module, retrieved with
+/// [`Device::poll_event`](crate::GsmClient::poll_event)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Event {
+    /// A new SMS has been stored at `index` in `storage`
+    SmsReceived { storage: MessageStorage, index: u8 },
+    /// The data connection is up on the PDP context `cid`, with the address
+    /// `ip` assigned by the network (+CGPADDR)
+    Connected {
+        cid: ContextId,
+        #[cfg_attr(feature = "defmt", defmt(Display2Format))]
+        ip: IpAddr,
+    },
+    /// The data connection has been lost, e.g. as the registration was lost
+    /// or the network deactivated the context. It is set up again by
+    /// [`spin`](crate::GsmClient::spin).
+    NetworkLost,
+    /// The PDP context `cid` has been deactivated, e.g. by the network
+    Disconnected(ContextId),
+    /// The module stopped answering AT commands while connected, and is
+    /// re-initialized. See [`CellularConfig::LIVENESS_TIMEOUT`](crate::CellularConfig::LIVENESS_TIMEOUT).
+    ModuleUnresponsive,
+    /// `len` bytes are available to read from the socket `handle`, as
+    /// announced by +UUSORD (TCP) or +UUSORF (UDP)
+    SocketDataAvailable { handle: SocketHandle, len: usize },
+    /// The socket `handle` has been closed by the remote (+UUSOCL)
+    ///
+    /// `error` is the last error of the socket, see
+    /// [`socket_last_error`](crate::DataService::socket_last_error), or
+    /// `None` if it could not be read.
+    SocketClosed {
+        handle: SocketHandle,
+        error: Option<u16>,
+    },
+    /// `percent` of the FOTA image has been downloaded, see
+    /// [`fota_download`](crate::GsmClient::fota_download)
+    FotaProgress { percent: u8 },
+    /// A message has been received on a subscribed MQTT topic, see
+    /// [`mqtt_subscribe`](crate::GsmClient::mqtt_subscribe)
+    MqttMessage {
+        topic: String<MAX_MQTT_TOPIC>,
+        payload: Vec<u8, MAX_MQTT_PAYLOAD>,
+    },
+    /// The connection to the MQTT broker has been lost, and has to be
+    /// re-established with [`mqtt_connect`](crate::GsmClient::mqtt_connect)
+    MqttDisconnected,
+    /// The FTP command `op` has completed (+UUFTPCR), see
+    /// [`ftp_get`](crate::GsmClient::ftp_get)
+    FtpTransferComplete {
+        op: ftp::types::FtpOperation,
+        success: bool,
+    },
+    /// A GNSS fix requested with
+    /// [`gnss_request_fix`](crate::GsmClient::gnss_request_fix) (+UULOC)
+    GnssFix(Position),
+    /// Packet domain event reported by +CGEV, see
+    /// [`CellularConfig::PACKET_EVENT_REPORTING`](crate::CellularConfig::PACKET_EVENT_REPORTING)
+    PacketDomain(PacketDomainEvent),
+    /// The SIM has been removed (+UUSIMSTAT, +CPIN). Registration is
+    /// suspended until it is inserted again.
+    SimRemoved,
+    /// The SIM has been inserted again after [`Event::SimRemoved`]
+    SimInserted,
+    /// The module has rebooted unexpectedly, as announced by its greeting
+    /// text. Sockets and PDP contexts of the module are lost, so the sockets
+    /// are removed with [`reset_sockets`](crate::GsmClient::reset_sockets),
+    /// and the module is re-initialized by [`spin`](crate::GsmClient::spin).
+    ModuleRebooted,
+    /// The module entered PSM (+UUPSMR), see
+    /// [`in_psm`](crate::GsmClient::in_psm)
+    PsmEntered,
+    /// The module exited PSM (+UUPSMR)
+    PsmExited,
+    /// Recovery from consecutive failures escalated to `level`, see
+    /// [`CellularConfig::RECOVERY`](crate::CellularConfig::RECOVERY)
+    RecoveryEscalated { level: RecoveryLevel },
+    /// The network provided a new time zone (+CTZV / +CTZE), see
+    /// [`CellularConfig::TIME_ZONE_REPORTING`](crate::CellularConfig::TIME_ZONE_REPORTING)
+    TimeZoneChanged {
+        /// Difference between local time and GMT, in quarters of an hour
+        offset: i8,
+        /// Daylight saving time adjustment included in `offset`, in hours,
+        /// if reported
+        dst: Option<u8>,
+    },
+    /// Events have been dropped as they were not retrieved in time, so e.g. a
+    /// registration change may have been missed. The registration and socket
+    /// states should be queried again.
+    EventQueueOverflow,
+}
+
+/// Packet domain event of a +CGEV URC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PacketDomainEvent {
+    /// "NW DETACH": the network detached the module from the packet domain
+    NetworkDetach,
+    /// "ME DETACH": the module detached from the packet domain
+    ModuleDetach,
+    /// "NW DEACT": the network deactivated a PDP context
+    NetworkDeactivate,
+    /// "ME DEACT": the module deactivated a PDP context
+    ModuleDeactivate,
+    /// "NW PDN DEACT": the network deactivated a PDN connection
+    NetworkPdnDeactivate,
+    /// "ME PDN DEACT": the module deactivated a PDN connection
+    ModulePdnDeactivate,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash32, Serialize, Deserialize, AtatLen)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ProfileId(pub u8);
@@ -47,8 +201,22 @@ pub struct ProfileId(pub u8);
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ContextId(pub u8);
 
+/// Response timeout from which commands are considered long running, and are
+/// not retried
+const LONG_COMMAND_TIMEOUT_MS: u32 = 10_000;
+
 pub struct AtTx<'sub, AtCl> {
     consecutive_timeouts: u8,
+    /// Number of attempts of each command, if it times out
+    retries: u8,
+    /// Time of the last successful AT command exchange
+    pub(crate) last_success: Option<Instant>,
+    /// Time of the last command sent
+    last_command: Option<Instant>,
+    /// UART idle time after which the module may be in power saving, and has
+    /// to be woken up before a command, see
+    /// [`CellularConfig::UART_POWER_SAVING`](crate::CellularConfig::UART_POWER_SAVING)
+    pub(crate) wake_after: Option<Duration>,
     urc_subscription: UrcSubscription<'sub, Urc, URC_CAPACITY, URC_SUBSCRIBERS>,
     client: AtCl,
 }
@@ -57,9 +225,14 @@ impl<'sub, AtCl: AtatClient> AtTx<'sub, AtCl> {
     pub fn new(
         client: AtCl,
         urc_subscription: UrcSubscription<'sub, Urc, URC_CAPACITY, URC_SUBSCRIBERS>,
+        retries: u8,
     ) -> Self {
         Self {
             consecutive_timeouts: 0,
+            retries: retries.max(1),
+            last_success: None,
+            last_command: None,
+            wake_after: None,
             urc_subscription,
             client,
         }
@@ -72,37 +245,36 @@ impl<'sub, AtCl: AtatClient> AtTx<'sub, AtCl> {
     where
         A: atat::AtatCmd<LEN>,
     {
-        self.client
-            .send_retry(req)
-            .map_err(|e| match e {
-                atat::Error::Timeout => {
-                    self.consecutive_timeouts =
-                        self.consecutive_timeouts.saturating_add(A::ATTEMPTS);
-                    Error::AT(atat::Error::Timeout)
-                }
-                atat::Error::Read => Error::AT(atat::Error::Read),
-                atat::Error::Write => Error::AT(atat::Error::Write),
-                atat::Error::InvalidResponse => Error::AT(atat::Error::InvalidResponse),
-                atat::Error::Aborted => Error::AT(atat::Error::Aborted),
-                atat::Error::Parse => Error::AT(atat::Error::Parse),
-                _ => Error::AT(atat::Error::Error),
-            })
-            .map(|res| {
-                self.consecutive_timeouts = 0;
-                res
-            })
+        self.send(req)
     }
 
     pub fn send<A, const LEN: usize>(&mut self, req: &A) -> Result<A::Response, Error>
     where
         A: atat::AtatCmd<LEN>,
     {
-        self.client
-            .send_retry(req)
+        self.send_attempts(req, self.attempts::<A, LEN>())
+    }
+
+    /// Send `req` in a single attempt, for commands that must not be retried
+    pub fn send_once<A, const LEN: usize>(&mut self, req: &A) -> Result<A::Response, Error>
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        self.send_attempts(req, 1)
+    }
+
+    fn send_attempts<A, const LEN: usize>(
+        &mut self,
+        req: &A,
+        attempts: u8,
+    ) -> Result<A::Response, Error>
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        self.send_retry(req, attempts)
             .map_err(|e| match e {
                 atat::Error::Timeout => {
-                    self.consecutive_timeouts =
-                        self.consecutive_timeouts.saturating_add(A::ATTEMPTS);
+                    self.consecutive_timeouts = self.consecutive_timeouts.saturating_add(attempts);
                     Error::AT(atat::Error::Timeout)
                 }
                 atat::Error::Read => Error::AT(atat::Error::Read),
@@ -110,19 +282,82 @@ impl<'sub, AtCl: AtatClient> AtTx<'sub, AtCl> {
                 atat::Error::InvalidResponse => Error::AT(atat::Error::InvalidResponse),
                 atat::Error::Aborted => Error::AT(atat::Error::Aborted),
                 atat::Error::Parse => Error::AT(atat::Error::Parse),
+                atat::Error::CmeError(e) => Error::Cme(e as u16),
+                atat::Error::CmsError(e) => Error::Cms(e as u16),
                 _ => Error::AT(atat::Error::Error),
             })
             .map(|res| {
                 self.consecutive_timeouts = 0;
+                self.last_success = Some(Instant::now());
                 res
             })
     }
 
-    pub fn handle_urc<F: FnOnce(Urc) -> bool>(&mut self, f: F) -> Result<(), Error> {
+    /// Number of times `A` is attempted while it times out: `retries`, except
+    /// for abortable and long running commands, which are attempted once
+    /// rather than multiplying their timeout
+    fn attempts<A, const LEN: usize>(&self) -> u8
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        if A::CAN_ABORT || A::MAX_TIMEOUT_MS >= LONG_COMMAND_TIMEOUT_MS {
+            1
+        } else {
+            self.retries
+        }
+    }
+
+    /// Send `req`, attempting it up to `attempts` times while it times out
+    fn send_retry<A, const LEN: usize>(
+        &mut self,
+        req: &A,
+        attempts: u8,
+    ) -> Result<A::Response, atat::Error>
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        self.wake();
+        for attempt in 1..=attempts {
+            if attempt > 1 {
+                debug!("Attempt {}:", attempt);
+            }
+            let res = self.client.send(req);
+            self.last_command = Some(Instant::now());
+            match res {
+                Err(atat::Error::Timeout) => {}
+                res => return res,
+            }
+        }
+        Err(atat::Error::Timeout)
+    }
+
+    /// Wake the module from UART power saving, if the UART has been idle for
+    /// longer than `wake_after`
+    ///
+    /// The module wakes up on the first character received, which is lost,
+    /// so a throwaway `AT` is sent, ignoring whether it is answered.
+    fn wake(&mut self) {
+        let Some(wake_after) = self.wake_after else {
+            return;
+        };
+        if self
+            .last_command
+            .map_or(false, |at| at.elapsed() < wake_after)
+        {
+            return;
+        }
+
+        trace!("Waking module from UART power saving");
+        self.client.send(&AT).ok();
+    }
+
+    /// Handle the next pending URC with `f`, returning whether there was one
+    pub fn handle_urc<F: FnOnce(Urc) -> bool>(&mut self, f: F) -> Result<bool, Error> {
         if let Some(urc) = self.urc_subscription.try_next_message_pure() {
             f(urc);
+            return Ok(true);
         }
-        Ok(())
+        Ok(false)
     }
 }
 
@@ -130,17 +365,145 @@ pub struct Network<'sub, AtCl> {
     pub(crate) status: RegistrationState,
     pub(crate) context_state: ContextState,
     pub(crate) at_tx: AtTx<'sub, AtCl>,
+    pub(crate) events: Deque<Event, EVENT_CAPACITY>,
+    /// Whether events have been dropped since the last
+    /// [`Event::EventQueueOverflow`]
+    pub(crate) events_overflowed: bool,
+    /// PDP contexts known to be active
+    pub(crate) active_contexts: Vec<ContextId, MAX_CONTEXTS>,
+    /// Secondary PDP contexts deactivated by the network, to be reactivated
+    dropped_contexts: Vec<ContextId, MAX_CONTEXTS>,
+    /// Last +CUSD URC, until retrieved by a USSD request
+    pub(crate) ussd_response:
+        Option<supplementary_services::urc::UnstructuredSupplementaryServiceData>,
+    /// Last +UFOTASTAT URC of an ongoing FOTA download, `None` if no download
+    /// has been started
+    pub(crate) fota_status: Option<system_features::urc::FotaStatus>,
+    /// Last +UUHTTPCR URC, until retrieved by an HTTP request
+    pub(crate) http_result: Option<http::urc::HttpResponse>,
+    /// Last +UUMQTTC URC, until retrieved by an MQTT command
+    pub(crate) mqtt_result: Option<mqtt::urc::MqttCommandResult>,
+    /// Last +UUFTPCR URC, until retrieved by an FTP command
+    pub(crate) ftp_result: Option<ftp::urc::FtpCommandResult>,
+    /// Sensor and deadline of the pending +ULOC request
+    pub(crate) location_request: Option<(LocationSensor, Instant)>,
+    /// Position of the completed CellLocate request, until retrieved
+    pub(crate) location: Option<Position>,
+    /// Time to try registering, before the module is reset and registration
+    /// attempted again
+    registration_timeout: Duration,
+    /// Number of registration attempts after the first one, before giving
+    /// up, `None` to retry indefinitely
+    max_attach_retries: Option<u8>,
+    /// Registration attempts that timed out since the last registration
+    pub(crate) attach_retries: u8,
+    /// Backoff after the first failed registration attempt, and its maximum
+    backoff_base: Duration,
+    backoff_max: Duration,
+    /// Backoff waited before the current registration attempt
+    pub(crate) backoff: Duration,
+    /// Time until which the next registration attempt is postponed
+    pub(crate) retry_at: Option<Instant>,
+    /// State of the backoff jitter generator, 0 while jitter is not seeded
+    pub(crate) jitter_state: u32,
+    /// Whether registering on a roaming network is allowed
+    roaming_allowed: bool,
+    /// Whether the module accepted the +CGEREP reporting mode
+    pub(crate) packet_event_reporting: bool,
+    /// Whether the packet domain was detached with
+    /// [`detach`](crate::GsmClient::detach), so it is not attached
+    /// automatically
+    pub(crate) detached: bool,
+    /// Whether the packet domain was attached (+CGATT) when last queried
+    pub(crate) attached: bool,
+    /// Whether [`CellularConfig::RAT_FALLBACK`](crate::CellularConfig::RAT_FALLBACK)
+    /// is selected instead of the configured RAT
+    pub(crate) rat_fallback: bool,
+    /// Whether a SIM is inserted, as far as known
+    pub(crate) sim_present: bool,
+    /// Time the SIM was last polled for, while it is removed
+    pub(crate) sim_check_time: Option<Instant>,
+    /// Whether the greeting text has been received since the module was last
+    /// set up
+    pub(crate) rebooted: bool,
+    /// Primary and secondary DNS servers configured on the PSD profile, see
+    /// [`set_dns_servers`](crate::GsmClient::set_dns_servers)
+    pub(crate) dns_servers: Option<(IpAddr, Option<IpAddr>)>,
+    /// Whether the module last reported entering PSM (+UUPSMR)
+    pub(crate) psm_entered: bool,
+    /// Consecutive failures since the module was last connected, and the
+    /// highest recovery level reached for them
+    pub(crate) recovery_failures: u16,
+    pub(crate) recovery_level: RecoveryLevel,
+    /// Current HEX mode of the module (+UDCONF=1), which is switched to the
+    /// mode of each socket as it is read from
+    pub(crate) hex_mode: bool,
+    /// Sockets using HEX mode, as a bitmask of their handles
+    pub(crate) hex_sockets: u8,
+    /// Operator registered on, and whether it has been recorded since the
+    /// module was last initialized
+    pub(crate) last_registration: Option<LastRegistration>,
+    pub(crate) registration_recorded: bool,
+    /// Registration the module is seeded with, see
+    /// [`restore_registration`](crate::GsmClient::restore_registration), and
+    /// the band mask replaced by its band
+    pub(crate) registration_hint: Option<LastRegistration>,
+    pub(crate) hint_bands: Option<(BandMaskRat, BandMask)>,
 }
 
 impl<'sub, AtCl> Network<'sub, AtCl>
 where
     AtCl: AtatClient,
 {
-    pub(crate) fn new(at_tx: AtTx<'sub, AtCl>) -> Self {
+    pub(crate) fn new(
+        at_tx: AtTx<'sub, AtCl>,
+        registration_timeout: Duration,
+        max_attach_retries: Option<u8>,
+        backoff_base: Duration,
+        backoff_max: Duration,
+        roaming_allowed: bool,
+    ) -> Self {
         Self {
             status: RegistrationState::new(),
             context_state: ContextState::Setup,
             at_tx,
+            events: Deque::new(),
+            events_overflowed: false,
+            active_contexts: Vec::new(),
+            dropped_contexts: Vec::new(),
+            ussd_response: None,
+            fota_status: None,
+            http_result: None,
+            mqtt_result: None,
+            ftp_result: None,
+            location_request: None,
+            location: None,
+            registration_timeout,
+            max_attach_retries,
+            attach_retries: 0,
+            backoff_base,
+            backoff_max,
+            backoff: Duration::from_ticks(0),
+            retry_at: None,
+            jitter_state: 0,
+            roaming_allowed,
+            packet_event_reporting: false,
+            detached: false,
+            attached: false,
+            rat_fallback: false,
+            sim_present: true,
+            sim_check_time: None,
+            rebooted: false,
+            dns_servers: None,
+            psm_entered: false,
+            recovery_failures: 0,
+            recovery_level: RecoveryLevel::Retry,
+            hex_mode: false,
+            hex_sockets: 0,
+            last_registration: None,
+            registration_recorded: false,
+            registration_hint: None,
+            hint_bands: None,
         }
     }
 
@@ -163,6 +526,7 @@ where
 
         self.handle_urc().ok(); // Ignore errors
         self.check_registration_state();
+        self.reactivate_dropped_contexts();
         self.intervene_registration()?;
         self.check_running_imsi().ok(); // Ignore errors
 
@@ -183,6 +547,7 @@ where
         self.status.reg_check_time.replace(now);
 
         self.update_registration()?;
+        self.reject_roaming()?;
 
         let now = Instant::now();
         let is_timeout = self
@@ -190,18 +555,72 @@ where
             .reg_start_time
             .and_then(|reg_start_time| {
                 now.checked_duration_since(reg_start_time)
-                    .map(|dur| dur >= REGISTRATION_TIMEOUT)
+                    .map(|dur| dur >= self.registration_timeout)
             })
             .unwrap_or(false);
 
         if self.status.conn_state == ConnectionState::Connecting && is_timeout {
+            if self
+                .max_attach_retries
+                .map_or(false, |max| self.attach_retries >= max)
+            {
+                error!(
+                    "Network registration failed after {} attempts",
+                    self.attach_retries.saturating_add(1)
+                );
+                // Start over if the application keeps spinning
+                self.attach_retries = 0;
+                self.reset_reg_time()?;
+                if self.status.denied() {
+                    return Err(Error::RegistrationDenied {
+                        cause: self.status.reject_cause.unwrap_or(0),
+                    });
+                }
+                return Err(Error::RegistrationTimeout);
+            }
+
             warn!("Resetting the modem due to the network registration timeout");
+            self.attach_retries = self.attach_retries.saturating_add(1);
+            self.postpone_retry();
 
             return Err(Error::Generic(GenericError::Timeout));
         }
         Ok(())
     }
 
+    /// Postpone the next registration attempt by the backoff of the failed
+    /// attempts so far
+    fn postpone_retry(&mut self) {
+        let jitter = (self.jitter_state != 0).then(|| {
+            self.jitter_state = xorshift32(self.jitter_state);
+            self.jitter_state
+        });
+        self.backoff = backoff(
+            self.backoff_base,
+            self.backoff_max,
+            self.attach_retries,
+            jitter,
+        );
+        self.retry_at = Some(Instant::now() + self.backoff);
+        info!("Retrying registration in {} ms", self.backoff.as_millis());
+    }
+
+    /// Whether the next registration attempt is postponed by the backoff
+    pub(crate) fn backing_off(&mut self) -> bool {
+        match self.retry_at {
+            Some(retry_at) if Instant::now() < retry_at => true,
+            _ => {
+                self.retry_at = None;
+                false
+            }
+        }
+    }
+
+    /// Seed the backoff jitter from the IMEI, so it differs between devices
+    pub(crate) fn seed_jitter(&mut self, imei: u64) {
+        self.jitter_state = (imei ^ (imei >> 32)) as u32 | 1;
+    }
+
     pub fn check_running_imsi(&mut self) -> Result<(), Error> {
         // Check current IMSI if registered successfully in which case
         // imsi_check_time will be `None`, else if not registered, check after
@@ -248,6 +667,8 @@ where
             || self.status.eps.registered()
         {
             self.status.set_connection_state(ConnectionState::Connected);
+            self.attach_retries = 0;
+            self.backoff = Duration::from_ticks(0);
         } else if self.status.conn_state == ConnectionState::Connected {
             // FIXME: potentially go back into connecting state only when getting into
             // a 'sticky' non-registered state
@@ -257,6 +678,35 @@ where
         }
     }
 
+    /// Deregister from the network if only registered on a roaming network,
+    /// while roaming is not allowed
+    ///
+    /// Registration is tried again on the next PLMN reselection, see
+    /// [`intervene_registration`](Network::intervene_registration).
+    fn reject_roaming(&mut self) -> Result<(), Error> {
+        if self.roaming_allowed || self.status.status() != registration::Status::Roaming {
+            return Ok(());
+        }
+
+        warn!("Registered on a roaming network, deregistering");
+        self.send_internal(
+            &SetOperatorSelection {
+                mode: OperatorSelectionMode::Deregister,
+                format: Some(2),
+                oper: None,
+                act: None,
+            },
+            false,
+        )?;
+        self.update_registration()?;
+
+        if self.status.conn_state == ConnectionState::Connected {
+            self.status
+                .set_connection_state(ConnectionState::Connecting);
+        }
+        Err(Error::RoamingNotAllowed)
+    }
+
     pub fn intervene_registration(&mut self) -> Result<(), Error> {
         if self.status.conn_state != ConnectionState::Connecting {
             return Ok(());
@@ -286,6 +736,8 @@ where
                     &SetOperatorSelection {
                         mode: OperatorSelectionMode::Automatic,
                         format: Some(2),
+                        oper: None,
+                        act: None,
                     },
                     false,
                 )
@@ -402,6 +854,8 @@ where
                     &SetOperatorSelection {
                         mode: OperatorSelectionMode::Automatic,
                         format: Some(2),
+                        oper: None,
+                        act: None,
                     },
                     true,
                 )?;
@@ -412,7 +866,16 @@ where
     }
 
     pub fn update_registration(&mut self) -> Result<(), Error> {
-        self.send_internal(&GetExtendedErrorReport, false).ok();
+        // Failure cause of the last CS or PS procedure, e.g. a rejected
+        // location update. The report is kept after registering, so a cause
+        // already reported while registered is of an earlier failure.
+        let ceer_cause = self
+            .send_internal(&GetExtendedErrorReport, false)
+            .ok()
+            .and_then(|report| u8::try_from(report.cause).ok())
+            .filter(|&cause| cause != 0);
+        let mut reject_cause =
+            ceer_cause.filter(|&cause| Some(cause) != self.status.stale_ceer_cause);
 
         if let Ok(reg) = self.send_internal(&GetNetworkRegistrationStatus, false) {
             self.status.compare_and_set(reg.into());
@@ -423,36 +886,91 @@ where
         }
 
         if let Ok(reg) = self.send_internal(&GetEPSNetworkRegistrationStatus, false) {
+            if reg.stat == EPSNetworkRegistrationStat::RegistrationDenied {
+                if let Some(cause) = self.eps_reject_cause() {
+                    reject_cause = Some(cause);
+                }
+            }
             self.status.compare_and_set(reg.into());
         }
 
+        self.status.update_reject_cause(reject_cause);
+        if self.status.registered() {
+            self.status.stale_ceer_cause = ceer_cause;
+        }
+
         Ok(())
     }
 
-    pub(crate) fn handle_urc(&mut self) -> Result<(), Error> {
+    /// EMM cause of a denied EPS registration, read with +CEREG=3
+    ///
+    /// `atat` cannot tell +CEREG URCs from the response to +CEREG?, so the
+    /// URCs stay disabled and the cause is only enabled for the read.
+    fn eps_reject_cause(&mut self) -> Option<u8> {
+        self.send_internal(
+            &SetEPSNetworkRegistrationStatus {
+                n: EPSNetworkRegistrationUrcConfig::UrcVerboseCause,
+            },
+            false,
+        )
+        .ok()?;
+        let reg = self.send_internal(&GetEPSNetworkRegistrationStatus, false);
+        self.send_internal(
+            &SetEPSNetworkRegistrationStatus {
+                n: EPSNetworkRegistrationUrcConfig::UrcDisabled,
+            },
+            false,
+        )
+        .ok();
+
+        reg.ok()
+            .filter(|reg| reg.cause_type == Some(0))
+            .and_then(|reg| reg.reject_cause)
+    }
+
+    /// Handle the next pending URC, returning whether there was one
+    pub(crate) fn handle_urc(&mut self) -> Result<bool, Error> {
         // TODO: How to do this cleaner?
         let mut ctx_state = self.context_state;
         // let mut new_reg_params: Option<RegistrationParams> = None;
+        let mut event = None;
+        let mut deactivated = None;
+        let mut ussd_response = None;
+        let mut fota_status = None;
+        let mut http_result = None;
+        let mut mqtt_result = None;
+        let mut mqtt_unread = 0;
+        let mut ftp_result = None;
+        let mut location = None;
+        let mut sim_state = None;
+        let mut rebooted = false;
+        let mut psm_state = None;
 
-        self.at_tx.handle_urc(|urc| {
+        let handled = self.at_tx.handle_urc(|urc| {
             match urc {
                 Urc::NetworkDetach => {
                     warn!("Network Detach URC!");
+                    event = Some(Event::PacketDomain(PacketDomainEvent::NetworkDetach));
                 }
                 Urc::MobileStationDetach => {
                     warn!("ME Detach URC!");
+                    event = Some(Event::PacketDomain(PacketDomainEvent::ModuleDetach));
                 }
                 Urc::NetworkDeactivate => {
                     warn!("Network Deactivate URC!");
+                    event = Some(Event::PacketDomain(PacketDomainEvent::NetworkDeactivate));
                 }
                 Urc::MobileStationDeactivate => {
                     warn!("ME Deactivate URC!");
+                    event = Some(Event::PacketDomain(PacketDomainEvent::ModuleDeactivate));
                 }
                 Urc::NetworkPDNDeactivate => {
                     warn!("Network PDN Deactivate URC!");
+                    event = Some(Event::PacketDomain(PacketDomainEvent::NetworkPdnDeactivate));
                 }
                 Urc::MobileStationPDNDeactivate => {
                     warn!("ME PDN Deactivate URC!");
+                    event = Some(Event::PacketDomain(PacketDomainEvent::ModulePdnDeactivate));
                 }
                 Urc::ExtendedPSNetworkRegistration(psn::urc::ExtendedPSNetworkRegistration {
                     state,
@@ -486,13 +1004,95 @@ where
                     profile_id,
                 }) => {
                     info!("[URC] DataConnectionDeactivated {:?}", profile_id);
-                    if profile_id == PROFILE_ID {
-                        ctx_state = ContextState::Activating;
+                    // PSD profiles are mapped to the context of the same id
+                    deactivated = Some(ContextId(profile_id.0));
+                }
+                Urc::ModuleRebooted => {
+                    info!("[URC] ModuleRebooted");
+                    rebooted = true;
+                }
+                Urc::TimeZone(mobile_control::urc::TimeZone { tz }) => {
+                    info!("[URC] TimeZone {}", tz.as_str());
+                    if let Some(offset) = parse_time_zone(&tz) {
+                        event = Some(Event::TimeZoneChanged { offset, dst: None });
                     }
                 }
+                Urc::ExtendedTimeZone(mobile_control::urc::ExtendedTimeZone { tz, dst }) => {
+                    info!("[URC] ExtendedTimeZone {} {}", tz.as_str(), dst);
+                    if let Some(offset) = parse_time_zone(&tz) {
+                        event = Some(Event::TimeZoneChanged {
+                            offset,
+                            dst: Some(dst),
+                        });
+                    }
+                }
+                Urc::PsmStatus(system_features::urc::PsmStatus { state }) => {
+                    info!("[URC] PsmStatus {:?}", state);
+                    psm_state = Some(state);
+                }
+                Urc::SimStatus(device_lock::urc::SimStatus { state }) => {
+                    info!("[URC] SimStatus {:?}", state);
+                    sim_state = Some(state);
+                }
                 Urc::MessageWaitingIndication(_) => {
                     info!("[URC] MessageWaitingIndication");
                 }
+                Urc::NewMessageIndication(sms::urc::NewMessageIndication { mem, index }) => {
+                    info!("[URC] NewMessageIndication {} {}", mem.as_str(), index);
+                    event = Some(Event::SmsReceived {
+                        storage: MessageStorage::from(mem.as_str()),
+                        index,
+                    });
+                }
+                Urc::Ussd(data) => {
+                    info!("[URC] Ussd {:?}", data.m);
+                    ussd_response = Some(data);
+                }
+                Urc::FotaStatus(status) => {
+                    info!("[URC] FotaStatus {:?} {}", status.state, status.value);
+                    if status.state == system_features::types::FotaState::Downloading {
+                        event = Some(Event::FotaProgress {
+                            percent: status.value.min(100) as u8,
+                        });
+                    }
+                    fota_status = Some(status);
+                }
+                Urc::HttpResponse(result) => {
+                    info!(
+                        "[URC] HttpResponse {} {} {}",
+                        result.profile_id, result.http_command, result.http_result
+                    );
+                    http_result = Some(result);
+                }
+                Urc::MqttCommandResult(result) => {
+                    info!(
+                        "[URC] MqttCommandResult {} {}",
+                        result.op_code, result.result
+                    );
+                    if result.op_code == MQTT_LOGOUT {
+                        event = Some(Event::MqttDisconnected);
+                    }
+                    mqtt_result = Some(result);
+                }
+                Urc::Location(data) => {
+                    info!("[URC] Location");
+                    location = Some(data);
+                }
+                Urc::FtpCommandResult(result) => {
+                    info!(
+                        "[URC] FtpCommandResult {:?} {}",
+                        result.op_code, result.ftp_result
+                    );
+                    event = Some(Event::FtpTransferComplete {
+                        op: result.op_code,
+                        success: result.ftp_result == 1,
+                    });
+                    ftp_result = Some(result);
+                }
+                Urc::MqttUnreadMessages(mqtt::urc::MqttUnreadMessages { unread, .. }) => {
+                    info!("[URC] MqttUnreadMessages {}", unread);
+                    mqtt_unread = unread;
+                }
                 _ => return false,
             };
             true
@@ -503,9 +1103,305 @@ where
         // }
 
         self.context_state = ctx_state;
+        if ussd_response.is_some() {
+            self.ussd_response = ussd_response;
+        }
+        if fota_status.is_some() {
+            self.fota_status = fota_status;
+        }
+        if http_result.is_some() {
+            self.http_result = http_result;
+        }
+        if mqtt_result.is_some() {
+            self.mqtt_result = mqtt_result;
+        }
+        if ftp_result.is_some() {
+            self.ftp_result = ftp_result;
+        }
+        if let Some(location) = location {
+            self.location_received(&location);
+        }
+        if let Some(event) = event {
+            self.push_event(event);
+        }
+        if rebooted {
+            self.rebooted = true;
+        }
+        match psm_state {
+            Some(PsmState::Entered) => {
+                self.psm_entered = true;
+                self.push_event(Event::PsmEntered);
+            }
+            Some(PsmState::Exited) => {
+                self.psm_entered = false;
+                self.push_event(Event::PsmExited);
+            }
+            _ => {}
+        }
+        if let Some(state) = sim_state {
+            self.sim_status_changed(state != SimState::NotPresent);
+        }
+        if let Some(cid) = deactivated {
+            self.context_deactivated(cid);
+        }
+        if mqtt_unread > 0 {
+            self.read_mqtt_messages(mqtt_unread);
+        }
+        Ok(handled)
+    }
+
+    /// Track whether a SIM is inserted, raising [`Event::SimRemoved`] or
+    /// [`Event::SimInserted`] on changes
+    pub(crate) fn sim_status_changed(&mut self, present: bool) {
+        if present == self.sim_present {
+            return;
+        }
+
+        self.sim_present = present;
+        if present {
+            info!("SIM inserted");
+            self.push_event(Event::SimInserted);
+        } else {
+            warn!("SIM removed");
+            self.sim_check_time = None;
+            self.push_event(Event::SimRemoved);
+        }
+    }
+
+    /// Handle the +UULOC URC, of the pending +ULOC request if any
+    fn location_received(&mut self, location: &gnss::urc::Location) {
+        let sensor = self.location_request.map(|(sensor, _)| sensor);
+
+        let fix_quality = match sensor {
+            Some(LocationSensor::CellLocate) => FixQuality::CellLocate,
+            // Locations without a pending request are GNSS fixes
+            Some(LocationSensor::Gnss | LocationSensor::Hybrid) | None => FixQuality::Gps,
+        };
+        let Some(position) = Position::from_location(location, fix_quality) else {
+            warn!("Invalid location");
+            return;
+        };
+
+        if sensor == Some(LocationSensor::CellLocate) {
+            // Retrieved by `cell_locate`
+            self.location = Some(position);
+        } else {
+            self.location_request = None;
+            self.push_event(Event::GnssFix(position));
+        }
+    }
+
+    /// Read `unread` received MQTT messages into [`Event::MqttMessage`]s
+    fn read_mqtt_messages(&mut self, unread: u16) {
+        for _ in 0..unread {
+            let message = match self.send_internal(&mqtt::MqttReadMessage, false) {
+                Ok(message) => message,
+                Err(e) => {
+                    warn!("Failed to read MQTT message: {:?}", e);
+                    return;
+                }
+            };
+
+            let data = &message.message[..];
+            let data = data
+                .strip_prefix(b"\"")
+                .and_then(|d| d.strip_suffix(b"\""))
+                .unwrap_or(data);
+
+            let mut payload = Vec::new();
+            if payload.extend_from_slice(data).is_err() {
+                warn!("MQTT message too long, dropping it");
+                continue;
+            }
+
+            self.push_event(Event::MqttMessage {
+                topic: message.topic,
+                payload,
+            });
+        }
+    }
+
+    pub(crate) fn context_activated(&mut self, cid: ContextId) {
+        if !self.active_contexts.contains(&cid) {
+            self.active_contexts.push(cid).ok();
+        }
+    }
+
+    /// Mark the data connection up on the primary context `cid`, raising
+    /// [`Event::Connected`]
+    pub(crate) fn data_connected(&mut self, cid: ContextId) {
+        self.context_state = ContextState::Active;
+        self.context_activated(cid);
+
+        let ip = match self.send_internal(&GetPDPContextAddress { cid }, false) {
+            Ok(address) => address.addresses().next(),
+            Err(e) => {
+                warn!("Failed to get the address of context {}: {:?}", cid.0, e);
+                None
+            }
+        };
+        self.push_event(Event::Connected {
+            cid,
+            ip: ip.unwrap_or(IpAddr::V4(Ipv4Addr::unspecified())),
+        });
+    }
+
+    /// Mark the data connection lost, to be set up again, raising
+    /// [`Event::NetworkLost`] if it was up
+    pub(crate) fn data_connection_lost(&mut self) {
+        if self.context_state == ContextState::Active {
+            self.context_state = ContextState::Activating;
+            self.push_event(Event::NetworkLost);
+        }
+    }
+
+    /// Handle the deactivation of the PDP context `cid`
+    ///
+    /// Only the primary data context requires the data connection to be set
+    /// up again. Secondary contexts are reactivated on their own, without
+    /// affecting the connection state.
+    fn context_deactivated(&mut self, cid: ContextId) {
+        self.active_contexts.retain(|c| *c != cid);
+        self.push_event(Event::Disconnected(cid));
+
+        if cid == CONTEXT_ID {
+            self.data_connection_lost();
+        } else if !self.dropped_contexts.contains(&cid) {
+            self.dropped_contexts.push(cid).ok();
+        }
+    }
+
+    /// Forget the secondary PDP context `cid`, deactivated on request, so it is
+    /// not reactivated
+    pub(crate) fn context_released(&mut self, cid: ContextId) {
+        self.active_contexts.retain(|c| *c != cid);
+        self.dropped_contexts.retain(|c| *c != cid);
+    }
+
+    /// Configure the DNS servers of
+    /// [`set_dns_servers`](crate::GsmClient::set_dns_servers), if any, on the
+    /// PSD profile `profile_id` (+UPSD)
+    pub(crate) fn configure_dns_servers(&mut self, profile_id: ProfileId) -> Result<(), Error> {
+        let Some((primary, secondary)) = self.dns_servers else {
+            return Ok(());
+        };
+
+        self.send_internal(
+            &psn::SetPacketSwitchedConfig {
+                profile_id,
+                param: psn::types::PacketSwitchedParam::DNS1(primary),
+            },
+            true,
+        )?;
+        // "0.0.0.0" clears a previously configured secondary server
+        self.send_internal(
+            &psn::SetPacketSwitchedConfig {
+                profile_id,
+                param: psn::types::PacketSwitchedParam::DNS2(
+                    secondary.unwrap_or(Ipv4Addr::unspecified().into()),
+                ),
+            },
+            true,
+        )?;
         Ok(())
     }
 
+    /// Last error of the socket `handle` (+USOCTL)
+    pub(crate) fn socket_last_error(&mut self, handle: SocketHandle) -> Result<u16, Error> {
+        let SocketControlResponse { param_val, .. } = self.send_internal(
+            &SocketControl {
+                socket: handle,
+                param_id: SocketControlParam::LastSocketError,
+            },
+            false,
+        )?;
+        Ok(u16::try_from(param_val).unwrap_or(u16::MAX))
+    }
+
+    /// Whether the socket `handle` uses HEX mode
+    pub(crate) fn socket_hex_mode(&self, handle: SocketHandle) -> bool {
+        self.hex_sockets & Self::socket_bit(handle) != 0
+    }
+
+    /// Set whether the socket `handle` uses HEX mode, e.g. when it is created
+    pub(crate) fn set_socket_hex_mode(&mut self, handle: SocketHandle, hex_mode: bool) {
+        if hex_mode {
+            self.hex_sockets |= Self::socket_bit(handle);
+        } else {
+            self.hex_sockets &= !Self::socket_bit(handle);
+        }
+    }
+
+    /// Switch the module to the HEX mode of the socket `handle`, if it is not
+    /// in that mode already, returning whether HEX mode is used
+    pub(crate) fn select_hex_mode(&mut self, handle: SocketHandle) -> Result<bool, Error> {
+        let hex_mode = self.socket_hex_mode(handle);
+        if hex_mode != self.hex_mode {
+            self.send_internal(
+                &SetHexMode {
+                    hex_mode_disable: if hex_mode {
+                        HexMode::Enabled
+                    } else {
+                        HexMode::Disabled
+                    },
+                },
+                false,
+            )?;
+            self.hex_mode = hex_mode;
+        }
+        Ok(hex_mode)
+    }
+
+    const fn socket_bit(handle: SocketHandle) -> u8 {
+        match 1u8.checked_shl(handle.0 as u32) {
+            Some(bit) => bit,
+            None => 0,
+        }
+    }
+
+    /// Try to reactivate secondary PDP contexts deactivated by the network
+    fn reactivate_dropped_contexts(&mut self) {
+        if self.status.conn_state != ConnectionState::Connected {
+            return;
+        }
+
+        while let Some(cid) = self.dropped_contexts.pop() {
+            match self.send_internal(
+                &SetPDPContextState {
+                    status: PDPContextStatus::Activated,
+                    cid: Some(cid),
+                },
+                false,
+            ) {
+                Ok(_) => self.context_activated(cid),
+                Err(e) => {
+                    warn!("Failed to reactivate context {}: {:?}", cid.0, e);
+                    self.dropped_contexts.push(cid).ok();
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Check whether the PDP context `cid` is active (+CGACT?)
+    pub(crate) fn pdp_context_active(&mut self, cid: ContextId) -> Result<bool, Error> {
+        let context_states = self.send_internal(&GetPDPContextState, true)?;
+
+        Ok(context_states
+            .iter()
+            .any(|state| state.cid == cid && state.status == PDPContextStatus::Activated))
+    }
+
+    /// Buffer `event`, dropping the oldest event if the buffer is full
+    pub(crate) fn push_event(&mut self, event: Event) {
+        if self.events.is_full() {
+            warn!("Event buffer full, dropping oldest event");
+            self.events.pop_front();
+            self.events_overflowed = true;
+        }
+        self.events.push_back(event).ok();
+    }
+
     pub(crate) fn send_internal<A, const LEN: usize>(
         &mut self,
         req: &A,
@@ -522,4 +1418,165 @@ where
 
         self.at_tx.send(req)
     }
+
+    /// Send `req` as a single attempt, waiting up to `TIMEOUT_MS` for the
+    /// response rather than the timeout of the command itself
+    ///
+    /// A slow response is not retried, to avoid multiplying long timeouts.
+    pub(crate) fn send_internal_timeout<A, const LEN: usize, const TIMEOUT_MS: u32>(
+        &mut self,
+        req: &A,
+        check_urc: bool,
+        _timeout: TimeoutMs<TIMEOUT_MS>,
+    ) -> Result<A::Response, Error>
+    where
+        A: atat::AtatCmd<LEN>,
+    {
+        if check_urc {
+            if let Err(e) = self.handle_urc() {
+                error!("Failed handle URC {:?}", &e);
+            }
+        }
+
+        self.at_tx.send_once(&WithTimeout::<A, TIMEOUT_MS>(req))
+    }
+}
+
+/// Response timeout of a long running command, see
+/// [`Network::send_internal_timeout`]
+///
+/// atat takes the response timeout from [`atat::AtatCmd::MAX_TIMEOUT_MS`], so
+/// the timeout is given as a const generic rather than a [`Duration`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TimeoutMs<const MS: u32>;
+
+/// Command wrapper overriding the response timeout of `A` with `TIMEOUT_MS`,
+/// limited to a single attempt
+struct WithTimeout<'a, A, const TIMEOUT_MS: u32>(&'a A);
+
+impl<'a, A, const LEN: usize, const TIMEOUT_MS: u32> atat::AtatCmd<LEN>
+    for WithTimeout<'a, A, TIMEOUT_MS>
+where
+    A: atat::AtatCmd<LEN>,
+{
+    type Response = A::Response;
+
+    const MAX_TIMEOUT_MS: u32 = TIMEOUT_MS;
+    const ATTEMPTS: u8 = 1;
+    const CAN_ABORT: bool = A::CAN_ABORT;
+    const EXPECTS_RESPONSE_CODE: bool = A::EXPECTS_RESPONSE_CODE;
+
+    fn as_bytes(&self) -> Vec<u8, LEN> {
+        self.0.as_bytes()
+    }
+
+    fn parse(
+        &self,
+        resp: Result<&[u8], atat::InternalError>,
+    ) -> Result<Self::Response, atat::Error> {
+        self.0.parse(resp)
+    }
+}
+
+/// Backoff before registration is attempted again, after `attempts` failed
+/// attempts, doubling from `base` up to `max`
+///
+/// With a random `jitter`, the backoff is spread over its upper half.
+fn backoff(base: Duration, max: Duration, attempts: u8, jitter: Option<u32>) -> Duration {
+    let doublings = u32::from(attempts.saturating_sub(1)).min(32);
+    let ms = base
+        .as_millis()
+        .saturating_mul(1 << doublings)
+        .min(max.as_millis());
+
+    match jitter {
+        Some(random) => Duration::from_millis(ms / 2 + u64::from(random) % (ms / 2 + 1)),
+        None => Duration::from_millis(ms),
+    }
+}
+
+/// Parse the <tz> of +CTZV / +CTZE, in quarters of an hour from -96 to +96
+fn parse_time_zone(tz: &str) -> Option<i8> {
+    tz.trim_matches('"')
+        .parse::<i8>()
+        .ok()
+        .filter(|quarters| (-96..=96).contains(quarters))
+}
+
+/// Next value of the xorshift32 generator
+fn xorshift32(mut x: u32) -> u32 {
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    x
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UbloxCellularUrcChannel;
+    use atat::AtatUrcChannel;
+
+    /// Client counting the commands sent, none of which are answered
+    #[derive(Default)]
+    struct TimeoutClient {
+        sent: u8,
+    }
+
+    impl AtatClient for TimeoutClient {
+        fn send<A: atat::AtatCmd<LEN>, const LEN: usize>(
+            &mut self,
+            _cmd: &A,
+        ) -> Result<A::Response, atat::Error> {
+            self.sent += 1;
+            Err(atat::Error::Timeout)
+        }
+    }
+
+    #[test]
+    fn at_retries_attempts() {
+        let channel = UbloxCellularUrcChannel::new();
+        for retries in [1, 3, 5] {
+            let subscription = AtatUrcChannel::subscribe(&channel).unwrap();
+            let mut tx = AtTx::new(TimeoutClient::default(), subscription, retries);
+
+            assert!(matches!(tx.send(&AT), Err(Error::AT(atat::Error::Timeout))));
+            assert_eq!(tx.client.sent, retries);
+            assert_eq!(tx.consecutive_timeouts, retries);
+        }
+
+        // Long running commands are attempted once
+        let subscription = AtatUrcChannel::subscribe(&channel).unwrap();
+        let mut tx = AtTx::new(TimeoutClient::default(), subscription, 5);
+        tx.send(&WithTimeout::<_, 60_000>(&AT)).ok();
+        assert_eq!(tx.client.sent, 1);
+        tx.send_once(&AT).ok();
+        assert_eq!(tx.client.sent, 2);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let base = Duration::from_secs(10);
+        let max = Duration::from_secs(60);
+
+        assert_eq!(backoff(base, max, 1, None), Duration::from_secs(10));
+        assert_eq!(backoff(base, max, 2, None), Duration::from_secs(20));
+        assert_eq!(backoff(base, max, 3, None), Duration::from_secs(40));
+        assert_eq!(backoff(base, max, 4, None), max);
+        assert_eq!(backoff(base, max, u8::MAX, None), max);
+
+        for random in [0, 1, 12345, u32::MAX] {
+            let jittered = backoff(base, max, 2, Some(random));
+            let range = Duration::from_secs(10)..=Duration::from_secs(20);
+            assert!(range.contains(&jittered));
+        }
+    }
+
+    #[test]
+    fn time_zone() {
+        assert_eq!(parse_time_zone("+04"), Some(4));
+        assert_eq!(parse_time_zone("\"-20\""), Some(-20));
+        assert_eq!(parse_time_zone("+97"), None);
+        assert_eq!(parse_time_zone(""), None);
+    }
 }
@@ -0,0 +1,230 @@
+use atat::blocking::AtatClient;
+use core::fmt::Write;
+use heapless::{String, Vec};
+
+use crate::{
+    client::Device,
+    command::network_service::{
+        responses::{OperatorList, OperatorSelection, MAX_SCANNED_OPERATORS},
+        types::{OperatorNameFormat, OperatorSelectionMode, OperatorStatus, RatAct},
+        GetOperatorList, GetOperatorSelection, SetOperatorSelection,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+    network::{Error as NetworkError, TimeoutMs},
+    registration::Status,
+};
+
+/// Operator selection and scanning can take up to three minutes
+pub(crate) const OPERATOR_SELECTION_TIMEOUT: TimeoutMs<180_000> = TimeoutMs;
+
+/// Public Land Mobile Network (PLMN) identity, consisting of the Mobile
+/// Country Code (MCC) and Mobile Network Code (MNC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Plmn {
+    pub mcc: u16,
+    pub mnc: u16,
+    /// Whether the MNC is 3 digits long (e.g. "310410"), rather than 2 digits
+    /// long (e.g. "24001")
+    pub three_digit_mnc: bool,
+}
+
+impl Plmn {
+    /// PLMN with a 2 digit MNC
+    pub const fn new(mcc: u16, mnc: u16) -> Self {
+        Self {
+            mcc,
+            mnc,
+            three_digit_mnc: false,
+        }
+    }
+
+    /// Parse a PLMN from its numeric representation, e.g. "24001" or "310410"
+    pub fn from_numeric(numeric: &str) -> Option<Self> {
+        if !matches!(numeric.len(), 5 | 6) || !numeric.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        Some(Self {
+            mcc: numeric[..3].parse().ok()?,
+            mnc: numeric[3..].parse().ok()?,
+            three_digit_mnc: numeric.len() == 6,
+        })
+    }
+
+    /// Numeric representation of the PLMN, as used by +COPS
+    pub fn to_numeric(&self) -> String<6> {
+        let mut numeric = String::new();
+        if self.three_digit_mnc {
+            write!(numeric, "{:03}{:03}", self.mcc, self.mnc).ok();
+        } else {
+            write!(numeric, "{:03}{:02}", self.mcc, self.mnc).ok();
+        }
+        numeric
+    }
+}
+
+/// Network operator the module is currently registered to
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Operator {
+    /// Not registered to any operator, e.g. while searching
+    None,
+    Registered {
+        /// Long alphanumeric operator name
+        name: Option<String<24>>,
+        plmn: Option<Plmn>,
+        /// Access technology in use
+        act: Option<RatAct>,
+    },
+}
+
+/// Network operator found by [`scan_operators`](Device::scan_operators)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OperatorScanResult {
+    /// Long alphanumeric operator name
+    pub name: String<24>,
+    pub plmn: Option<Plmn>,
+    pub status: OperatorStatus,
+    pub act: Option<RatAct>,
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Get the network operator the module is currently registered to
+    ///
+    /// Returns [`Operator::None`] if the module is not registered.
+    pub fn operator(&mut self) -> Result<Operator, Error> {
+        let OperatorSelection { oper: long, .. } = self.get_operator_selection(0)?;
+        // Leave the module in numeric format, as used during initialization
+        let OperatorSelection {
+            oper: numeric, act, ..
+        } = self.get_operator_selection(2)?;
+
+        let name = match long {
+            Some(OperatorNameFormat::Long(name)) => Some(name),
+            _ => None,
+        };
+        let plmn = match numeric {
+            Some(OperatorNameFormat::Numeric(numeric)) => Plmn::from_numeric(&numeric),
+            _ => None,
+        };
+
+        if name.is_none() && plmn.is_none() {
+            return Ok(Operator::None);
+        }
+
+        Ok(Operator::Registered { name, plmn, act })
+    }
+
+    /// Manually select the network operator `plmn`, optionally on the given
+    /// access technology
+    ///
+    /// Fails with [`Error::RegistrationDenied`] if the module is denied
+    /// registration, e.g. because the PLMN is forbidden. If the selection
+    /// times out, the module is returned to automatic operator selection.
+    pub fn select_operator(&mut self, plmn: Plmn, act: Option<RatAct>) -> Result<(), Error> {
+        let numeric = plmn.to_numeric();
+
+        match self.send_at_timeout(
+            &SetOperatorSelection {
+                mode: OperatorSelectionMode::Manual,
+                format: Some(2),
+                oper: Some(numeric.as_str()),
+                act,
+            },
+            OPERATOR_SELECTION_TIMEOUT,
+        ) {
+            Ok(_) => Ok(()),
+            Err(Error::Network(NetworkError::AT(atat::Error::Timeout))) => {
+                warn!("Manual operator selection timed out, reverting to automatic");
+                self.auto_operator().ok();
+                Err(Error::Generic(GenericError::Timeout))
+            }
+            Err(e) => {
+                // The error result code does not tell why the selection
+                // failed, so check if the registration was denied
+                self.network.update_registration().ok();
+                if self.network.status.csd.get_status() == Status::Denied
+                    || self.network.status.eps.get_status() == Status::Denied
+                {
+                    return Err(Error::RegistrationDenied {
+                        cause: self.network.status.reject_cause.unwrap_or(0),
+                    });
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Return to automatic network operator selection
+    pub fn auto_operator(&mut self) -> Result<(), Error> {
+        self.send_at(&SetOperatorSelection {
+            mode: OperatorSelectionMode::Automatic,
+            format: Some(2),
+            oper: None,
+            act: None,
+        })?;
+        Ok(())
+    }
+
+    /// Scan for available network operators
+    ///
+    /// The scan can take up to three minutes. At most
+    /// [`MAX_SCANNED_OPERATORS`] operators are returned.
+    pub fn scan_operators(
+        &mut self,
+    ) -> Result<Vec<OperatorScanResult, MAX_SCANNED_OPERATORS>, Error> {
+        let OperatorList { operators } =
+            self.send_at_timeout(&GetOperatorList, OPERATOR_SELECTION_TIMEOUT)?;
+
+        Ok(operators
+            .into_iter()
+            .map(|operator| OperatorScanResult {
+                name: operator.long,
+                plmn: Plmn::from_numeric(&operator.numeric),
+                status: operator.stat,
+                act: operator.act,
+            })
+            .collect())
+    }
+
+    /// Read the operator selection (+COPS?), with `<oper>` in the given
+    /// `format`
+    fn get_operator_selection(&mut self, format: u8) -> Result<OperatorSelection, Error> {
+        self.send_at(&SetOperatorSelection {
+            mode: OperatorSelectionMode::FormatOnly,
+            format: Some(format),
+            oper: None,
+            act: None,
+        })?;
+        self.send_at(&GetOperatorSelection)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plmn_numeric() {
+        let plmn = Plmn::from_numeric("24001").unwrap();
+        assert_eq!(plmn, Plmn::new(240, 1));
+        assert_eq!(plmn.to_numeric().as_str(), "24001");
+
+        let plmn = Plmn::from_numeric("310410").unwrap();
+        assert_eq!(plmn.mnc, 410);
+        assert!(plmn.three_digit_mnc);
+        assert_eq!(plmn.to_numeric().as_str(), "310410");
+
+        assert_eq!(Plmn::from_numeric("2400"), None);
+        assert_eq!(Plmn::from_numeric("2400a"), None);
+    }
+}
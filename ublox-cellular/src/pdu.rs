@@ -0,0 +1,341 @@
+//! Encoding of SMS-SUBMIT PDUs (3GPP TS 23.040), for messages sent in PDU
+//! mode
+
+use core::fmt::Write;
+use heapless::{String, Vec};
+
+/// Maximum number of parts of a concatenated message
+pub const MAX_SMS_PARTS: usize = 8;
+
+/// Maximum length of the user data of a single PDU, in octets
+const MAX_USER_DATA: usize = 140;
+
+/// Length of the concatenation user data header, including its length octet
+const CONCAT_UDH_LEN: usize = 6;
+
+/// Maximum length of the destination address, in digits
+const MAX_ADDRESS_DIGITS: usize = 20;
+
+/// Maximum length of a TPDU: first octet, message reference, destination
+/// address, protocol identifier, data coding scheme, user data length and
+/// user data
+const MAX_TPDU_LEN: usize = 3 + 2 + MAX_ADDRESS_DIGITS / 2 + 3 + MAX_USER_DATA;
+
+/// GSM 7 bit default alphabet, indexed by code. The escape to the extension
+/// table (0x1B) is never matched.
+const GSM7_BASIC: [char; 128] = [
+    '@', '£', '$', '¥', 'è', 'é', 'ù', 'ì', 'ò', 'Ç', '\n', 'Ø', 'ø', '\r', 'Å', 'å', 'Δ', '_',
+    'Φ', 'Γ', 'Λ', 'Ω', 'Π', 'Ψ', 'Σ', 'Θ', 'Ξ', '\u{1b}', 'Æ', 'æ', 'ß', 'É', ' ', '!', '"', '#',
+    '¤', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', '0', '1', '2', '3', '4', '5', '6',
+    '7', '8', '9', ':', ';', '<', '=', '>', '?', '¡', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
+    'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Ä', 'Ö',
+    'Ñ', 'Ü', '§', '¿', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'ä', 'ö', 'ñ', 'ü', 'à',
+];
+
+/// GSM 7 bit extension table, as characters and their codes following the
+/// escape
+const GSM7_EXTENSION: [(char, u8); 10] = [
+    ('\u{c}', 0x0A),
+    ('^', 0x14),
+    ('{', 0x28),
+    ('}', 0x29),
+    ('\\', 0x2F),
+    ('[', 0x3C),
+    ('~', 0x3D),
+    (']', 0x3E),
+    ('|', 0x40),
+    ('€', 0x65),
+];
+
+const GSM7_ESCAPE: u8 = 0x1B;
+
+/// Data coding scheme of the message text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    Gsm7,
+    Ucs2,
+}
+
+impl Encoding {
+    /// GSM 7 bit if all characters of `text` are in the GSM 7 bit alphabet,
+    /// UCS2 otherwise
+    pub(crate) fn of(text: &str) -> Self {
+        if text.chars().all(|c| gsm7_septets(c).is_some()) {
+            Self::Gsm7
+        } else {
+            Self::Ucs2
+        }
+    }
+
+    /// TP-DCS of the encoding
+    fn dcs(self) -> u8 {
+        match self {
+            Self::Gsm7 => 0x00,
+            Self::Ucs2 => 0x08,
+        }
+    }
+
+    /// Length of `c`, in septets or UTF-16 code units
+    fn units(self, c: char) -> usize {
+        match self {
+            Self::Gsm7 => gsm7_septets(c).map_or(1, |(_, len)| len),
+            Self::Ucs2 => c.len_utf16(),
+        }
+    }
+
+    /// Maximum length of the user data of a part, in septets or UTF-16 code
+    /// units
+    fn max_units(self, concatenated: bool) -> usize {
+        let octets = if concatenated {
+            MAX_USER_DATA - CONCAT_UDH_LEN
+        } else {
+            MAX_USER_DATA
+        };
+        match self {
+            Self::Gsm7 => octets * 8 / 7,
+            Self::Ucs2 => octets / 2,
+        }
+    }
+}
+
+/// Septets of `c` in the GSM 7 bit alphabet, and how many of them are used
+fn gsm7_septets(c: char) -> Option<([u8; 2], usize)> {
+    if let Some(code) = GSM7_BASIC.iter().position(|&b| b == c && c != '\u{1b}') {
+        return Some(([code as u8, 0], 1));
+    }
+
+    GSM7_EXTENSION
+        .iter()
+        .find(|(ext, _)| *ext == c)
+        .map(|&(_, code)| ([GSM7_ESCAPE, code], 2))
+}
+
+/// Split `text` into the parts of a message, as byte ranges
+///
+/// Characters are never split across parts. Returns `None` if more than
+/// [`MAX_SMS_PARTS`] parts are needed.
+pub(crate) fn split(text: &str, encoding: Encoding) -> Option<Vec<(usize, usize), MAX_SMS_PARTS>> {
+    let mut parts = Vec::new();
+
+    let total: usize = text.chars().map(|c| encoding.units(c)).sum();
+    if total <= encoding.max_units(false) {
+        parts.push((0, text.len())).ok()?;
+        return Some(parts);
+    }
+
+    let max = encoding.max_units(true);
+    let mut start = 0;
+    let mut units = 0;
+    for (i, c) in text.char_indices() {
+        let len = encoding.units(c);
+        if units + len > max {
+            parts.push((start, i)).ok()?;
+            start = i;
+            units = 0;
+        }
+        units += len;
+    }
+    parts.push((start, text.len())).ok()?;
+
+    Some(parts)
+}
+
+/// Concatenation information of a part of a message
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Concatenation {
+    /// Reference number, the same for all parts of a message
+    pub reference: u8,
+    pub total: u8,
+    /// Sequence number of the part, starting at 1
+    pub sequence: u8,
+}
+
+/// Encoded SMS-SUBMIT PDU
+pub(crate) struct Pdu {
+    /// Hex encoded PDU, including the (empty) SMSC address
+    pub hex: String<{ 2 * (MAX_TPDU_LEN + 1) }>,
+    /// Length of the TPDU in octets, excluding the SMSC address, as given to
+    /// +CMGS
+    pub tpdu_len: usize,
+}
+
+/// Writes values to a stream of bits, least significant bit first
+struct BitWriter {
+    data: Vec<u8, MAX_USER_DATA>,
+    bits: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u8, bits: usize) -> Option<()> {
+        for i in 0..bits {
+            if self.bits % 8 == 0 {
+                self.data.push(0).ok()?;
+            }
+            if (value >> i) & 1 == 1 {
+                *self.data.last_mut()? |= 1 << (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+        Some(())
+    }
+}
+
+/// Encode the message `text` to `number` as an SMS-SUBMIT PDU
+///
+/// The SMSC stored in the module is used. Returns `None` if the number is
+/// invalid, or the text does not fit a single PDU.
+pub(crate) fn encode(
+    number: &str,
+    text: &str,
+    encoding: Encoding,
+    concatenation: Option<Concatenation>,
+) -> Option<Pdu> {
+    let (international, digits) = match number.strip_prefix('+') {
+        Some(digits) => (true, digits),
+        None => (false, number),
+    };
+    if digits.is_empty()
+        || digits.len() > MAX_ADDRESS_DIGITS
+        || !digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let mut tpdu = Vec::<u8, MAX_TPDU_LEN>::new();
+
+    // SMS-SUBMIT, with TP-UDHI if concatenated
+    let first_octet = if concatenation.is_some() { 0x41 } else { 0x01 };
+    tpdu.push(first_octet).ok()?;
+    // TP-MR, set by the module
+    tpdu.push(0x00).ok()?;
+
+    // TP-DA: number of digits, type of address and swapped BCD digits
+    tpdu.push(digits.len() as u8).ok()?;
+    tpdu.push(if international { 0x91 } else { 0x81 }).ok()?;
+    for pair in digits.as_bytes().chunks(2) {
+        let low = pair[0] - b'0';
+        let high = pair.get(1).map_or(0xF, |d| d - b'0');
+        tpdu.push((high << 4) | low).ok()?;
+    }
+
+    // TP-PID and TP-DCS
+    tpdu.push(0x00).ok()?;
+    tpdu.push(encoding.dcs()).ok()?;
+
+    let mut ud = BitWriter {
+        data: Vec::new(),
+        bits: 0,
+    };
+    if let Some(c) = concatenation {
+        for octet in [5, 0x00, 3, c.reference, c.total, c.sequence] {
+            ud.write(octet, 8)?;
+        }
+    }
+
+    let udl = match encoding {
+        Encoding::Gsm7 => {
+            // Septets start at a septet boundary, after the header
+            let fill = (7 - ud.bits % 7) % 7;
+            ud.write(0, fill)?;
+
+            for c in text.chars() {
+                let (septets, len) = gsm7_septets(c)?;
+                for &septet in &septets[..len] {
+                    ud.write(septet, 7)?;
+                }
+            }
+            ud.bits / 7
+        }
+        Encoding::Ucs2 => {
+            let mut units = [0u16; 2];
+            for c in text.chars() {
+                for unit in c.encode_utf16(&mut units) {
+                    for octet in unit.to_be_bytes() {
+                        ud.write(octet, 8)?;
+                    }
+                }
+            }
+            ud.data.len()
+        }
+    };
+
+    tpdu.push(udl as u8).ok()?;
+    tpdu.extend_from_slice(&ud.data).ok()?;
+
+    // Empty SMSC address, to use the one stored in the module
+    let mut hex = String::new();
+    hex.push_str("00").ok()?;
+    for octet in &tpdu {
+        write!(hex, "{:02X}", octet).ok()?;
+    }
+
+    Some(Pdu {
+        hex,
+        tpdu_len: tpdu.len(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gsm7_pdu() {
+        let pdu = encode("+4512345678", "hello", Encoding::Gsm7, None).unwrap();
+        assert_eq!(pdu.hex.as_str(), "0001000A915421436587000005E8329BFD06");
+        assert_eq!(pdu.tpdu_len, 17);
+    }
+
+    #[test]
+    fn ucs2_pdu() {
+        assert_eq!(Encoding::of("Grüße"), Encoding::Gsm7);
+        assert_eq!(Encoding::of("Привет"), Encoding::Ucs2);
+
+        let pdu = encode("12345", "Ж€", Encoding::Ucs2, None).unwrap();
+        assert_eq!(pdu.hex.as_str(), "00010005812143F5000804041620AC");
+    }
+
+    #[test]
+    fn concatenated_pdu() {
+        let c = Concatenation {
+            reference: 0x2A,
+            total: 2,
+            sequence: 1,
+        };
+        let pdu = encode("+4512345678", "hi", Encoding::Gsm7, Some(c)).unwrap();
+        // 7 septets of header and fill bits, followed by "hi"
+        assert_eq!(
+            pdu.hex.as_str(),
+            "0041000A9154214365870000090500032A0201D069"
+        );
+    }
+
+    #[test]
+    fn split_parts() {
+        let text = "a".repeat(160);
+        assert_eq!(
+            split(&text, Encoding::Gsm7).unwrap().as_slice(),
+            &[(0, 160)]
+        );
+
+        let text = "a".repeat(161);
+        assert_eq!(
+            split(&text, Encoding::Gsm7).unwrap().as_slice(),
+            &[(0, 153), (153, 161)]
+        );
+
+        // Extension characters take two septets, and are not split
+        let text = "a".repeat(152) + "€" + &"a".repeat(8);
+        let parts = split(&text, Encoding::Gsm7).unwrap();
+        assert_eq!(parts.as_slice(), &[(0, 152), (152, 163)]);
+
+        let text = "ж".repeat(71);
+        assert_eq!(
+            split(&text, Encoding::Ucs2).unwrap().as_slice(),
+            &[(0, 134), (134, 142)]
+        );
+
+        assert!(split(&"a".repeat(153 * 8 + 1), Encoding::Gsm7).is_none());
+    }
+}
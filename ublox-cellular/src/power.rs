@@ -4,11 +4,11 @@ use embedded_hal::digital::{InputPin, OutputPin};
 
 use crate::{
     blocking_timer::BlockingTimer,
-    client::Device,
+    client::{Device, State},
     command::{
         mobile_control::{
-            types::{Functionality, ResetMode},
-            ModuleSwitchOff, SetModuleFunctionality,
+            types::{Functionality, PowerMode, ResetMode},
+            GetModuleFunctionality, ModuleSwitchOff, SetModuleFunctionality,
         },
         system_features::{
             types::{FSFactoryRestoreType, NVMFactoryRestoreType},
@@ -18,7 +18,9 @@ use crate::{
     },
     config::CellularConfig,
     error::{Error, GenericError},
-    module_timing::{pwr_off_time, pwr_on_time, reset_time},
+    module::Capability,
+    registration::ConnectionState,
+    services::data::ContextState,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -102,24 +104,47 @@ where
         Ok(())
     }
 
-    /// Reset the module by driving it's `RESET_N` pin low for 50 ms
+    /// Reset the module by driving it's `RESET_N` pin low for 50 ms, falling
+    /// back to a software restart (+CFUN=15) if no `RESET_N` pin is
+    /// configured. Fails if the module does not respond after the software
+    /// restart.
     ///
     /// **NOTE** This function will reset NVM settings!
     pub fn hard_reset(&mut self) -> Result<(), Error> {
+        match self.hard_reset_strict() {
+            Err(Error::PinUnavailable) => {
+                warn!("No RESET_N pin configured, falling back to a software restart");
+                self.soft_reset(false)?;
+                self.is_alive(5)
+            }
+            res => res,
+        }
+    }
+
+    /// Reset the module by driving it's `RESET_N` pin low for
+    /// [`RESET_TIME`](CellularConfig::RESET_TIME)
+    ///
+    /// Fails with [`Error::PinUnavailable`] if no `RESET_N` pin is configured,
+    /// rather than falling back to a software restart.
+    ///
+    /// **NOTE** This function will reset NVM settings!
+    pub fn hard_reset_strict(&mut self) -> Result<(), Error> {
         trace!("Attempting to hard reset of the modem.");
-        if let Some(rst) = self.config.reset_pin() {
-            rst.set_low().ok();
+        let rst = self.config.reset_pin().ok_or(Error::PinUnavailable)?;
 
-            BlockingTimer::after(reset_time()).wait();
+        rst.set_low().ok();
 
-            rst.set_high().ok();
+        BlockingTimer::after(Config::RESET_TIME).wait();
 
-            BlockingTimer::after(Duration::from_secs(5)).wait();
-        }
+        rst.set_high().ok();
+
+        BlockingTimer::after(Config::BOOT_WAIT_TIME).wait();
 
         self.power_state = PowerState::Off;
 
-        self.power_on()?;
+        if self.is_alive(5).is_err() {
+            self.power_on()?;
+        }
 
         Ok(())
     }
@@ -134,10 +159,10 @@ where
         if self.power_state()? != PowerState::On {
             trace!("Powering modem on.");
             match self.config.power_pin() {
-                // Apply Low pulse on PWR_ON for 50 microseconds to power on
+                // Apply Low pulse on PWR_ON for PWR_ON_TIME to power on
                 Some(pwr) => {
                     pwr.set_low().ok();
-                    BlockingTimer::after(pwr_on_time()).wait();
+                    BlockingTimer::after(Config::PWR_ON_TIME).wait();
 
                     pwr.set_high().ok();
 
@@ -163,6 +188,32 @@ where
         Ok(())
     }
 
+    /// Gracefully power off the module
+    ///
+    /// Sends +CPWROFF and waits for the module to power down, falling back to
+    /// a power off pulse on the `PWR_ON` pin if that fails. A subsequent call
+    /// to [`spin`](Device::spin) will power on and fully re-initialize the
+    /// module.
+    pub fn power_off(&mut self) -> Result<(), Error> {
+        info!("Powering off the modem.");
+
+        let graceful = self.network.send_internal(&ModuleSwitchOff, false).is_ok()
+            && self
+                .wait_power_state(PowerState::Off, Duration::from_secs(40))
+                .is_ok();
+
+        if !graceful {
+            warn!("Graceful power off failed, trying PWR_ON pin");
+            self.hard_power_off()?;
+        }
+
+        self.power_state = PowerState::Off;
+        self.set_state(State::Off);
+        self.network.context_state = ContextState::Setup;
+
+        Ok(())
+    }
+
     pub fn soft_power_off(&mut self) -> Result<(), Error> {
         trace!("Attempting to soft power off the modem.");
 
@@ -182,9 +233,9 @@ where
         if self.power_state()? == PowerState::On {
             match self.config.power_pin() {
                 Some(pwr) => {
-                    // Apply Low pulse on PWR_ON >= 1 second to power off
+                    // Apply Low pulse on PWR_ON for PWR_OFF_TIME to power off
                     pwr.set_low().ok();
-                    BlockingTimer::after(pwr_off_time()).wait();
+                    BlockingTimer::after(Config::PWR_OFF_TIME).wait();
 
                     pwr.set_high().ok();
                     self.power_state = PowerState::Off;
@@ -199,24 +250,121 @@ where
         Ok(())
     }
 
+    /// Get the current functionality level of the module (+CFUN?)
+    ///
+    /// E.g. [`PowerMode::AirplaneMode`] after [`radio_off`](Device::radio_off).
+    pub fn functionality(&mut self) -> Result<PowerMode, Error> {
+        let fun = self.network.send_internal(&GetModuleFunctionality, false)?;
+        Ok(fun.power_mode)
+    }
+
+    /// Switch the radio off, keeping the module powered and the AT interface
+    /// available (+CFUN=4, airplane mode)
+    ///
+    /// The data connection is closed. [`spin`](Device::spin) does not try to
+    /// register until [`radio_on`](Device::radio_on) is called. Modules
+    /// without airplane mode are set to minimum functionality (+CFUN=0).
+    pub fn radio_off(&mut self) -> Result<(), Error> {
+        let fun = if Config::MODULE.supports(Capability::AirplaneMode) {
+            Functionality::AirplaneMode
+        } else {
+            Functionality::Minimum
+        };
+
+        self.network.send_internal(
+            &SetModuleFunctionality {
+                fun,
+                rst: Some(ResetMode::DontReset),
+            },
+            false,
+        )?;
+
+        // Disconnected by choice, so not reconnected by the state machine
+        self.network.status.reset();
+        self.network
+            .status
+            .set_connection_state(ConnectionState::Disconnected);
+        self.network.active_contexts.clear();
+        if self.network.context_state == ContextState::Active {
+            self.network.context_state = ContextState::Activating;
+        }
+
+        Ok(())
+    }
+
+    /// Switch the radio back on after [`radio_off`](Device::radio_off)
+    /// (+CFUN=1)
+    ///
+    /// Registration and the data connection are resumed by
+    /// [`spin`](Device::spin), without re-initializing the module.
+    pub fn radio_on(&mut self) -> Result<(), Error> {
+        self.network.send_internal(
+            &SetModuleFunctionality {
+                fun: Functionality::Full,
+                rst: Some(ResetMode::DontReset),
+            },
+            false,
+        )?;
+
+        self.network.status.reset();
+        self.network
+            .status
+            .set_connection_state(ConnectionState::Connecting);
+        self.network.reset_reg_time()?;
+
+        Ok(())
+    }
+
+    /// Drive the UART DTR line ON (`asserted`) or OFF
+    ///
+    /// DTR is active low, so ON drives the `DTR` pin low. With
+    /// [`CellularConfig::DTR_POWER_SAVING`], the module exits power saving
+    /// while DTR is ON, and may enter it while DTR is OFF.
+    pub fn set_dtr(&mut self, asserted: bool) -> Result<(), Error> {
+        let dtr = self.config.dtr_pin().ok_or(Error::PinUnavailable)?;
+
+        if asserted {
+            dtr.set_low().ok();
+        } else {
+            dtr.set_high().ok();
+        }
+
+        Ok(())
+    }
+
     /// Check the power state of the module, by probing `Vint` pin if available,
     /// fallbacking to checking for AT responses through `is_alive`
     pub fn power_state(&mut self) -> Result<PowerState, Error> {
         match self.config.vint_pin() {
-            Some(vint) => {
-                if vint
-                    .is_high()
-                    .map_err(|_| Error::Generic(GenericError::Unsupported))?
-                {
-                    Ok(PowerState::On)
-                } else {
-                    Ok(PowerState::Off)
+            Some(vint) => match vint.is_high() {
+                Ok(true) => Ok(PowerState::On),
+                Ok(false) => Ok(PowerState::Off),
+                Err(_) => {
+                    // Don't let a flaky pin read prevent initialization
+                    warn!("Failed to read VInt pin, falling back to AT probe");
+                    Ok(self.is_alive(2).map_or(PowerState::Off, |_| PowerState::On))
                 }
-            }
+            },
             _ => Ok(self.is_alive(2).map_or(PowerState::Off, |_| PowerState::On)),
         }
     }
 
+    /// Whether the module is asleep in PSM, as reported by +UUPSMR
+    ///
+    /// If a `VINT` pin is configured, it must also be low, confirming that
+    /// the module has actually powered down. Only then is it safe to cut the
+    /// power of peripherals shared with the module.
+    pub fn in_psm(&mut self) -> bool {
+        if !self.network.psm_entered {
+            return false;
+        }
+
+        match self.config.vint_pin() {
+            Some(vint) => matches!(vint.is_low(), Ok(true)),
+            None => true,
+        }
+    }
+
     /// Wait for the power state to change into `expected`, with a timeout
     fn wait_power_state(&mut self, expected: PowerState, timeout: Duration) -> Result<(), Error> {
         let start = Instant::now();
@@ -176,9 +176,9 @@ impl From<RegType> for RadioAccessNetwork {
 #[derive(Debug, Clone, Default)]
 pub struct CellularGlobalIdentity {
     /// Registered network operator cell Id.
-    cell_id: Option<String<8>>,
+    pub(crate) cell_id: Option<String<8>>,
     /// Registered network operator Location Area Code.
-    lac: Option<String<4>>,
+    pub(crate) lac: Option<String<4>>,
     // Registered network operator Routing Area Code.
     // rac: u8,
     // Registered network operator Tracking Area Code.
@@ -203,8 +203,13 @@ pub struct RegistrationState {
     check_imsi: bool,
 
     pub(crate) cgi: CellularGlobalIdentity,
-    // Radio Access Technology (RAT)
-    // pub(crate) act: RatAct,
+    /// Radio Access Technology (RAT) of the latest registration, `None` while
+    /// not registered
+    pub(crate) act: Option<RatAct>,
+    /// Reject cause of the latest denied registration, until registered
+    pub(crate) reject_cause: Option<u8>,
+    /// +CEER cause reported while registered, i.e. of an earlier failure
+    pub(crate) stale_ceer_cause: Option<u8>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -236,7 +241,9 @@ impl RegistrationState {
             check_imsi: false,
 
             cgi: CellularGlobalIdentity::default(),
-            // act: RatAct::default(),
+            act: None,
+            reject_cause: None,
+            stale_ceer_cause: None,
         }
     }
 
@@ -244,12 +251,64 @@ impl RegistrationState {
         self.csd.reset();
         self.psd.reset();
         self.eps.reset();
+        self.act = None;
         self.reg_start_time = Some(Instant::now());
         self.reg_check_time = Some(Instant::now());
         self.imsi_check_time = None;
         self.registration_interventions = 1;
     }
 
+    /// Combined registration status of the CS, PS and EPS domains, i.e. the
+    /// best status of any of them
+    pub fn status(&self) -> Status {
+        const PRIORITY: [Status; 6] = [
+            Status::Home,
+            Status::Roaming,
+            Status::Searching,
+            Status::Denied,
+            Status::NotRegistering,
+            Status::Unknown,
+        ];
+
+        let statuses = [
+            self.eps.get_status(),
+            self.psd.get_status(),
+            self.csd.get_status(),
+        ];
+        PRIORITY
+            .into_iter()
+            .find(|status| statuses.contains(status))
+            .unwrap_or_default()
+    }
+
+    /// Whether any domain is denied registration, while none is registered
+    pub(crate) fn denied(&self) -> bool {
+        !self.registered()
+            && [&self.csd, &self.psd, &self.eps]
+                .iter()
+                .any(|domain| domain.get_status() == Status::Denied)
+    }
+
+    /// Whether any domain is registered
+    pub(crate) fn registered(&self) -> bool {
+        self.csd.registered() || self.psd.registered() || self.eps.registered()
+    }
+
+    /// Record `cause` as the reject cause while any domain is denied
+    /// registration, and clear it once registered
+    pub(crate) fn update_reject_cause(&mut self, cause: Option<u8>) {
+        if self.registered() {
+            self.reject_cause = None;
+        } else if self.denied() {
+            if let Some(cause) = cause {
+                if self.reject_cause != Some(cause) {
+                    warn!("Registration denied, reject cause {}", cause);
+                }
+                self.reject_cause = Some(cause);
+            }
+        }
+    }
+
     pub fn set_connection_state(&mut self, state: ConnectionState) {
         if self.conn_state == state {
             return;
@@ -288,6 +347,19 @@ impl RegistrationState {
             }
         }
 
+        // Update Radio Access Technology
+        if self.csd.registered() || self.psd.registered() || self.eps.registered() {
+            if matches!(new_params.status, Status::Home | Status::Roaming)
+                && new_params.act != RatAct::Unknown
+                && self.act != Some(new_params.act)
+            {
+                debug!("Radio access technology changed to {:?}", new_params.act);
+                self.act = Some(new_params.act);
+            }
+        } else {
+            self.act = None;
+        }
+
         // Update Cellular Global Identity
         if new_params.cell_id.is_some() && self.cgi.cell_id != new_params.cell_id {
             self.cgi.cell_id = new_params.cell_id.clone();
@@ -312,8 +384,23 @@ impl RegistrationState {
 
 impl From<NetworkRegistrationStatus> for RegistrationParams {
     fn from(v: NetworkRegistrationStatus) -> Self {
+        // <AcT> of +CREG uses the same values as of +CGREG and +CEREG
+        let act = match v.act_status {
+            Some(0) => RatAct::Gsm,
+            Some(1) => RatAct::GsmCompact,
+            Some(2) => RatAct::Utran,
+            Some(3) => RatAct::GsmGprsEdge,
+            Some(4) => RatAct::UtranHspda,
+            Some(5) => RatAct::UtranHsupa,
+            Some(6) => RatAct::UtranHspdaHsupa,
+            Some(7) => RatAct::Lte,
+            Some(8) => RatAct::EcGsmIot,
+            Some(9) => RatAct::Eutran,
+            _ => RatAct::Unknown,
+        };
+
         Self {
-            act: RatAct::Gsm,
+            act,
             reg_type: RegType::Creg,
             status: v.stat.into(),
             cell_id: None,
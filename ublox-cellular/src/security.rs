@@ -0,0 +1,262 @@
+use atat::blocking::AtatClient;
+use core::fmt::Write;
+use heapless::{String, Vec};
+
+use crate::{
+    client::Device,
+    command::device_data_security::{
+        responses::{SecurityData, SecurityDataImport},
+        types::{CertificateValidationLevel, SecurityDataType, SecurityProfileOperation},
+        DeleteSecurityData, ListSecurityData, PrepareSecurityDataImport, SecurityProfileManager,
+        SendSecurityDataChunk, SendSecurityDataImport,
+    },
+    config::CellularConfig,
+    error::Error,
+    md5,
+    services::data::{ssl::SecurityProfileId, Error as DataServiceError},
+};
+
+/// Maximum size of an imported certificate or private key
+pub const MAX_CERTIFICATE_SIZE: usize = 8192;
+
+/// Size of the parts an import is written in
+const IMPORT_CHUNK_SIZE: usize = 2048;
+
+/// Maximum number of certificates and private keys stored in the module
+pub const MAX_CERTIFICATES: usize = 16;
+
+/// Kind of security data imported with
+/// [`import_certificate`](Device::import_certificate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CertKind {
+    /// Trusted root CA certificate
+    RootCa,
+    ClientCertificate,
+    ClientPrivateKey,
+}
+
+impl From<CertKind> for SecurityDataType {
+    fn from(kind: CertKind) -> Self {
+        match kind {
+            CertKind::RootCa => Self::TrustedRootCA,
+            CertKind::ClientCertificate => Self::ClientCertificate,
+            CertKind::ClientPrivateKey => Self::ClientPrivateKey,
+        }
+    }
+}
+
+/// Certificate or private key stored in the module, as returned by
+/// [`list_certificates`](Device::list_certificates)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    /// `None` for other kinds of security data, e.g. server certificates
+    pub kind: Option<CertKind>,
+    pub name: String<200>,
+    /// Subject common name of certificates
+    pub common_name: Option<String<100>>,
+    /// Expiration date of certificates
+    pub expiration_date: Option<String<100>>,
+}
+
+impl From<SecurityData> for Certificate {
+    fn from(data: SecurityData) -> Self {
+        let kind = match data.cert_type.as_str() {
+            "CA" => Some(CertKind::RootCa),
+            "CC" => Some(CertKind::ClientCertificate),
+            "PK" => Some(CertKind::ClientPrivateKey),
+            _ => None,
+        };
+
+        Self {
+            kind,
+            name: data.internal_name,
+            common_name: data.common_name,
+            expiration_date: data.expiration_date,
+        }
+    }
+}
+
+/// SSL/TLS security profile, configured with
+/// [`configure_security_profile`](Device::configure_security_profile)
+///
+/// Certificates and keys are referenced by the internal name they were
+/// imported under.
+#[derive(Clone)]
+pub struct SecurityProfile<'a> {
+    pub id: SecurityProfileId,
+    pub validation: CertificateValidationLevel,
+    /// Trusted root certificate internal name
+    pub ca_certificate: Option<&'a str>,
+    /// Client certificate internal name
+    pub client_certificate: Option<&'a str>,
+    /// Client private key internal name
+    pub client_key: Option<&'a str>,
+    /// Expected server hostname, sent as SNI (Server Name Indication) and
+    /// checked against the server certificate from validation level 2
+    pub expected_hostname: Option<&'a str>,
+}
+
+impl<'a> SecurityProfile<'a> {
+    /// Profile `id` without certificate validation
+    pub const fn new(id: SecurityProfileId) -> Self {
+        Self {
+            id,
+            validation: CertificateValidationLevel::NoValidation,
+            ca_certificate: None,
+            client_certificate: None,
+            client_key: None,
+            expected_hostname: None,
+        }
+    }
+}
+
+/// Copy `value` into a bounded string, failing if it is too long
+pub(crate) fn bounded<const N: usize>(value: &str) -> Result<String<N>, Error> {
+    let mut s = String::new();
+    s.push_str(value)
+        .map_err(|_| Error::DataService(DataServiceError::BadLength))?;
+    Ok(s)
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Configure the security profile `profile.id` (+USECPRF)
+    ///
+    /// The profile is reset to the factory-programmed values first, so
+    /// settings not given in `profile` are cleared. Certificate names longer
+    /// than 200 characters, or a hostname longer than 128 characters, fail
+    /// with [`DataServiceError::BadLength`].
+    pub fn configure_security_profile(&mut self, profile: SecurityProfile) -> Result<(), Error> {
+        let id = profile.id;
+
+        self.send_at(&SecurityProfileManager {
+            profile_id: id,
+            operation: None,
+        })?;
+
+        self.send_at(&SecurityProfileManager {
+            profile_id: id,
+            operation: Some(SecurityProfileOperation::CertificateValidationLevel(
+                profile.validation,
+            )),
+        })?;
+
+        if let Some(name) = profile.ca_certificate {
+            self.send_at(&SecurityProfileManager {
+                profile_id: id,
+                operation: Some(
+                    SecurityProfileOperation::TrustedRootCertificateInternalName(bounded(name)?),
+                ),
+            })?;
+        }
+
+        if let Some(name) = profile.client_certificate {
+            self.send_at(&SecurityProfileManager {
+                profile_id: id,
+                operation: Some(SecurityProfileOperation::ClientCertificateInternalName(
+                    bounded(name)?,
+                )),
+            })?;
+        }
+
+        if let Some(name) = profile.client_key {
+            self.send_at(&SecurityProfileManager {
+                profile_id: id,
+                operation: Some(SecurityProfileOperation::ClientPrivateKeyInternalName(
+                    bounded(name)?,
+                )),
+            })?;
+        }
+
+        if let Some(hostname) = profile.expected_hostname {
+            self.send_at(&SecurityProfileManager {
+                profile_id: id,
+                operation: Some(SecurityProfileOperation::ExpectedServerHostname(bounded(
+                    hostname,
+                )?)),
+            })?;
+            self.send_at(&SecurityProfileManager {
+                profile_id: id,
+                operation: Some(SecurityProfileOperation::ServerNameIndication(bounded(
+                    hostname,
+                )?)),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Import a certificate or private key into the module under `name`
+    /// (+USECMNG)
+    ///
+    /// An existing entry of the same name is overwritten. The data is
+    /// written in parts of at most 2048 bytes, and for DER encoded data the
+    /// MD5 reported by the module is verified. PEM encoded data is converted
+    /// to DER by the module, so it can't be verified.
+    pub fn import_certificate(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        kind: CertKind,
+    ) -> Result<(), Error> {
+        if name.len() > 200 || data.is_empty() || data.len() > MAX_CERTIFICATE_SIZE {
+            return Err(Error::DataService(DataServiceError::BadLength));
+        }
+
+        self.send_at(&PrepareSecurityDataImport {
+            data_type: kind.into(),
+            internal_name: name,
+            data_size: data.len(),
+            password: None,
+        })?;
+
+        // All parts but the last are sent without waiting for the result
+        let last_len = (data.len() - 1) % IMPORT_CHUNK_SIZE + 1;
+        let (parts, last) = data.split_at(data.len() - last_len);
+
+        for part in parts.chunks(IMPORT_CHUNK_SIZE) {
+            self.send_at(&SendSecurityDataChunk {
+                data: atat::serde_bytes::Bytes::new(part),
+            })?;
+        }
+
+        let SecurityDataImport { md5_string, .. } = self.send_at(&SendSecurityDataImport {
+            data: atat::serde_bytes::Bytes::new(last),
+        })?;
+
+        if !data.starts_with(b"-----BEGIN") {
+            let mut expected = String::<32>::new();
+            for byte in md5::digest(data) {
+                write!(expected, "{:02x}", byte).ok();
+            }
+
+            if !expected.eq_ignore_ascii_case(&md5_string) {
+                error!("MD5 mismatch of imported {}", name);
+                return Err(Error::ChecksumMismatch);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List the certificates and private keys stored in the module
+    pub fn list_certificates(&mut self) -> Result<Vec<Certificate, MAX_CERTIFICATES>, Error> {
+        let list = self.send_at(&ListSecurityData)?;
+        Ok(list.into_iter().map(Certificate::from).collect())
+    }
+
+    /// Remove the certificate or private key `name` from the module
+    pub fn remove_certificate(&mut self, name: &str, kind: CertKind) -> Result<(), Error> {
+        self.send_at(&DeleteSecurityData {
+            data_type: kind.into(),
+            internal_name: name,
+        })?;
+        Ok(())
+    }
+}
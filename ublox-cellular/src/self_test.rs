@@ -0,0 +1,76 @@
+use atat::blocking::AtatClient;
+
+use crate::{
+    client::Device,
+    command::device_lock::{responses::PinStatus, types::PinStatusCode, GetPinStatus},
+    config::CellularConfig,
+    error::Error,
+    registration::Status as RegistrationStatus,
+    signal::SignalQuality,
+};
+
+/// Result of [`self_test`](Device::self_test)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestReport {
+    /// Whether the module answers AT commands. If not, no other check is run.
+    pub at_responsive: bool,
+    /// Whether a SIM is inserted and unlocked (+CPIN)
+    pub sim_ready: bool,
+    /// Signal quality of the serving cell, `None` if it could not be read
+    pub signal: Option<SignalQuality>,
+    /// Registration status of the module
+    pub registration: RegistrationStatus,
+}
+
+impl SelfTestReport {
+    /// Whether the module answers, the SIM is ready and a signal is
+    /// received. Registration is not required, as it depends on the network.
+    pub fn passed(&self) -> bool {
+        self.at_responsive
+            && self.sim_ready
+            && self.signal.map_or(false, |signal| {
+                signal.rssi.is_some() || signal.rsrp.is_some()
+            })
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Check the AT interface, the SIM, the signal quality and the
+    /// registration of the module in sequence, e.g. for manufacturing tests
+    ///
+    /// Failed checks are reported in the returned [`SelfTestReport`] rather
+    /// than as errors. Only the AT interface has to be set up, e.g. with
+    /// [`setup_at_commands`](Device::setup_at_commands), the module does not
+    /// have to be connected.
+    pub fn self_test(&mut self) -> Result<SelfTestReport, Error> {
+        let mut report = SelfTestReport {
+            at_responsive: self.is_alive(2).is_ok(),
+            sim_ready: false,
+            signal: None,
+            registration: RegistrationStatus::None,
+        };
+        if !report.at_responsive {
+            return Ok(report);
+        }
+
+        report.sim_ready = matches!(
+            self.network.send_internal(&GetPinStatus, false),
+            Ok(PinStatus {
+                code: PinStatusCode::Ready
+            })
+        );
+        report.signal = self.signal_quality().ok();
+
+        self.network.update_registration().ok();
+        report.registration = self.registration_status();
+
+        Ok(report)
+    }
+}
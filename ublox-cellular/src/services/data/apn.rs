@@ -1,3 +1,9 @@
+/// Maximum length of an APN, as accepted by +CGDCONT and +UPSD
+pub const MAX_APN_LEN: usize = 99;
+
+/// Maximum length of the user name and password of an APN
+pub const MAX_CREDENTIAL_LEN: usize = 64;
+
 #[derive(Debug, Clone)]
 pub enum Apn<'a> {
     Given(&'a str),
@@ -10,20 +16,82 @@ impl<'a> Default for Apn<'a> {
     }
 }
 
+/// Packet Data Protocol (PDP) type of the context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PdpType {
+    #[default]
+    Ipv4,
+    Ipv6,
+    /// Dual stack
+    Ipv4v6,
+}
+
+impl PdpType {
+    /// PDP type as used by +CGDCONT
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ipv4 => "IP",
+            Self::Ipv6 => "IPV6",
+            Self::Ipv4v6 => "IPV4V6",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct APNInfo<'a> {
     pub apn: Apn<'a>,
     pub user_name: Option<&'a str>,
     pub password: Option<&'a str>,
+    pub pdp_type: PdpType,
 }
 
 impl<'a> APNInfo<'a> {
     #[must_use]
-    pub fn new(apn: &'a str) -> Self {
+    pub const fn new(apn: &'a str) -> Self {
         Self {
             apn: Apn::Given(apn),
             user_name: None,
             password: None,
+            pdp_type: PdpType::Ipv4,
         }
     }
+
+    /// APN with the given user name and password
+    #[must_use]
+    pub const fn with_credentials(apn: &'a str, user_name: &'a str, password: &'a str) -> Self {
+        Self {
+            apn: Apn::Given(apn),
+            user_name: Some(user_name),
+            password: Some(password),
+            pdp_type: PdpType::Ipv4,
+        }
+    }
+
+    /// APN selected automatically by the network
+    #[must_use]
+    pub const fn automatic() -> Self {
+        Self {
+            apn: Apn::Automatic,
+            user_name: None,
+            password: None,
+            pdp_type: PdpType::Ipv4,
+        }
+    }
+
+    /// Whether the APN, user name and password fit within the module limits
+    pub(crate) const fn fits(&self) -> bool {
+        let apn_fits = match self.apn {
+            Apn::Given(apn) => apn.len() <= MAX_APN_LEN,
+            Apn::Automatic => true,
+        };
+        let user_name_fits = match self.user_name {
+            Some(user_name) => user_name.len() <= MAX_CREDENTIAL_LEN,
+            None => true,
+        };
+        let password_fits = match self.password {
+            Some(password) => password.len() <= MAX_CREDENTIAL_LEN,
+            None => true,
+        };
+        apn_fits && user_name_fits && password_fits
+    }
 }
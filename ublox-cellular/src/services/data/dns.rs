@@ -5,7 +5,21 @@ use embedded_nal::{AddrType, Dns};
 use heapless::String;
 
 use super::DataService;
-use crate::command::dns::{self, types::ResolutionType};
+#[cfg(not(feature = "upsd-context-activation"))]
+use crate::services::data::CONTEXT_ID;
+use crate::{
+    client::{Device, State},
+    command::{
+        dns::{self, types::ResolutionType},
+        psn,
+    },
+    config::CellularConfig,
+    error::Error as DeviceError,
+    network::{Error as NetworkError, Network},
+    services::data::ContextState,
+};
+#[cfg(feature = "upsd-context-activation")]
+use crate::{command::psn::types::PacketSwitchedNetworkDataParam, services::data::PROFILE_ID};
 use ublox_sockets::Error;
 
 impl<'a, 'sub, AtCl, const N: usize, const L: usize> Dns for DataService<'a, 'sub, AtCl, N, L>
@@ -38,10 +52,6 @@ where
         hostname: &str,
         addr_type: AddrType,
     ) -> nb::Result<IpAddr, Self::Error> {
-        if addr_type == AddrType::IPv6 {
-            return Err(nb::Error::Other(Error::Illegal));
-        }
-
         match self.network.send_internal(
             &dns::ResolveNameIp {
                 resolution_type: ResolutionType::DomainNameToIp,
@@ -49,10 +59,11 @@ where
             },
             true,
         ) {
-            Ok(resp) => resp
-                .ip_domain_string
-                .parse()
-                .map_err(|_e| nb::Error::Other(Error::Illegal)),
+            Ok(resp) => match (resp.ip_domain_string.parse(), addr_type) {
+                (Ok(ip @ IpAddr::V4(_)), AddrType::IPv4 | AddrType::Either)
+                | (Ok(ip @ IpAddr::V6(_)), AddrType::IPv6 | AddrType::Either) => Ok(ip),
+                _ => Err(nb::Error::Other(Error::Illegal)),
+            },
             Err(e) => {
                 error!("get_host_by_name failed: {:?}", e);
                 Err(nb::Error::Other(Error::Unaddressable))
@@ -60,3 +71,128 @@ where
         }
     }
 }
+
+impl<'sub, AtCl> Network<'sub, AtCl>
+where
+    AtCl: AtatClient,
+{
+    /// Resolve `hostname` using +UDNSRN, to the single IPv4 or IPv6 address
+    /// the module answers with
+    pub(crate) fn resolve_host(&mut self, hostname: &str) -> Result<IpAddr, DeviceError> {
+        let resp = self
+            .send_internal(
+                &dns::ResolveNameIp {
+                    resolution_type: ResolutionType::DomainNameToIp,
+                    ip_domain_string: hostname,
+                },
+                true,
+            )
+            .map_err(|e| dns_error(e.into()))?;
+
+        parse_answer(&resp.ip_domain_string)
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Resolve `hostname` to an IPv4 or IPv6 address, using the DNS servers
+    /// of the active data context
+    ///
+    /// Fails with [`DeviceError::DnsResolutionFailed`] if the host could not be
+    /// found.
+    pub fn resolve_host(&mut self, hostname: &str) -> Result<IpAddr, DeviceError> {
+        if self.state == State::Off {
+            error!("Device not initialized!");
+            return Err(DeviceError::Uninitialized);
+        }
+
+        self.network.resolve_host(hostname)
+    }
+
+    /// Use the DNS servers `primary` and `secondary`, rather than the ones
+    /// assigned by the network, e.g. for [`resolve_host`](Device::resolve_host)
+    /// (+UPSD)
+    ///
+    /// The servers are configured on the PSD profile when the data connection
+    /// is set up, so this fails with [`DeviceError::ContextActive`] while it
+    /// is up. Modules without PSD profiles (SARA-R4 / LARA-R6) fail with
+    /// [`DeviceError::UnsupportedByModule`].
+    pub fn set_dns_servers(
+        &mut self,
+        primary: IpAddr,
+        secondary: Option<IpAddr>,
+    ) -> Result<(), DeviceError> {
+        if cfg!(all(
+            not(feature = "upsd-context-activation"),
+            any(feature = "sara-r4", feature = "lara-r6")
+        )) {
+            return Err(DeviceError::UnsupportedByModule);
+        }
+        if self.network.context_state == ContextState::Active {
+            return Err(DeviceError::ContextActive);
+        }
+
+        self.network.dns_servers = Some((primary, secondary));
+        Ok(())
+    }
+
+    /// Get the DNS servers in use by the active data context, as assigned by
+    /// the network or set with [`set_dns_servers`](Device::set_dns_servers)
+    /// (+UPSND / +CGCONTRDP)
+    ///
+    /// Fails with [`DeviceError::NotConnected`] if the data context is not
+    /// active, or no DNS server is assigned.
+    pub fn dns_servers(&mut self) -> Result<(IpAddr, Option<IpAddr>), DeviceError> {
+        if self.network.context_state != ContextState::Active {
+            return Err(DeviceError::NotConnected);
+        }
+
+        #[cfg(feature = "upsd-context-activation")]
+        let (primary, secondary) = {
+            let mut server = |param| {
+                self.send_at(&psn::GetPacketSwitchedNetworkAddress {
+                    profile_id: PROFILE_ID,
+                    param,
+                })
+                .map(|resp| resp.address())
+            };
+            (
+                server(PacketSwitchedNetworkDataParam::DNS1)?,
+                server(PacketSwitchedNetworkDataParam::DNS2)?,
+            )
+        };
+        #[cfg(not(feature = "upsd-context-activation"))]
+        let (primary, secondary) = self
+            .send_at(&psn::GetPDPContextDynamicParameters { cid: CONTEXT_ID })?
+            .dns_servers();
+
+        match (primary, secondary) {
+            (Some(primary), secondary) => Ok((primary, secondary)),
+            (None, Some(secondary)) => Ok((secondary, None)),
+            (None, None) => Err(DeviceError::NotConnected),
+        }
+    }
+}
+
+fn dns_error(e: DeviceError) -> DeviceError {
+    match e {
+        // The module answers with an error result code if the host is not
+        // found
+        DeviceError::Network(NetworkError::AT(atat::Error::Error)) | DeviceError::Cme(_) => {
+            DeviceError::DnsResolutionFailed
+        }
+        e => e,
+    }
+}
+
+fn parse_answer(answer: &str) -> Result<IpAddr, DeviceError> {
+    answer.parse().map_err(|_| {
+        error!("Invalid DNS answer: {}", answer);
+        DeviceError::DnsResolutionFailed
+    })
+}
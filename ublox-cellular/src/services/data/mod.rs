@@ -16,34 +16,38 @@ use crate::{
     client::Device,
     command::mobile_control::types::{Functionality, ResetMode},
     command::mobile_control::SetModuleFunctionality,
+    command::psn::types::AuthenticationType,
     command::psn::types::PDPContextStatus,
     command::psn::SetPDPContextDefinition,
     command::psn::SetPDPContextState,
     command::Urc,
     command::{
-        ip_transport_layer::{
-            responses::{SocketData, UDPSocketData},
-            ReadSocketData, ReadUDPSocketData,
-        },
-        psn::{self, responses::GPRSAttached, GetPDPContextState},
+        ip_transport_layer::{responses::SocketData, CloseSocket, ReadSocketData},
+        psn::{self, responses::GPRSAttached},
     },
     config::CellularConfig,
     error::Error as DeviceError,
     network::{ContextId, Network},
     ProfileId,
 };
+#[cfg(feature = "sara-r5")]
+use apn::PdpType;
 use apn::{APNInfo, Apn};
 use atat::blocking::AtatClient;
 use embassy_time::Duration;
 
 pub use error::Error;
-use psn::{types::GPRSAttachedState, GetGPRSAttached};
-use ublox_sockets::{Error as SocketError, SocketSet, SocketType};
+use psn::{types::GPRSAttachedState, GetGPRSAttached, SetGPRSAttached};
+use ublox_sockets::{Error as SocketError, SocketHandle, SocketSet, SocketType};
 
+#[cfg(feature = "socket-udp")]
+use crate::command::ip_transport_layer::GetUDPSocketDataLength;
 use crate::command::psn::responses::PacketSwitchedNetworkData;
 use crate::command::psn::types::{
     PacketSwitchedAction, PacketSwitchedNetworkDataParam, PacketSwitchedParam, ProtocolType,
 };
+#[cfg(not(feature = "upsd-context-activation"))]
+use crate::command::psn::SetAuthParameters;
 use crate::command::psn::{
     GetPacketSwitchedNetworkData, SetPacketSwitchedAction, SetPacketSwitchedConfig,
 };
@@ -56,10 +60,13 @@ use heapless::String;
 pub const INGRESS_CHUNK_SIZE: usize = 256;
 pub const EGRESS_CHUNK_SIZE: usize = 1024;
 
+/// Maximum number of sockets the module can have open at the same time.
+pub const MAX_SOCKETS: usize = 6;
+
 pub const PROFILE_ID: ProfileId = ProfileId(1);
 
-#[cfg(not(feature = "upsd-context-activation"))]
-const CONTEXT_ID: ContextId = ContextId(1);
+/// Primary data context, used by the internal sockets
+pub(crate) const CONTEXT_ID: ContextId = ContextId(1);
 
 impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
     Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
@@ -75,6 +82,14 @@ where
             return Ok(());
         }
 
+        // Skip redefining an already active context, e.g. after restarting the
+        // application without resetting the module
+        if self.network.pdp_context_active(cid)? {
+            debug!("PDP context {} already active", cid.0);
+            self.network.context_state = ContextState::Activating;
+            return Ok(());
+        }
+
         self.network.send_internal(
             &SetModuleFunctionality {
                 fun: Functionality::Minimum,
@@ -91,22 +106,24 @@ where
             self.network.send_internal(
                 &SetPDPContextDefinition {
                     cid,
-                    pdp_type: "IP",
+                    pdp_type: apn_info.pdp_type.as_str(),
                     apn,
                 },
                 true,
             )?;
         }
 
-        // self.network.send_internal(
-        //     &SetAuthParameters {
-        //         cid,
-        //         auth_type: AuthenticationType::Auto,
-        //         username: &apn_info.clone().user_name.unwrap_or_default(),
-        //         password: &apn_info.clone().password.unwrap_or_default(),
-        //     },
-        //     true,
-        // )?;
+        if apn_info.user_name.is_some() || apn_info.password.is_some() {
+            self.network.send_internal(
+                &SetAuthParameters {
+                    cid,
+                    auth_type: AuthenticationType::Auto,
+                    username: apn_info.user_name.unwrap_or_default(),
+                    password: apn_info.password.unwrap_or_default(),
+                },
+                true,
+            )?;
+        }
 
         self.network.send_internal(
             &SetModuleFunctionality {
@@ -120,6 +137,123 @@ where
         Ok(())
     }
 
+    /// Handle modem data connection, using the APN of
+    /// [`CellularConfig::apn`]
+    ///
+    /// See [`data_service`](Device::data_service).
+    pub fn configured_data_service<'a>(
+        &'a mut self,
+    ) -> nb::Result<DataService<'a, 'sub, AtCl, N, L>, DeviceError> {
+        let apn = self.config.apn();
+        self.data_service(&apn)
+    }
+
+    /// Run `f` on the data service of [`CellularConfig::apn`], as used by the
+    /// `embedded-nal` stacks of [`Device`]
+    #[cfg(any(feature = "socket-tcp", feature = "socket-udp"))]
+    pub(crate) fn with_data_service<R, F>(&mut self, f: F) -> nb::Result<R, DeviceError>
+    where
+        F: FnOnce(&mut DataService<'_, 'sub, AtCl, N, L>) -> nb::Result<R, SocketError>,
+    {
+        let mut service = self.configured_data_service()?;
+        f(&mut service).map_err(|e| e.map(|e| Error::Socket(e).into()))
+    }
+
+    /// Check whether the PDP context `cid` is active (+CGACT?)
+    pub fn pdp_context_active(&mut self, cid: u8) -> Result<bool, DeviceError> {
+        Ok(self.network.pdp_context_active(ContextId(cid))?)
+    }
+
+    /// Detach from the packet domain (+CGATT=0), e.g. for a controlled
+    /// disconnect before sleeping
+    ///
+    /// Open sockets are closed and the data connection is torn down, while
+    /// the module stays registered. The next data service request, e.g. from
+    /// [`data_service`](Device::data_service), attaches again without
+    /// re-initializing the module.
+    pub fn detach(&mut self) -> Result<(), DeviceError> {
+        if let Some(sockets) = self.sockets.as_deref_mut() {
+            let handles: heapless::Vec<SocketHandle, N> =
+                sockets.iter_mut().map(|(handle, _)| handle).collect();
+            for handle in handles {
+                self.network
+                    .send_internal(&CloseSocket { socket: handle }, false)
+                    .ok();
+                sockets.remove(handle).ok();
+            }
+        }
+
+        self.network.send_internal(
+            &SetGPRSAttached {
+                state: GPRSAttachedState::Detached,
+            },
+            true,
+        )?;
+
+        self.network.detached = true;
+        self.network.attached = false;
+        self.network.active_contexts.clear();
+        if self.network.context_state == ContextState::Active {
+            self.network.context_state = ContextState::Activating;
+        }
+        Ok(())
+    }
+
+    /// Remove all sockets from the socket set, and close all sockets of the
+    /// module (+USOCL)
+    ///
+    /// Every socket handle the module may use is closed, as the module may
+    /// hold sockets unknown to the socket set, e.g. after a reboot. Errors of
+    /// already closed sockets are ignored.
+    pub fn reset_sockets(&mut self) {
+        if let Some(sockets) = self.sockets.as_deref_mut() {
+            let handles: heapless::Vec<SocketHandle, N> =
+                sockets.iter_mut().map(|(handle, _)| handle).collect();
+            for handle in handles {
+                sockets.remove(handle).ok();
+            }
+        }
+
+        for socket in 0..=MAX_SOCKETS as u8 {
+            self.network
+                .send_internal(
+                    &CloseSocket {
+                        socket: SocketHandle(socket),
+                    },
+                    false,
+                )
+                .ok();
+        }
+    }
+
+    /// Check whether the module is attached to the packet domain (+CGATT?),
+    /// without setting up the data connection
+    pub fn is_attached(&mut self) -> Result<bool, DeviceError> {
+        let GPRSAttached { state } = self.send_at(&GetGPRSAttached)?;
+        self.network.attached = state == GPRSAttachedState::Attached;
+        Ok(self.network.attached)
+    }
+
+    /// Get the number of bytes waiting to be read from the module, of all
+    /// sockets at once
+    ///
+    /// Rather than querying each socket with +USORD, the counts announced by
+    /// the +UUSORD / +UUSORF URCs received so far are returned.
+    pub fn poll_sockets(&mut self) -> Result<SocketPollResults, DeviceError> {
+        self.process_urcs()?;
+
+        let sockets = self
+            .sockets
+            .as_deref_mut()
+            .ok_or(Error::Socket(SocketError::Illegal))?;
+
+        let mut results = SocketPollResults::default();
+        for (handle, socket) in sockets.iter_mut() {
+            results.sockets.push((handle, socket.available_data())).ok();
+        }
+        Ok(results)
+    }
+
     /// Handle modem data connection
     ///
     /// For typical use case only this is needed to manage modem automatically.
@@ -163,7 +297,10 @@ where
 
         // At this point we WILL be registered on the network!
         match DataService::try_new(apn_info, &mut self.network, self.sockets.as_deref_mut()) {
-            Ok(service) => Ok(service),
+            Ok(mut service) => {
+                service.hex_mode = Config::HEX_MODE;
+                Ok(service)
+            }
             Err(nb::Error::Other(e)) => Err(nb::Error::Other(e.into())),
             Err(nb::Error::WouldBlock) => Err(nb::Error::WouldBlock),
         }
@@ -178,12 +315,55 @@ pub enum ContextState {
     Active,
 }
 
+/// Connection state of a socket, see
+/// [`socket_state`](DataService::socket_state)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SocketState {
+    /// Created but not yet connected. UDP sockets stay in this state, as they
+    /// are connectionless
+    Open,
+    /// TCP socket connected to a remote
+    Connected,
+    /// Closed, e.g. by the remote
+    Closed,
+}
+
+/// Bytes waiting to be read from the module, of each socket, see
+/// [`poll_sockets`](Device::poll_sockets)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SocketPollResults {
+    pub sockets: heapless::Vec<(SocketHandle, usize), MAX_SOCKETS>,
+}
+
+impl SocketPollResults {
+    /// Bytes waiting to be read of the socket `handle`, `None` if it is not a
+    /// known socket
+    pub fn available(&self, handle: SocketHandle) -> Option<usize> {
+        self.sockets
+            .iter()
+            .find(|(h, _)| *h == handle)
+            .map(|(_, len)| *len)
+    }
+
+    /// Sockets with data waiting to be read
+    pub fn readable(&self) -> impl Iterator<Item = SocketHandle> + '_ {
+        self.sockets
+            .iter()
+            .filter(|(_, len)| *len > 0)
+            .map(|(handle, _)| *handle)
+    }
+}
+
 pub struct DataService<'a, 'sub, AtCl, const N: usize, const L: usize>
 where
     AtCl: AtatClient,
 {
     network: &'a mut Network<'sub, AtCl>,
     pub(crate) sockets: Option<&'a mut SocketSet<N, L>>,
+    /// Whether new sockets use HEX mode, see
+    /// [`CellularConfig::HEX_MODE`]
+    hex_mode: bool,
 }
 
 impl<'a, 'sub, AtCl, const N: usize, const L: usize> DataService<'a, 'sub, AtCl, N, L>
@@ -195,7 +375,11 @@ where
         network: &'a mut Network<'sub, AtCl>,
         sockets: Option<&'a mut SocketSet<N, L>>,
     ) -> nb::Result<Self, Error> {
-        let mut data_service = Self { network, sockets };
+        let mut data_service = Self {
+            network,
+            sockets,
+            hex_mode: true,
+        };
 
         // Check if context is active, and create if not
         data_service.connect(apn_info)?;
@@ -239,6 +423,19 @@ where
 
     // Make sure we are attached to the cellular network.
     fn attach_network(&mut self) -> nb::Result<(), Error> {
+        // Attach again after an explicit detach
+        if self.network.detached {
+            self.network
+                .send_internal(
+                    &SetGPRSAttached {
+                        state: GPRSAttachedState::Attached,
+                    },
+                    true,
+                )
+                .map_err(Error::from)?;
+            self.network.detached = false;
+        }
+
         // Wait for AT+CGATT to return 1
         for _ in 0..10 {
             let GPRSAttached { state } = self
@@ -246,7 +443,8 @@ where
                 .send_internal(&GetGPRSAttached, true)
                 .map_err(Error::from)?;
 
-            if state == GPRSAttachedState::Attached {
+            self.network.attached = state == GPRSAttachedState::Attached;
+            if self.network.attached {
                 return Ok(());
             }
 
@@ -366,7 +564,11 @@ where
                 .send_internal(
                     &SetPacketSwitchedConfig {
                         profile_id,
-                        param: PacketSwitchedParam::ProtocolType(ProtocolType::IPv4),
+                        param: PacketSwitchedParam::ProtocolType(match apn_info.pdp_type {
+                            PdpType::Ipv4 => ProtocolType::IPv4,
+                            PdpType::Ipv6 => ProtocolType::IPv6,
+                            PdpType::Ipv4v6 => ProtocolType::IPv4v6PreferV4Internal,
+                        }),
                     },
                     true,
                 )
@@ -377,12 +579,16 @@ where
                 .send_internal(
                     &SetPacketSwitchedConfig {
                         profile_id,
-                        param: PacketSwitchedParam::MapProfile(ContextId(1)),
+                        param: PacketSwitchedParam::MapProfile(CONTEXT_ID),
                     },
                     true,
                 )
                 .map_err(Error::from)?;
 
+            self.network
+                .configure_dns_servers(profile_id)
+                .map_err(Error::from)?;
+
             self.network
                 .send_internal(
                     &SetPacketSwitchedAction {
@@ -394,7 +600,7 @@ where
                 .map_err(Error::from)?;
         }
 
-        self.network.context_state = ContextState::Active;
+        self.network.data_connected(CONTEXT_ID);
         Ok(())
     }
 
@@ -406,23 +612,7 @@ where
             return Ok(());
         }
 
-        let context_states = self
-            .network
-            .send_internal(&GetPDPContextState, true)
-            .map_err(Error::from)?;
-
-        let activated = context_states
-            .iter()
-            .find_map(|state| {
-                if state.cid == cid {
-                    Some(state.status == PDPContextStatus::Activated)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(false);
-
-        if activated {
+        if self.network.pdp_context_active(cid).map_err(Error::from)? {
             // Note: SARA-R4 only supports a single context at any one time and
             // so doesn't require/support AT+UPSD.
             #[cfg(not(any(feature = "sara-r4", feature = "lara-r6")))]
@@ -476,6 +666,9 @@ where
                     .map_err(Error::from)?;
 
                 if param_tag == 0 {
+                    self.network
+                        .configure_dns_servers(profile_id)
+                        .map_err(Error::from)?;
                     self.network
                         .send_internal(
                             &psn::SetPacketSwitchedAction {
@@ -488,7 +681,7 @@ where
                 }
             }
 
-            self.network.context_state = ContextState::Active;
+            self.network.data_connected(cid);
             Ok(())
         } else {
             self.network
@@ -505,6 +698,128 @@ where
         }
     }
 
+    /// Close the socket `handle` using +USOCL, and remove it from the socket
+    /// set.
+    ///
+    /// Closing a socket the module already reports as closed succeeds, so this
+    /// can be called on sockets closed by the remote. Fails with
+    /// [`SocketError::InvalidSocket`] if `handle` is not a known socket.
+    pub fn close(&mut self, handle: SocketHandle) -> Result<(), SocketError> {
+        if let Some(ref mut sockets) = self.sockets {
+            if !sockets.iter_mut().any(|(h, _)| h == handle) {
+                return Err(SocketError::InvalidSocket);
+            }
+
+            if let Err(e) = self
+                .network
+                .send_internal(&CloseSocket { socket: handle }, false)
+            {
+                // The module reports an error if the socket is already closed
+                debug!("Closing socket {} failed: {:?}", handle.0, e);
+            }
+
+            sockets.remove(handle)?;
+            Ok(())
+        } else {
+            Err(SocketError::Illegal)
+        }
+    }
+
+    /// Get the last error of the socket `handle` (+USOCTL)
+    ///
+    /// The error is a BSD error code, e.g. 0 once the remote closed the
+    /// connection cleanly, 104 (ECONNRESET) if it reset the connection, or
+    /// 110 (ETIMEDOUT) if the network stopped answering.
+    pub fn socket_last_error(&mut self, handle: SocketHandle) -> Result<u16, SocketError> {
+        let sockets = self.sockets.as_deref_mut().ok_or(SocketError::Illegal)?;
+        if !sockets.iter_mut().any(|(h, _)| h == handle) {
+            return Err(SocketError::InvalidSocket);
+        }
+
+        self.network
+            .socket_last_error(handle)
+            .map_err(|_| SocketError::Unaddressable)
+    }
+
+    /// Get the connection state of the socket `handle`
+    pub fn socket_state(&mut self, handle: SocketHandle) -> Result<SocketState, SocketError> {
+        let sockets = self.sockets.as_deref_mut().ok_or(SocketError::Illegal)?;
+
+        let socket_type = sockets
+            .iter_mut()
+            .find(|(h, _)| *h == handle)
+            .map(|(_, socket)| socket.get_type())
+            .ok_or(SocketError::InvalidSocket)?;
+
+        match socket_type {
+            #[cfg(feature = "socket-tcp")]
+            SocketType::Tcp => {
+                let tcp = sockets.get::<ublox_sockets::TcpSocket<L>>(handle)?;
+                Ok(if tcp.is_connected() {
+                    SocketState::Connected
+                } else if matches!(tcp.state(), ublox_sockets::TcpState::Created) {
+                    SocketState::Open
+                } else {
+                    SocketState::Closed
+                })
+            }
+            #[cfg(feature = "socket-udp")]
+            SocketType::Udp => {
+                let udp = sockets.get::<ublox_sockets::UdpSocket<L>>(handle)?;
+                Ok(if udp.is_open() {
+                    SocketState::Open
+                } else {
+                    SocketState::Closed
+                })
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(SocketError::Illegal),
+        }
+    }
+
+    /// Query the number of bytes waiting to be read from the module for the
+    /// socket `handle`, using the `+USORD=<socket>,0` form, or
+    /// `+USORF=<socket>,0` for UDP sockets
+    ///
+    /// The count is cached in the socket set, so subsequent reads know how
+    /// much data to pull.
+    pub fn bytes_available(&mut self, handle: SocketHandle) -> Result<usize, SocketError> {
+        let sockets = self.sockets.as_deref_mut().ok_or(SocketError::Illegal)?;
+
+        let (_, mut socket) = sockets
+            .iter_mut()
+            .find(|(h, _)| *h == handle)
+            .ok_or(SocketError::InvalidSocket)?;
+
+        let length = match socket.get_type() {
+            #[cfg(feature = "socket-udp")]
+            SocketType::Udp => self
+                .network
+                .send_internal(
+                    &GetUDPSocketDataLength {
+                        socket: handle,
+                        length: 0,
+                    },
+                    false,
+                )
+                .map(|resp| resp.length),
+            _ => self
+                .network
+                .send_internal(
+                    &ReadSocketData {
+                        socket: handle,
+                        length: 0,
+                    },
+                    false,
+                )
+                .map(|SocketData { length, .. }| length),
+        }
+        .map_err(|_| SocketError::Unaddressable)?;
+
+        socket.set_available_data(length);
+        Ok(length)
+    }
+
     pub fn send_at<A, const LEN: usize>(&mut self, cmd: &A) -> Result<A::Response, Error>
     where
         A: atat::AtatCmd<LEN>,
@@ -513,7 +828,11 @@ where
     }
 
     pub fn handle_urc<F: FnOnce(Urc) -> bool>(&mut self, f: F) -> Result<(), Error> {
-        self.network.at_tx.handle_urc(f).map_err(Error::Network)
+        self.network
+            .at_tx
+            .handle_urc(f)
+            .map(|_| ())
+            .map_err(Error::Network)
     }
 
     fn socket_ingress_all(&mut self) -> Result<(), Error> {
@@ -543,6 +862,12 @@ where
                         return Ok(());
                     }
 
+                    // UDP datagrams are read one at a time on demand, in order
+                    // to keep track of the address each was sent from
+                    if matches!(socket.get_type(), SocketType::Udp) {
+                        return Ok(());
+                    }
+
                     if !socket.can_recv() {
                         return Err(Error::BufferFull);
                     }
@@ -554,6 +879,7 @@ where
                     // ingress the smallest of the two
                     let requested_len = core::cmp::min(wanted_len, socket.rx_window());
 
+                    let hex_mode = network.select_hex_mode(handle)?;
                     let (socket_handle, mut data, len) = match socket.get_type() {
                         SocketType::Tcp => {
                             // Allow room for 2x length (Hex), and command
@@ -572,24 +898,6 @@ where
 
                             (socket, data, length)
                         }
-                        SocketType::Udp => {
-                            // Allow room for 2x length (Hex), and command
-                            // overhead
-                            let UDPSocketData {
-                                socket,
-                                data,
-                                length,
-                                ..
-                            } = network.send_internal(
-                                &ReadUDPSocketData {
-                                    socket: handle,
-                                    length: requested_len,
-                                },
-                                false,
-                            )?;
-
-                            (socket, data, length)
-                        }
                         _ => return Err(Error::WrongSocketType),
                     };
 
@@ -603,8 +911,6 @@ where
                     }
 
                     if let Some(ref mut data) = data {
-                        let hex_mode = true;
-                        // let hex_mode = self.config.hex_mode;
                         let data_len = if hex_mode { data.len() / 2 } else { data.len() };
                         if len > 0 && data_len != len {
                             error!("BadLength {} != {}, {}", len, data_len, data.as_str());
@@ -645,3 +951,49 @@ where
         }
     }
 }
+
+/// +USOER error code of a local port already bound by another socket
+#[cfg(any(feature = "socket-tcp", feature = "socket-udp"))]
+const EADDRINUSE: u8 = 98;
+
+/// Error of +USOCR failing for a socket bound to `local_port`, for which a
+/// port in use by another socket is reported as [`DeviceError::PortInUse`]
+///
+/// The socket error (+USOER) is only set by +USOCR if the module answered it
+/// with an error, so it is not read if the command timed out.
+#[cfg(any(feature = "socket-tcp", feature = "socket-udp"))]
+fn create_socket_error<AtCl: AtatClient>(
+    network: &mut Network<'_, AtCl>,
+    e: crate::network::Error,
+    local_port: Option<u16>,
+) -> DeviceError {
+    use crate::command::ip_transport_layer::{responses::SocketErrorResponse, GetSocketError};
+
+    if local_port.is_some() && !matches!(e, crate::network::Error::AT(atat::Error::Timeout)) {
+        let code = network.send_internal(&GetSocketError, false);
+        if matches!(code, Ok(SocketErrorResponse { error: EADDRINUSE })) {
+            return DeviceError::PortInUse;
+        }
+    }
+    Error::Socket(SocketError::Unaddressable).into()
+}
+
+/// Socket error of a failure to create a socket, for the `embedded-nal`
+/// traits
+#[cfg(any(feature = "socket-tcp", feature = "socket-udp"))]
+fn socket_error(e: DeviceError) -> SocketError {
+    match e {
+        DeviceError::DataService(Error::Socket(e)) => e,
+        _ => SocketError::Unaddressable,
+    }
+}
+
+/// Error of a non-blocking `embedded-nal` call, for which an unavailable data
+/// connection is reported as [`DeviceError::Busy`]
+#[cfg(any(feature = "socket-tcp", feature = "socket-udp"))]
+fn not_ready(e: nb::Error<DeviceError>) -> DeviceError {
+    match e {
+        nb::Error::Other(e) => e,
+        nb::Error::WouldBlock => DeviceError::Busy,
+    }
+}
@@ -1,15 +1,42 @@
 use super::ssl::SecurityProfileId;
-use super::DataService;
-use super::EGRESS_CHUNK_SIZE;
+use super::Error as DataServiceError;
+use super::{create_socket_error, hex, not_ready, socket_error, DataService};
+use super::{EGRESS_CHUNK_SIZE, MAX_SOCKETS};
+use crate::blocking_timer::BlockingTimer;
+use crate::client::Device;
+use crate::command::device_data_security::{
+    types::SecurityProfileOperation, SecurityProfileManager,
+};
 use crate::command::ip_transport_layer::{
-    types::{SocketProtocol, SslTlsStatus},
-    CloseSocket, ConnectSocket, CreateSocket, PrepareWriteSocketDataBinary, SetSocketSslState,
-    WriteSocketDataBinary,
+    responses::SocketControlResponse,
+    types::{SocketControlParam, SocketOptionLevel, SocketProtocol, SslTlsStatus},
+    ConnectSocket, CreateSocket, GetSocketError, PrepareWriteSocketDataBinary, SetSocketOption,
+    SetSocketSslState, SocketControl, WriteSocketDataBinary,
 };
+use crate::config::CellularConfig;
+use crate::error::Error as DeviceError;
+use crate::network::TimeoutMs;
+use crate::security::bounded;
 use atat::blocking::AtatClient;
+use embassy_time::{Duration, Instant};
 use embedded_nal::{SocketAddr, TcpClientStack};
 use ublox_sockets::{Error, SocketHandle, TcpSocket, TcpState};
 
+/// Establishing a TCP connection can take up to 120 seconds
+const CONNECT_TIMEOUT: TimeoutMs<120_000> = TimeoutMs;
+
+/// +USOSO option names
+const SO_KEEPALIVE: u8 = 8;
+const TCP_KEEPIDLE: u8 = 2;
+
+/// +USOER codes of a failed SSL/TLS handshake: EPROTO, EBADMSG, EKEYEXPIRED,
+/// EKEYREVOKED and EKEYREJECTED. Other codes, e.g. ECONNREFUSED, are failures
+/// of the TCP connection itself.
+const TLS_SOCKET_ERRORS: [u8; 5] = [71, 74, 127, 128, 129];
+
+/// Interval in which URCs and socket data are polled while waiting for data
+const RECEIVE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 impl<'a, 'sub, AtCl, const N: usize, const L: usize> TcpClientStack
     for DataService<'a, 'sub, AtCl, N, L>
 where
@@ -23,31 +50,7 @@ where
 
     /// Open a new TCP socket to the given address and port. The socket starts in the unconnected state.
     fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
-        if let Some(ref mut sockets) = self.sockets {
-            // Check if there are any unused sockets available
-            if sockets.len() >= sockets.capacity() {
-                // Check if there are any sockets closed by remote, and close it
-                // if it has exceeded its timeout, in order to recycle it.
-                if !sockets.recycle() {
-                    return Err(Error::SocketSetFull);
-                }
-            }
-
-            let socket_resp = self
-                .network
-                .send_internal(
-                    &CreateSocket {
-                        protocol: SocketProtocol::TCP,
-                        local_port: None,
-                    },
-                    true,
-                )
-                .map_err(|_| Error::Unaddressable)?;
-
-            Ok(sockets.add(TcpSocket::new(socket_resp.socket.0))?)
-        } else {
-            Err(Error::Illegal)
-        }
+        self.tcp_socket(None, self.hex_mode).map_err(socket_error)
     }
 
     /// Connect to the given remote host and port.
@@ -56,46 +59,7 @@ where
         socket: &mut Self::TcpSocket,
         remote: SocketAddr,
     ) -> nb::Result<(), Self::Error> {
-        if let Some(ref mut sockets) = self.sockets {
-            let mut tcp = sockets
-                .get::<TcpSocket<L>>(*socket)
-                .map_err(Self::Error::from)?;
-
-            if matches!(tcp.state(), TcpState::Created) {
-                self.network
-                    .send_internal(
-                        &SetSocketSslState {
-                            socket: *socket,
-                            ssl_tls_status: SslTlsStatus::Enabled(SecurityProfileId(0)),
-                        },
-                        true,
-                    )
-                    .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
-
-                self.network
-                    .send_internal(
-                        &ConnectSocket {
-                            socket: *socket,
-                            remote_addr: remote.ip(),
-                            remote_port: remote.port(),
-                        },
-                        false,
-                    )
-                    .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
-
-                tcp.set_state(TcpState::Connected(remote));
-                Ok(())
-            } else {
-                error!(
-                    "Cannot connect socket! Socket: {:?} is in state: {:?}",
-                    socket,
-                    tcp.state()
-                );
-                Err(Error::Illegal.into())
-            }
-        } else {
-            Err(Error::Illegal.into())
-        }
+        self.connect_with_security(*socket, remote, SecurityProfileId(0))
     }
 
     /// Check if this socket is still connected
@@ -172,14 +136,422 @@ where
 
     /// Close an existing TCP socket.
     fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        DataService::close(self, socket)
+    }
+}
+
+impl<'a, 'sub, AtCl, const N: usize, const L: usize> DataService<'a, 'sub, AtCl, N, L>
+where
+    AtCl: AtatClient,
+{
+    /// Open a new TCP socket, optionally sending from `local_port`, and
+    /// connect it to `remote`.
+    ///
+    /// At most [`MAX_SOCKETS`] sockets can be open at the same time, after
+    /// which [`Error::SocketSetFull`] is returned. If `local_port` is bound by
+    /// another socket, [`DeviceError::PortInUse`] is returned.
+    pub fn tcp_connect(
+        &mut self,
+        remote: SocketAddr,
+        local_port: Option<u16>,
+    ) -> Result<SocketHandle, DeviceError> {
+        self.tcp_connect_with_hex_mode(remote, local_port, self.hex_mode)
+    }
+
+    /// Like [`tcp_connect`](Self::tcp_connect), but with the data of the
+    /// socket received in HEX mode or not, instead of as set by
+    /// [`CellularConfig::HEX_MODE`](crate::CellularConfig::HEX_MODE)
+    ///
+    /// HEX mode keeps control characters out of the read responses, at the
+    /// cost of twice the bytes transferred.
+    pub fn tcp_connect_with_hex_mode(
+        &mut self,
+        remote: SocketAddr,
+        local_port: Option<u16>,
+        hex_mode: bool,
+    ) -> Result<SocketHandle, DeviceError> {
+        let mut socket = self.tcp_socket(local_port, hex_mode)?;
+
+        match nb::block!(TcpClientStack::connect(self, &mut socket, remote)) {
+            Ok(()) => Ok(socket),
+            Err(e) => {
+                TcpClientStack::close(self, socket).ok();
+                Err(DataServiceError::Socket(e).into())
+            }
+        }
+    }
+
+    /// Open a new TCP socket, optionally bound to `local_port`
+    fn tcp_socket(
+        &mut self,
+        local_port: Option<u16>,
+        hex_mode: bool,
+    ) -> Result<SocketHandle, DeviceError> {
         if let Some(ref mut sockets) = self.sockets {
+            // Check if there are any unused sockets available
+            if sockets.len() >= sockets.capacity().min(MAX_SOCKETS) {
+                // Check if there are any sockets closed by remote, and close it
+                // if it has exceeded its timeout, in order to recycle it.
+                if !sockets.recycle() {
+                    return Err(DataServiceError::Socket(Error::SocketSetFull).into());
+                }
+            }
+
+            let socket_resp = self
+                .network
+                .send_internal(
+                    &CreateSocket {
+                        protocol: SocketProtocol::TCP,
+                        local_port,
+                    },
+                    true,
+                )
+                .map_err(|e| create_socket_error(self.network, e, local_port))?;
+
+            let handle = sockets
+                .add(TcpSocket::new(socket_resp.socket.0))
+                .map_err(|e| DataServiceError::Socket(e.into()))?;
+            self.network.set_socket_hex_mode(handle, hex_mode);
+            Ok(handle)
+        } else {
+            Err(DataServiceError::Socket(Error::Illegal).into())
+        }
+    }
+
+    /// Connect the TCP socket `handle` to `remote`, using the security profile
+    /// `profile_id` for the SSL/TLS connection (+USOSEC)
+    ///
+    /// The profile is configured with
+    /// [`configure_security_profile`](crate::GsmClient::configure_security_profile).
+    /// If given, `hostname` is set as the SNI of the profile first, so the
+    /// module requests the certificate of the intended host. A failed
+    /// handshake, e.g. on a hostname mismatch, fails with
+    /// [`DeviceError::TlsHandshakeFailed`], while other failures to connect
+    /// are returned as socket errors.
+    pub fn tcp_connect_secure(
+        &mut self,
+        handle: SocketHandle,
+        remote: SocketAddr,
+        profile_id: SecurityProfileId,
+        hostname: Option<&str>,
+    ) -> nb::Result<(), DeviceError> {
+        if let Some(hostname) = hostname {
             self.network
-                .send_internal(&CloseSocket { socket }, false)
-                .ok();
-            sockets.remove(socket)?;
+                .send_internal(
+                    &SecurityProfileManager {
+                        profile_id,
+                        operation: Some(SecurityProfileOperation::ServerNameIndication(bounded(
+                            hostname,
+                        )?)),
+                    },
+                    true,
+                )
+                .map_err(DeviceError::from)?;
+        }
+
+        match self.connect_with_security(handle, remote, profile_id) {
+            Err(nb::Error::Other(Error::Unaddressable)) => {
+                // The module reports the reason of the failed handshake as
+                // the socket error
+                match self.network.send_internal(&GetSocketError, false) {
+                    Ok(e) if TLS_SOCKET_ERRORS.contains(&e.error) => {
+                        Err(nb::Error::Other(DeviceError::TlsHandshakeFailed(e.error)))
+                    }
+                    _ => Err(nb::Error::Other(
+                        DataServiceError::Socket(Error::Unaddressable).into(),
+                    )),
+                }
+            }
+            res => res.map_err(|e| e.map(|e| DataServiceError::Socket(e).into())),
+        }
+    }
+
+    fn connect_with_security(
+        &mut self,
+        socket: SocketHandle,
+        remote: SocketAddr,
+        profile_id: SecurityProfileId,
+    ) -> nb::Result<(), Error> {
+        if let Some(ref mut sockets) = self.sockets {
+            let mut tcp = sockets.get::<TcpSocket<L>>(socket).map_err(Error::from)?;
+
+            if matches!(tcp.state(), TcpState::Created) {
+                self.network
+                    .send_internal(
+                        &SetSocketSslState {
+                            socket,
+                            ssl_tls_status: SslTlsStatus::Enabled(profile_id),
+                        },
+                        true,
+                    )
+                    .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
+
+                self.network
+                    .send_internal_timeout(
+                        &ConnectSocket {
+                            socket,
+                            remote_addr: remote.ip(),
+                            remote_port: remote.port(),
+                        },
+                        false,
+                        CONNECT_TIMEOUT,
+                    )
+                    .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
+
+                tcp.set_state(TcpState::Connected(remote));
+                Ok(())
+            } else {
+                error!(
+                    "Cannot connect socket! Socket: {:?} is in state: {:?}",
+                    socket,
+                    tcp.state()
+                );
+                Err(Error::Illegal.into())
+            }
+        } else {
+            Err(Error::Illegal.into())
+        }
+    }
+
+    /// Resolve `host` using +UDNSRN, and connect a new TCP socket to it on
+    /// `port`.
+    ///
+    /// If the host cannot be resolved, [`DeviceError::DnsResolutionFailed`] is
+    /// returned without allocating a socket.
+    pub fn tcp_connect_host(&mut self, host: &str, port: u16) -> Result<SocketHandle, DeviceError> {
+        let ip = self.network.resolve_host(host)?;
+
+        self.tcp_connect(SocketAddr::new(ip, port), None)
+    }
+
+    /// Write `data` to a connected TCP socket, in chunks of at most
+    /// [`EGRESS_CHUNK_SIZE`] bytes.
+    pub fn tcp_send(&mut self, mut handle: SocketHandle, data: &[u8]) -> nb::Result<usize, Error> {
+        TcpClientStack::send(self, &mut handle, data)
+    }
+
+    /// Check whether the data written to the TCP socket `handle` has been
+    /// acknowledged by the remote, querying the outgoing unacknowledged data
+    /// of the socket (+USOCTL)
+    ///
+    /// Returns `WouldBlock` while data is unacknowledged. Also returns once
+    /// the socket is closed, in which case unacknowledged data may have been
+    /// lost.
+    pub fn tcp_flush(&mut self, handle: SocketHandle) -> nb::Result<(), Error> {
+        let Some(ref mut sockets) = self.sockets else {
+            return Err(nb::Error::Other(Error::Illegal));
+        };
+        if !sockets.get::<TcpSocket<L>>(handle)?.is_connected() {
+            return Ok(());
+        }
+
+        let SocketControlResponse { param_val, .. } = self
+            .network
+            .send_internal(
+                &SocketControl {
+                    socket: handle,
+                    param_id: SocketControlParam::OutgoingUnackData,
+                },
+                false,
+            )
+            .map_err(|_| Error::Unaddressable)?;
+
+        if param_val == 0 {
             Ok(())
         } else {
-            Err(Error::Illegal)
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Enable TCP keepalive probes on the socket `handle` after it has been
+    /// idle for `interval`, or disable them with `None` (+USOSO)
+    ///
+    /// This keeps NAT mappings of idle connections alive. If the probes are
+    /// not answered, the module closes the socket, which is reported with
+    /// [`Event::SocketClosed`](crate::Event::SocketClosed).
+    pub fn tcp_set_keepalive(
+        &mut self,
+        handle: SocketHandle,
+        interval: Option<Duration>,
+    ) -> Result<(), Error> {
+        let Some(ref mut sockets) = self.sockets else {
+            return Err(Error::Illegal);
+        };
+        sockets.get::<TcpSocket<L>>(handle)?;
+
+        if let Some(interval) = interval {
+            let idle_ms = u32::try_from(interval.as_millis()).unwrap_or(u32::MAX);
+            self.network
+                .send_internal(
+                    &SetSocketOption {
+                        socket: handle,
+                        level: SocketOptionLevel::Tcp,
+                        option_name: TCP_KEEPIDLE,
+                        option_value: idle_ms,
+                    },
+                    false,
+                )
+                .map_err(|_| Error::Unaddressable)?;
+        }
+
+        self.network
+            .send_internal(
+                &SetSocketOption {
+                    socket: handle,
+                    level: SocketOptionLevel::Socket,
+                    option_name: SO_KEEPALIVE,
+                    option_value: u32::from(interval.is_some()),
+                },
+                false,
+            )
+            .map_err(|_| Error::Unaddressable)?;
+        Ok(())
+    }
+
+    /// Read already received data from a TCP socket into `buf`.
+    pub fn tcp_receive(
+        &mut self,
+        mut handle: SocketHandle,
+        buf: &mut [u8],
+    ) -> nb::Result<usize, Error> {
+        TcpClientStack::receive(self, &mut handle, buf)
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Open a new TCP socket and connect it to `remote`, on the data service
+    /// of [`CellularConfig::apn`]
+    ///
+    /// Fails with [`DeviceError::Busy`] while the data connection is not up
+    /// yet. See [`DataService::tcp_connect`] for the socket limit.
+    pub fn tcp_connect(&mut self, remote: SocketAddr) -> Result<SocketHandle, DeviceError> {
+        let mut socket = TcpClientStack::socket(self)?;
+
+        match nb::block!(TcpClientStack::connect(self, &mut socket, remote)) {
+            Ok(()) => Ok(socket),
+            Err(e) => {
+                TcpClientStack::close(self, socket).ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Write `data` to the connected TCP socket `handle`, in chunks of at most
+    /// [`EGRESS_CHUNK_SIZE`] bytes
+    pub fn tcp_send(
+        &mut self,
+        mut handle: SocketHandle,
+        data: &[u8],
+    ) -> nb::Result<usize, DeviceError> {
+        TcpClientStack::send(self, &mut handle, data)
+    }
+
+    /// Read already received data from the TCP socket `handle` into `buf`
+    ///
+    /// Returns `WouldBlock` while no data has been received, and `Ok(0)` once
+    /// the socket was closed by the remote.
+    pub fn tcp_receive(
+        &mut self,
+        mut handle: SocketHandle,
+        buf: &mut [u8],
+    ) -> nb::Result<usize, DeviceError> {
+        TcpClientStack::receive(self, &mut handle, buf)
+    }
+
+    /// Read data from the TCP socket `handle` into `buf`, waiting for data to
+    /// arrive until `deadline`
+    ///
+    /// URCs and +USORD are polled while waiting. Returns `Ok(0)` if no data
+    /// arrived before the deadline, or the socket was closed by the remote.
+    pub fn tcp_receive_deadline(
+        &mut self,
+        handle: SocketHandle,
+        buf: &mut [u8],
+        deadline: Instant,
+    ) -> Result<usize, DeviceError> {
+        loop {
+            match self.tcp_receive(handle, buf) {
+                Ok(n) => return Ok(n),
+                Err(nb::Error::WouldBlock) => {}
+                Err(nb::Error::Other(e)) => return Err(e),
+            }
+
+            if Instant::now() >= deadline {
+                return Ok(0);
+            }
+
+            self.process_urcs()?;
+            BlockingTimer::after(RECEIVE_POLL_INTERVAL).wait();
         }
     }
 }
+
+/// TCP stack on the data service of [`CellularConfig::apn`], which is set up
+/// on demand. While the data connection is not up yet, the non-blocking
+/// methods return `WouldBlock`, and the others [`DeviceError::Busy`].
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize> TcpClientStack
+    for Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    type Error = DeviceError;
+
+    type TcpSocket = SocketHandle;
+
+    fn socket(&mut self) -> Result<Self::TcpSocket, Self::Error> {
+        self.with_data_service(|service| TcpClientStack::socket(service).map_err(nb::Error::Other))
+            .map_err(not_ready)
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        remote: SocketAddr,
+    ) -> nb::Result<(), Self::Error> {
+        self.with_data_service(|service| TcpClientStack::connect(service, socket, remote))
+    }
+
+    fn is_connected(&mut self, socket: &Self::TcpSocket) -> Result<bool, Self::Error> {
+        self.with_data_service(|service| {
+            TcpClientStack::is_connected(service, socket).map_err(nb::Error::Other)
+        })
+        .map_err(not_ready)
+    }
+
+    fn send(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &[u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.with_data_service(|service| TcpClientStack::send(service, socket, buffer))
+    }
+
+    fn receive(
+        &mut self,
+        socket: &mut Self::TcpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<usize, Self::Error> {
+        self.with_data_service(|service| {
+            match TcpClientStack::receive(service, socket, buffer)? {
+                // Nothing received yet, rather than the end of the stream
+                0 if !buffer.is_empty() && TcpClientStack::is_connected(service, socket)? => {
+                    Err(nb::Error::WouldBlock)
+                }
+                n => Ok(n),
+            }
+        })
+    }
+
+    fn close(&mut self, socket: Self::TcpSocket) -> Result<(), Self::Error> {
+        self.with_data_service(|service| service.close(socket).map_err(nb::Error::Other))
+            .map_err(not_ready)
+    }
+}
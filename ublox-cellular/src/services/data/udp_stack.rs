@@ -1,9 +1,14 @@
-use super::DataService;
-use super::EGRESS_CHUNK_SIZE;
+use super::hex;
+use super::Error as DataServiceError;
+use super::{create_socket_error, not_ready, socket_error, DataService};
+use super::{EGRESS_CHUNK_SIZE, INGRESS_CHUNK_SIZE, MAX_SOCKETS};
+use crate::client::Device;
 use crate::command::ip_transport_layer::{
-    types::SocketProtocol, CloseSocket, CreateSocket, PrepareUDPSendToDataBinary,
-    UDPSendToDataBinary,
+    responses::UDPSocketData, types::SocketProtocol, CreateSocket, PrepareUDPSendToDataBinary,
+    ReadUDPSocketData, UDPSendToDataBinary,
 };
+use crate::config::CellularConfig;
+use crate::error::Error as DeviceError;
 use atat::blocking::AtatClient;
 use embedded_nal::{SocketAddr, UdpClientStack};
 use ublox_sockets::{Error, SocketHandle, UdpSocket};
@@ -22,12 +27,92 @@ where
     /// Open a new UDP socket to the given address and port. UDP is connectionless,
     /// so unlike `TcpStack` no `connect()` is required.
     fn socket(&mut self) -> Result<Self::UdpSocket, Self::Error> {
+        self.udp_socket(None, self.hex_mode).map_err(socket_error)
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::UdpSocket,
+        remote: SocketAddr,
+    ) -> Result<(), Self::Error> {
+        if let Some(ref mut sockets) = self.sockets {
+            let mut udp = sockets
+                .get::<UdpSocket<L>>(*socket)
+                .map_err(Self::Error::from)?;
+            udp.bind(remote).map_err(Self::Error::from)?;
+            Ok(())
+        } else {
+            Err(Error::Illegal)
+        }
+    }
+
+    /// Send a datagram to the remote host.
+    fn send(&mut self, socket: &mut Self::UdpSocket, buffer: &[u8]) -> nb::Result<(), Self::Error> {
+        let endpoint = if let Some(ref mut sockets) = self.sockets {
+            let udp = sockets
+                .get::<UdpSocket<L>>(*socket)
+                .map_err(Self::Error::from)?;
+
+            udp.endpoint().ok_or(Error::SocketClosed)?
+        } else {
+            return Err(Error::Illegal.into());
+        };
+
+        self.udp_send_to(*socket, endpoint, buffer)
+    }
+
+    /// Read a datagram the remote host has sent to us. Returns `Ok(n)`, which
+    /// means a datagram of size `n` has been received and it has been placed
+    /// in `&buffer[0..n]`, or an error.
+    fn receive(
+        &mut self,
+        socket: &mut Self::UdpSocket,
+        buffer: &mut [u8],
+    ) -> nb::Result<(usize, SocketAddr), Self::Error> {
+        self.udp_receive_from(*socket, buffer)
+    }
+
+    /// Close an existing UDP socket.
+    fn close(&mut self, socket: Self::UdpSocket) -> Result<(), Self::Error> {
+        DataService::close(self, socket)
+    }
+}
+
+impl<'a, 'sub, AtCl, const N: usize, const L: usize> DataService<'a, 'sub, AtCl, N, L>
+where
+    AtCl: AtatClient,
+{
+    /// Open a new UDP socket, optionally bound to `local_port`.
+    ///
+    /// At most [`MAX_SOCKETS`] sockets can be open at the same time, after
+    /// which [`Error::SocketSetFull`] is returned. If `local_port` is bound by
+    /// another socket, [`DeviceError::PortInUse`] is returned.
+    pub fn udp_open(&mut self, local_port: Option<u16>) -> Result<SocketHandle, DeviceError> {
+        self.udp_open_with_hex_mode(local_port, self.hex_mode)
+    }
+
+    /// Like [`udp_open`](Self::udp_open), but with the datagrams of the
+    /// socket received in HEX mode or not, instead of as set by
+    /// [`CellularConfig::HEX_MODE`]
+    pub fn udp_open_with_hex_mode(
+        &mut self,
+        local_port: Option<u16>,
+        hex_mode: bool,
+    ) -> Result<SocketHandle, DeviceError> {
+        self.udp_socket(local_port, hex_mode)
+    }
+
+    fn udp_socket(
+        &mut self,
+        local_port: Option<u16>,
+        hex_mode: bool,
+    ) -> Result<SocketHandle, DeviceError> {
         if let Some(ref mut sockets) = self.sockets {
-            if sockets.len() >= sockets.capacity() {
+            if sockets.len() >= sockets.capacity().min(MAX_SOCKETS) {
                 // Check if there are any sockets closed by remote, and close it
                 // if it has exceeded its timeout, in order to recycle it.
                 if !sockets.recycle() {
-                    return Err(Error::SocketSetFull);
+                    return Err(DataServiceError::Socket(Error::SocketSetFull).into());
                 }
             }
 
@@ -36,116 +121,193 @@ where
                 .send_internal(
                     &CreateSocket {
                         protocol: SocketProtocol::UDP,
-                        local_port: None,
+                        local_port,
                     },
                     false,
                 )
-                .map_err(|_| Error::Unaddressable)?;
+                .map_err(|e| create_socket_error(self.network, e, local_port))?;
 
-            Ok(sockets.add(UdpSocket::new(socket_resp.socket.0))?)
+            let handle = sockets
+                .add(UdpSocket::new(socket_resp.socket.0))
+                .map_err(|e| DataServiceError::Socket(e.into()))?;
+            self.network.set_socket_hex_mode(handle, hex_mode);
+            Ok(handle)
         } else {
-            Err(Error::Illegal)
+            Err(DataServiceError::Socket(Error::Illegal).into())
         }
     }
 
-    fn connect(
+    /// Send `data` as a single datagram to `remote`, using +USOST.
+    ///
+    /// Fails with [`Error::BadLength`] if `data` exceeds
+    /// [`EGRESS_CHUNK_SIZE`] bytes, the maximum datagram size.
+    pub fn udp_send_to(
         &mut self,
-        socket: &mut Self::UdpSocket,
+        handle: SocketHandle,
         remote: SocketAddr,
-    ) -> Result<(), Self::Error> {
+        data: &[u8],
+    ) -> nb::Result<(), Error> {
+        if data.len() > EGRESS_CHUNK_SIZE {
+            return Err(Error::BadLength.into());
+        }
+
         if let Some(ref mut sockets) = self.sockets {
-            let mut udp = sockets
-                .get::<UdpSocket<L>>(*socket)
-                .map_err(Self::Error::from)?;
-            udp.bind(remote).map_err(Self::Error::from)?;
-            Ok(())
+            let udp = sockets.get::<UdpSocket<L>>(handle).map_err(Error::from)?;
+
+            if !udp.is_open() {
+                return Err(Error::SocketClosed.into());
+            }
         } else {
-            Err(Error::Illegal)
+            return Err(Error::Illegal.into());
+        }
+
+        trace!("Sending: {} bytes", data.len());
+        self.network
+            .send_internal(
+                &PrepareUDPSendToDataBinary {
+                    socket: handle,
+                    remote_addr: remote.ip(),
+                    remote_port: remote.port(),
+                    length: data.len(),
+                },
+                false,
+            )
+            .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
+
+        let response = self
+            .network
+            .send_internal(
+                &UDPSendToDataBinary {
+                    data: atat::serde_bytes::Bytes::new(data),
+                },
+                false,
+            )
+            .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
+
+        if response.length != data.len() {
+            return Err(Error::BadLength.into());
         }
+        if response.socket != handle {
+            return Err(Error::InvalidSocket.into());
+        }
+
+        Ok(())
     }
 
-    /// Send a datagram to the remote host.
-    fn send(&mut self, socket: &mut Self::UdpSocket, buffer: &[u8]) -> nb::Result<(), Self::Error> {
+    /// Read a single queued datagram into `buf` using +USORF, returning its
+    /// length along with the address it was sent from.
+    ///
+    /// If more datagrams are queued, they are returned one by one on
+    /// subsequent calls.
+    pub fn udp_receive_from(
+        &mut self,
+        handle: SocketHandle,
+        buf: &mut [u8],
+    ) -> nb::Result<(usize, SocketAddr), Error> {
         if let Some(ref mut sockets) = self.sockets {
-            let udp = sockets
-                .get::<UdpSocket<L>>(*socket)
-                .map_err(Self::Error::from)?;
+            let mut udp = sockets.get::<UdpSocket<L>>(handle).map_err(Error::from)?;
 
-            if !udp.is_open() {
-                return Err(Error::SocketClosed.into());
+            let available_data = udp.available_data();
+            if available_data == 0 {
+                return Err(nb::Error::WouldBlock);
             }
 
-            for chunk in buffer.chunks(EGRESS_CHUNK_SIZE) {
-                trace!("Sending: {} bytes", chunk.len());
-                let endpoint = udp.endpoint().ok_or(Error::SocketClosed)?;
-                self.network
-                    .send_internal(
-                        &PrepareUDPSendToDataBinary {
-                            socket: *socket,
-                            remote_addr: endpoint.ip(),
-                            remote_port: endpoint.port(),
-                            length: chunk.len(),
-                        },
-                        false,
-                    )
-                    .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
-
-                let response = self
-                    .network
-                    .send_internal(
-                        &UDPSendToDataBinary {
-                            data: atat::serde_bytes::Bytes::new(chunk),
-                        },
-                        false,
-                    )
-                    .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
-
-                if response.length != chunk.len() {
-                    return Err(Error::BadLength.into());
-                }
-                if &response.socket != socket {
-                    return Err(Error::InvalidSocket.into());
-                }
+            let hex_mode = self
+                .network
+                .select_hex_mode(handle)
+                .map_err(|_| Error::Unaddressable)?;
+            let UDPSocketData {
+                socket,
+                remote_addr,
+                remote_port,
+                length,
+                data,
+            } = self
+                .network
+                .send_internal(
+                    &ReadUDPSocketData {
+                        socket: handle,
+                        length: buf.len().min(INGRESS_CHUNK_SIZE),
+                    },
+                    false,
+                )
+                .map_err(|_| nb::Error::Other(Error::Unaddressable))?;
+
+            if socket != handle {
+                return Err(Error::InvalidSocket.into());
             }
 
-            Ok(())
+            if length == 0 {
+                udp.set_available_data(0);
+                return Err(nb::Error::WouldBlock);
+            }
+            udp.set_available_data(available_data.saturating_sub(length));
+
+            let mut data = data.ok_or(Error::Exhausted)?;
+            let payload = if hex_mode {
+                hex::from_hex(unsafe { data.as_bytes_mut() }).map_err(|_| Error::BadLength)?
+            } else {
+                data.as_bytes()
+            };
+            if payload.len() != length || payload.len() > buf.len() {
+                return Err(Error::BadLength.into());
+            }
+
+            buf[..length].copy_from_slice(payload);
+            Ok((length, SocketAddr::new(remote_addr, remote_port)))
         } else {
             Err(Error::Illegal.into())
         }
     }
+}
+
+/// UDP stack on the data service of [`CellularConfig::apn`], which is set up
+/// on demand. While the data connection is not up yet, the non-blocking
+/// methods return `WouldBlock`, and the others [`DeviceError::Busy`].
+///
+/// UDP and TCP sockets share the socket set, so at most [`MAX_SOCKETS`]
+/// sockets of either type are open at the same time.
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize> UdpClientStack
+    for Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    type Error = DeviceError;
+
+    type UdpSocket = SocketHandle;
+
+    fn socket(&mut self) -> Result<Self::UdpSocket, Self::Error> {
+        self.with_data_service(|service| UdpClientStack::socket(service).map_err(nb::Error::Other))
+            .map_err(not_ready)
+    }
+
+    fn connect(
+        &mut self,
+        socket: &mut Self::UdpSocket,
+        remote: SocketAddr,
+    ) -> Result<(), Self::Error> {
+        self.with_data_service(|service| {
+            UdpClientStack::connect(service, socket, remote).map_err(nb::Error::Other)
+        })
+        .map_err(not_ready)
+    }
+
+    fn send(&mut self, socket: &mut Self::UdpSocket, buffer: &[u8]) -> nb::Result<(), Self::Error> {
+        self.with_data_service(|service| UdpClientStack::send(service, socket, buffer))
+    }
 
-    /// Read a datagram the remote host has sent to us. Returns `Ok(n)`, which
-    /// means a datagram of size `n` has been received and it has been placed
-    /// in `&buffer[0..n]`, or an error.
     fn receive(
         &mut self,
         socket: &mut Self::UdpSocket,
         buffer: &mut [u8],
     ) -> nb::Result<(usize, SocketAddr), Self::Error> {
-        if let Some(ref mut sockets) = self.sockets {
-            let mut udp = sockets
-                .get::<UdpSocket<L>>(*socket)
-                .map_err(Self::Error::from)?;
-
-            let bytes = udp.recv_slice(buffer).map_err(Self::Error::from)?;
-
-            let endpoint = udp.endpoint().ok_or(Error::SocketClosed)?;
-            Ok((bytes, endpoint))
-        } else {
-            Err(Error::Illegal.into())
-        }
+        self.with_data_service(|service| UdpClientStack::receive(service, socket, buffer))
     }
 
-    /// Close an existing UDP socket.
     fn close(&mut self, socket: Self::UdpSocket) -> Result<(), Self::Error> {
-        if let Some(ref mut sockets) = self.sockets {
-            self.network
-                .send_internal(&CloseSocket { socket }, false)
-                .ok();
-            sockets.remove(socket)?;
-            Ok(())
-        } else {
-            Err(Error::Illegal)
-        }
+        self.with_data_service(|service| service.close(socket).map_err(nb::Error::Other))
+            .map_err(not_ready)
     }
 }
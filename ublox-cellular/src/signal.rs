@@ -0,0 +1,112 @@
+use crate::command::network_service::responses::{
+    SignalQuality as ExtendedSignalQuality, SignalStrength,
+};
+
+/// Radio signal quality of the serving cell.
+///
+/// Metrics that are not known, or not applicable to the RAT of the serving
+/// cell, are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SignalQuality {
+    /// Received signal strength indicator, in dBm
+    pub rssi: Option<i16>,
+    /// Reference signal received power (LTE only), in dBm
+    pub rsrp: Option<i16>,
+    /// Reference signal received quality (LTE only), in dB
+    pub rsrq: Option<i16>,
+    /// Signal to interference plus noise ratio, in dB
+    ///
+    /// Not reported by +CSQ / +CESQ, and thus currently always `None`.
+    pub sinr: Option<i16>,
+}
+
+impl SignalQuality {
+    pub(crate) fn new(csq: &SignalStrength, cesq: Option<&ExtendedSignalQuality>) -> Self {
+        Self {
+            rssi: rssi_dbm(csq.signal_power),
+            rsrp: cesq.and_then(|q| rsrp_dbm(q.rsrp)),
+            rsrq: cesq.and_then(|q| rsrq_db(q.rsrq)),
+            sinr: None,
+        }
+    }
+}
+
+/// +CSQ <signal_power>: 0 is -113 dBm or less, 31 is -51 dBm or greater, 99 is
+/// unknown.
+fn rssi_dbm(signal_power: u8) -> Option<i16> {
+    match signal_power {
+        0..=31 => Some(2 * signal_power as i16 - 113),
+        _ => None,
+    }
+}
+
+/// +CESQ <rsrp>: 0 is less than -140 dBm, 97 is -44 dBm or greater, 255 is
+/// unknown.
+fn rsrp_dbm(rsrp: u8) -> Option<i16> {
+    match rsrp {
+        0..=97 => Some(rsrp as i16 - 141),
+        _ => None,
+    }
+}
+
+/// +CESQ <rsrq>: 0 is less than -19.5 dB, 34 is -3 dB or greater in steps of
+/// 0.5 dB, 255 is unknown.
+fn rsrq_db(rsrq: u8) -> Option<i16> {
+    match rsrq {
+        0..=34 => Some(rsrq as i16 / 2 - 20),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown_values_are_none() {
+        let csq = SignalStrength {
+            signal_power: 99,
+            qual: 99,
+        };
+        let cesq = ExtendedSignalQuality {
+            rxlev: 99,
+            ber: 99,
+            rscp: 255,
+            ecn0: 255,
+            rsrq: 255,
+            rsrp: 255,
+        };
+
+        assert_eq!(
+            SignalQuality::new(&csq, Some(&cesq)),
+            SignalQuality::default()
+        );
+    }
+
+    #[test]
+    fn lte_values() {
+        let csq = SignalStrength {
+            signal_power: 20,
+            qual: 99,
+        };
+        let cesq = ExtendedSignalQuality {
+            rxlev: 99,
+            ber: 99,
+            rscp: 255,
+            ecn0: 255,
+            rsrq: 24,
+            rsrp: 50,
+        };
+
+        assert_eq!(
+            SignalQuality::new(&csq, Some(&cesq)),
+            SignalQuality {
+                rssi: Some(-73),
+                rsrp: Some(-91),
+                rsrq: Some(-8),
+                sinr: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use atat::blocking::AtatClient;
+use embassy_time::Instant;
+use heapless::{String, Vec};
+
+use crate::{
+    client::Device,
+    command::sms::{
+        responses::Message, types::MessageFormatMode, DeleteMessage, GetMessageFormat,
+        GetServiceCenterAddress, PrepareSendMessage, PrepareSendPdu, ReadMessage, SendMessageBody,
+        SetMessageFormat, SetServiceCenterAddress,
+    },
+    config::CellularConfig,
+    error::Error,
+    pdu::{self, Concatenation, Encoding},
+    services::data::Error as DataServiceError,
+};
+
+/// Maximum length of a single text message, in GSM 7 bit characters
+pub const MAX_SMS_LENGTH: usize = 160;
+
+/// Ctrl-Z, terminating the message body
+const CTRL_Z: u8 = 0x1A;
+
+/// Characters of the GSM 7 bit default alphabet extension table, which take
+/// up two characters (escape + character) in a message
+const GSM7_EXTENSION: [char; 10] = ['\u{c}', '^', '{', '}', '\\', '[', '~', ']', '|', '€'];
+
+/// Memory storage of a received SMS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MessageStorage {
+    /// "SM": SIM card
+    Sim,
+    /// "ME": module memory
+    Module,
+    /// "MT": SIM card or module memory
+    Any,
+    /// "BM": broadcast messages
+    Broadcast,
+    /// "SR": status reports
+    StatusReport,
+    Unknown,
+}
+
+impl From<&str> for MessageStorage {
+    fn from(mem: &str) -> Self {
+        match mem {
+            "SM" => Self::Sim,
+            "ME" => Self::Module,
+            "MT" => Self::Any,
+            "BM" => Self::Broadcast,
+            "SR" => Self::StatusReport,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Received text message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsMessage {
+    /// Phone number of the sender
+    pub sender: String<20>,
+    /// Service center time stamp, "yy/MM/dd,hh:mm:ss+zz"
+    pub timestamp: String<20>,
+    pub body: String<MAX_SMS_LENGTH>,
+}
+
+impl From<Message> for SmsMessage {
+    fn from(msg: Message) -> Self {
+        Self {
+            sender: msg.oa,
+            timestamp: msg.scts,
+            body: msg.data,
+        }
+    }
+}
+
+/// Address of the SMS service center (SMSC), see [`smsc`](Device::smsc)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmscAddress {
+    /// Phone number of the service center, e.g. "+4540590000"
+    pub number: String<20>,
+    /// Type of address, e.g. 145 for international numbers
+    pub type_of_address: Option<u8>,
+}
+
+/// Length of `text` in GSM 7 bit characters
+fn gsm7_len(text: &str) -> usize {
+    text.chars()
+        .map(|c| if GSM7_EXTENSION.contains(&c) { 2 } else { 1 })
+        .sum()
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Send `text` as a text message to `number`
+    ///
+    /// Fails with [`Error::MessageTooLong`] if `text` is longer than
+    /// [`MAX_SMS_LENGTH`] GSM 7 bit characters. Text mode only carries the
+    /// GSM 7 bit alphabet, so texts with other characters are sent as UCS2
+    /// with [`send_sms_pdu`](Device::send_sms_pdu) instead. The message format
+    /// selected before the call is restored afterwards.
+    pub fn send_sms(&mut self, number: &str, text: &str) -> Result<(), Error> {
+        if Encoding::of(text) != Encoding::Gsm7 {
+            return self.send_sms_pdu(number, text);
+        }
+        if gsm7_len(text) > MAX_SMS_LENGTH {
+            return Err(Error::MessageTooLong);
+        }
+
+        let mut body = Vec::<u8, { 4 * MAX_SMS_LENGTH + 1 }>::new();
+        body.extend_from_slice(text.as_bytes())
+            .map_err(|_| Error::MessageTooLong)?;
+        body.push(CTRL_Z).map_err(|_| Error::MessageTooLong)?;
+
+        let reference = self.in_message_format(MessageFormatMode::Text, |device| {
+            device.send_at(&PrepareSendMessage { da: number })?;
+            device.send_at(&SendMessageBody {
+                data: atat::serde_bytes::Bytes::new(&body),
+            })
+        })?;
+
+        debug!("Sent SMS, message reference: {}", reference.mr);
+        Ok(())
+    }
+
+    /// Send `text` as a message to `number`, in PDU mode
+    ///
+    /// The text is encoded as UCS2 if it contains characters outside the GSM 7
+    /// bit alphabet. Longer texts are sent as a concatenated message of up to
+    /// [`MAX_SMS_PARTS`](crate::MAX_SMS_PARTS) parts, failing with
+    /// [`Error::MessageTooLong`] if more are needed. The message format
+    /// selected before the call is restored afterwards.
+    pub fn send_sms_pdu(&mut self, number: &str, text: &str) -> Result<(), Error> {
+        let encoding = Encoding::of(text);
+        let parts = pdu::split(text, encoding).ok_or(Error::MessageTooLong)?;
+        let reference = Instant::now().as_ticks() as u8;
+
+        self.in_message_format(MessageFormatMode::Pdu, |device| {
+            for (i, &(start, end)) in parts.iter().enumerate() {
+                let concatenation = (parts.len() > 1).then(|| Concatenation {
+                    reference,
+                    total: parts.len() as u8,
+                    sequence: i as u8 + 1,
+                });
+                let pdu = pdu::encode(number, &text[start..end], encoding, concatenation)
+                    .ok_or(Error::DataService(DataServiceError::BadLength))?;
+
+                let mut body = Vec::<u8, 641>::new();
+                body.extend_from_slice(pdu.hex.as_bytes())
+                    .map_err(|_| Error::MessageTooLong)?;
+                body.push(CTRL_Z).map_err(|_| Error::MessageTooLong)?;
+
+                device.send_at(&PrepareSendPdu {
+                    length: pdu.tpdu_len,
+                })?;
+                let sent = device.send_at(&SendMessageBody {
+                    data: atat::serde_bytes::Bytes::new(&body),
+                })?;
+                debug!(
+                    "Sent SMS part {}/{}, message reference: {}",
+                    i + 1,
+                    parts.len(),
+                    sent.mr
+                );
+            }
+            Ok(())
+        })
+    }
+
+    /// Read the SMS stored at `index`, e.g. as reported by
+    /// [`Event::SmsReceived`](crate::Event::SmsReceived)
+    pub fn read_sms(&mut self, index: u8) -> Result<SmsMessage, Error> {
+        let msg = self.in_message_format(MessageFormatMode::Text, |device| {
+            device.send_at(&ReadMessage { index })
+        })?;
+        Ok(msg.into())
+    }
+
+    /// Delete the SMS stored at `index`
+    pub fn delete_sms(&mut self, index: u8) -> Result<(), Error> {
+        self.send_at(&DeleteMessage { index })?;
+        Ok(())
+    }
+
+    /// Get the SMS service center address (+CSCA)
+    ///
+    /// `None` if no service center is provisioned on the SIM, in which case
+    /// it has to be set with [`set_smsc`](Device::set_smsc) to send messages.
+    pub fn smsc(&mut self) -> Result<Option<SmscAddress>, Error> {
+        let address = self.send_at(&GetServiceCenterAddress)?;
+        if address.sca.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(SmscAddress {
+            number: address.sca,
+            type_of_address: address.tosca,
+        }))
+    }
+
+    /// Set the SMS service center address to `number` (+CSCA)
+    pub fn set_smsc(&mut self, number: &str) -> Result<(), Error> {
+        if number.is_empty() || number.len() > 20 {
+            return Err(Error::DataService(DataServiceError::BadLength));
+        }
+
+        self.send_at(&SetServiceCenterAddress { sca: number })?;
+        Ok(())
+    }
+
+    /// Run `f` with the message format `mode` selected, restoring the message
+    /// format selected before afterwards
+    fn in_message_format<T>(
+        &mut self,
+        mode: MessageFormatMode,
+        f: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let prior = self.send_at(&GetMessageFormat)?.mode;
+        if prior != mode {
+            self.send_at(&SetMessageFormat { mode })?;
+        }
+
+        let res = f(self);
+
+        if prior != mode {
+            self.send_at(&SetMessageFormat { mode: prior }).ok();
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gsm7_length() {
+        assert_eq!(gsm7_len("Hello"), 5);
+        assert_eq!(gsm7_len("{x}"), 5);
+        assert_eq!(gsm7_len("5€"), 3);
+        assert_eq!(gsm7_len(""), 0);
+    }
+}
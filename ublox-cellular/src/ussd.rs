@@ -0,0 +1,106 @@
+use atat::blocking::AtatClient;
+use embassy_time::{Duration, Instant};
+use heapless::String;
+
+use crate::{
+    blocking_timer::BlockingTimer,
+    client::Device,
+    command::supplementary_services::{
+        types::{UssdMode, UssdStatus},
+        urc::UnstructuredSupplementaryServiceData,
+        SetUssd,
+    },
+    config::CellularConfig,
+    error::{Error, GenericError},
+};
+
+/// Time to wait for the network to answer a USSD request
+const USSD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Data Coding Scheme of the GSM 7 bit default alphabet
+const USSD_DCS_GSM7: u8 = 15;
+
+/// Network answer to a USSD request, see [`send_ussd`](Device::send_ussd)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UssdResponse {
+    pub text: String<182>,
+    /// Whether the network expects a reply, see
+    /// [`ussd_reply`](Device::ussd_reply). Otherwise the session is
+    /// terminated.
+    pub action_required: bool,
+}
+
+impl TryFrom<UnstructuredSupplementaryServiceData> for UssdResponse {
+    type Error = Error;
+
+    fn try_from(data: UnstructuredSupplementaryServiceData) -> Result<Self, Self::Error> {
+        let action_required = match data.m {
+            UssdStatus::FurtherActionRequired => true,
+            UssdStatus::NoFurtherAction
+            | UssdStatus::TerminatedByNetwork
+            | UssdStatus::OtherClientResponded => false,
+            UssdStatus::NotSupported => return Err(Error::Generic(GenericError::Unsupported)),
+            UssdStatus::NetworkTimeout => return Err(Error::Generic(GenericError::Timeout)),
+        };
+
+        Ok(Self {
+            text: data.str.unwrap_or_default(),
+            action_required,
+        })
+    }
+}
+
+impl<'buf, 'sub, AtCl, AtUrcCh, Config, const N: usize, const L: usize>
+    Device<'buf, 'sub, AtCl, AtUrcCh, Config, N, L>
+where
+    'buf: 'sub,
+    AtCl: AtatClient,
+    Config: CellularConfig,
+{
+    /// Send the USSD `code`, e.g. `"*100#"`, and wait for the network answer
+    ///
+    /// If [`UssdResponse::action_required`] is set, the session stays open
+    /// for [`ussd_reply`](Device::ussd_reply) until it is cancelled with
+    /// [`cancel_ussd`](Device::cancel_ussd).
+    pub fn send_ussd(&mut self, code: &str) -> Result<UssdResponse, Error> {
+        self.ussd_request(code)
+    }
+
+    /// Reply `text` to the network in an interactive USSD session
+    pub fn ussd_reply(&mut self, text: &str) -> Result<UssdResponse, Error> {
+        self.ussd_request(text)
+    }
+
+    /// Cancel the ongoing USSD session
+    pub fn cancel_ussd(&mut self) -> Result<(), Error> {
+        self.send_at(&SetUssd {
+            n: UssdMode::Cancel,
+            str: None,
+            dcs: None,
+        })?;
+        self.network.ussd_response = None;
+        Ok(())
+    }
+
+    fn ussd_request(&mut self, text: &str) -> Result<UssdResponse, Error> {
+        self.network.ussd_response = None;
+
+        self.send_at(&SetUssd {
+            n: UssdMode::Enabled,
+            str: Some(text),
+            dcs: Some(USSD_DCS_GSM7),
+        })?;
+
+        // The answer is returned with the +CUSD URC
+        let start = Instant::now();
+        while start.elapsed() < USSD_TIMEOUT {
+            self.network.handle_urc().ok();
+            if let Some(data) = self.network.ussd_response.take() {
+                return data.try_into();
+            }
+            BlockingTimer::after(Duration::from_millis(100)).wait();
+        }
+
+        Err(Error::Generic(GenericError::Timeout))
+    }
+}